@@ -3,8 +3,8 @@
 mod types;
 
 pub use types::{
-    Finding, ReportDisplay, ReportDisplayOpts, Severity, VerifierOptionsLike, VerifyPhases,
-    VerifyReport,
+    Finding, Outcome, ReportDisplay, ReportDisplayOpts, Severity, VerifierOptionsLike,
+    VerifyPhases, VerifyReport,
 };
 
 pub mod stats;
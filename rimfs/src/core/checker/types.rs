@@ -1,5 +1,5 @@
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
-use alloc::{string::String, vec::Vec};
+use alloc::{format, string::String, vec::Vec};
 use core::cmp::Ordering;
 
 use core::fmt;
@@ -64,6 +64,28 @@ impl Finding {
     }
 }
 
+/// Overall verdict of a [`VerifyReport`], driven by its worst finding.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Nothing above `Info`.
+    Clean,
+    /// At least one warning, no errors.
+    Warnings,
+    /// At least one error.
+    Errors,
+}
+
+impl Outcome {
+    /// Process exit code for tools: 0 clean, 1 warnings, 2 errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Outcome::Clean => 0,
+            Outcome::Warnings => 1,
+            Outcome::Errors => 2,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct VerifyReport {
     pub findings: Vec<Finding>,
@@ -87,6 +109,37 @@ impl VerifyReport {
         !self.has_error()
     }
 
+    pub fn outcome(&self) -> Outcome {
+        match self.findings.iter().map(|f| f.sev).max() {
+            Some(Severity::Error) => Outcome::Errors,
+            Some(Severity::Warn) => Outcome::Warnings,
+            _ => Outcome::Clean,
+        }
+    }
+
+    /// One-line verdict, e.g. `"FAILED: 2 errors, 1 warning"`.
+    pub fn conclusion(&self) -> String {
+        let plural = |n: usize, what: &str| {
+            if n == 1 {
+                format!("{n} {what}")
+            } else {
+                format!("{n} {what}s")
+            }
+        };
+        match self.outcome() {
+            Outcome::Clean => "OK: no problems found".into(),
+            Outcome::Warnings => format!(
+                "OK with warnings: {}",
+                plural(self.count(Severity::Warn), "warning")
+            ),
+            Outcome::Errors => format!(
+                "FAILED: {}, {}",
+                plural(self.count(Severity::Error), "error"),
+                plural(self.count(Severity::Warn), "warning")
+            ),
+        }
+    }
+
     pub fn push(&mut self, f: Finding) {
         self.findings.push(f)
     }
@@ -253,3 +306,37 @@ impl Default for CoreVerifyOptions {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(findings: Vec<Finding>) -> VerifyReport {
+        VerifyReport { findings }
+    }
+
+    #[test]
+    fn outcome_follows_worst_finding() {
+        let clean = report(vec![Finding::info("I1", "fine")]);
+        assert_eq!(clean.outcome(), Outcome::Clean);
+        assert_eq!(report(vec![]).outcome(), Outcome::Clean);
+        assert_eq!(clean.conclusion(), "OK: no problems found");
+
+        let warned = report(vec![
+            Finding::info("I1", "fine"),
+            Finding::warn("W1", "hmm"),
+        ]);
+        assert_eq!(warned.outcome(), Outcome::Warnings);
+        assert_eq!(warned.conclusion(), "OK with warnings: 1 warning");
+        assert!(warned.ok());
+
+        let failed = report(vec![
+            Finding::err("E1", "bad"),
+            Finding::warn("W1", "hmm"),
+            Finding::err("E2", "worse"),
+        ]);
+        assert_eq!(failed.outcome(), Outcome::Errors);
+        assert_eq!(failed.conclusion(), "FAILED: 2 errors, 1 warning");
+        assert_eq!(failed.outcome().exit_code(), 2);
+    }
+}
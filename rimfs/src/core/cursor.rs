@@ -21,6 +21,19 @@ pub trait ClusterMeta: FsMeta<u32> {
     }
 
     fn num_fats(&self) -> u8;
+
+    /// Byte offset of `cluster`, rejecting values outside the cluster heap.
+    ///
+    /// Use it on cluster numbers read from disk so a corrupt pointer fails
+    /// instead of producing a wild offset. System clusters (bitmap, up-case,
+    /// root) are accepted, like `ClusterCursor::new` does.
+    fn try_unit_offset(&self, cluster: u32) -> FsCursorResult<u64> {
+        if (Self::FIRST_CLUSTER..=self.last_data_unit()).contains(&cluster) {
+            Ok(self.unit_offset(cluster))
+        } else {
+            Err(FsCursorError::InvalidCluster(cluster))
+        }
+    }
 }
 
 /// Generic cursor for cluster-based filesystems.
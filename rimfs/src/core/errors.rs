@@ -143,6 +143,7 @@ impl FsResolverError {
         match self {
             FsResolverError::IO(e) => Some(FsError::IO(*e)),
             FsResolverError::Cursor(e) => Some(FsError::Cursor(*e)),
+            FsResolverError::Parsing(e) => Some(FsError::Parsing(*e)),
             _ => None,
         }
     }
@@ -202,6 +203,11 @@ pub enum FsInjectorError {
     Allocator(FsAllocatorError),
     Resolver(FsResolverError),
     StackUnderflow,
+    /// Injection would leave the volume fuller than `FsInjectOptions::max_fill`.
+    MaxFillExceeded {
+        needed_units: u64,
+        allowed_units: u64,
+    },
     Invalid(&'static str),
     Other(&'static str),
 }
@@ -213,6 +219,7 @@ impl FsInjectorError {
             FsInjectorError::Allocator(_) => "Allocator error",
             FsInjectorError::Resolver(_) => "Resolver error",
             FsInjectorError::StackUnderflow => "Stack underflow",
+            FsInjectorError::MaxFillExceeded { .. } => "Injection would exceed max_fill",
             FsInjectorError::Invalid(msg) => msg,
             FsInjectorError::Other(msg) => msg,
         }
@@ -231,6 +238,13 @@ impl FsInjectorError {
 impl fmt::Display for FsInjectorError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.msg())?;
+        if let FsInjectorError::MaxFillExceeded {
+            needed_units,
+            allowed_units,
+        } = self
+        {
+            write!(f, " ({needed_units} units needed, {allowed_units} allowed)")?;
+        }
         let mut current = self.source();
         while let Some(src) = current {
             write!(f, "\n  caused by: {}", src.msg())?;
@@ -479,4 +493,64 @@ mod tests {
 
         println!("{top}");
     }
+
+    #[test]
+    fn sub_errors_convert_into_fs_error() {
+        let io = RimIOError::Unsupported;
+        assert_eq!(FsError::from(io), FsError::IO(io));
+        assert!(matches!(
+            FsError::from(FsAllocatorError::OutOfBlocks),
+            FsError::Allocator(FsAllocatorError::OutOfBlocks)
+        ));
+        assert!(matches!(
+            FsError::from(FsParsingError::Corrupted),
+            FsError::Parsing(FsParsingError::Corrupted)
+        ));
+        assert!(matches!(
+            FsError::from(FsResolverError::NotFound),
+            FsError::Resolver(FsResolverError::NotFound)
+        ));
+        assert!(matches!(
+            FsError::from(FsFormatterError::Invalid("size")),
+            FsError::Formatter(FsFormatterError::Invalid("size"))
+        ));
+        assert!(matches!(
+            FsError::from(FsInjectorError::StackUnderflow),
+            FsError::Injector(FsInjectorError::StackUnderflow)
+        ));
+        assert!(matches!(
+            FsError::from(FsCheckerError::Invalid("bad")),
+            FsError::Checker(FsCheckerError::Invalid("bad"))
+        ));
+        assert!(matches!(
+            FsError::from(FsCursorError::InvalidCluster(7)),
+            FsError::Cursor(FsCursorError::InvalidCluster(7))
+        ));
+    }
+
+    #[test]
+    fn question_mark_lifts_into_fs_error() {
+        fn resolve() -> FsResolverResult<u32> {
+            Err(FsCursorError::LoopDetected)?
+        }
+        fn spanning() -> FsResult<u32> {
+            Ok(resolve()?)
+        }
+
+        let err = spanning().unwrap_err();
+        assert_eq!(
+            err,
+            FsError::Resolver(FsResolverError::Cursor(FsCursorError::LoopDetected))
+        );
+        assert_eq!(
+            err.source(),
+            Some(FsError::Cursor(FsCursorError::LoopDetected))
+        );
+
+        let parsing = FsError::from(FsResolverError::Parsing(FsParsingError::Corrupted));
+        assert_eq!(
+            parsing.source(),
+            Some(FsError::Parsing(FsParsingError::Corrupted))
+        );
+    }
 }
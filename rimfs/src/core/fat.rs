@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: MIT
 
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
 
 use crate::core::cursor::ClusterMeta;
 use rimio::prelude::*;
@@ -33,6 +35,38 @@ pub mod chain {
         Ok(v & M::ENTRY_MASK)
     }
 
+    /// Streams every entry of the `fat_index` FAT copy, from cluster 0 to the
+    /// last data cluster, calling `f(cluster, value)` with masked values.
+    ///
+    /// Entries are read in `BLOCK_BUF_SIZE` batches instead of one backend call
+    /// per cluster; no allocation beyond the stream buffer.
+    pub fn for_each_entry<IO, M, F>(io: &mut IO, meta: &M, fat_index: u8, mut f: F) -> RimIOResult
+    where
+        IO: RimIO + ?Sized,
+        M: ClusterMeta,
+        F: FnMut(u32, u32),
+    {
+        debug_assert_eq!(M::ENTRY_SIZE, 4);
+        let count = meta.last_data_unit() as usize + 1;
+        io.read_chunks_streamed::<4, _>(
+            meta.fat_entry_offset(0, fat_index),
+            count,
+            rimio::BLOCK_BUF_SIZE / 4,
+            |i, b| f(i as u32, u32::from_le_bytes(*b) & M::ENTRY_MASK),
+        )
+    }
+
+    /// Loads the whole `fat_index` FAT copy in memory, indexed by cluster number.
+    pub fn load_fat<IO: RimIO + ?Sized, M: ClusterMeta>(
+        io: &mut IO,
+        meta: &M,
+        fat_index: u8,
+    ) -> RimIOResult<Vec<u32>> {
+        let mut table = Vec::with_capacity(meta.last_data_unit() as usize + 1);
+        for_each_entry(io, meta, fat_index, |_, v| table.push(v))?;
+        Ok(table)
+    }
+
     /// Read an entire chain into a vector of clusters.
     /// Stops at EOC or if loop/overflow is detected.
     pub fn read_chain<IO: RimIO + ?Sized, M: ClusterMeta>(
@@ -102,6 +136,23 @@ pub mod chain {
         Ok(())
     }
 
+    /// Mark every cluster of `chain` free (0) in all FAT copies.
+    pub fn free_chain<IO: RimIO + ?Sized, M: ClusterMeta>(
+        io: &mut IO,
+        meta: &impl ClusterMeta,
+        chain: &[u32],
+    ) -> RimIOResult {
+        if chain.is_empty() {
+            return Ok(());
+        }
+        let entries = vec![0u8; chain.len() * M::ENTRY_SIZE];
+        for fi in 0..meta.num_fats() {
+            let offs = entry_offsets::<M>(meta, chain, fi);
+            io.write_multi_at(&offs, M::ENTRY_SIZE, &entries)?;
+        }
+        Ok(())
+    }
+
     /// Simple contiguity check (useful for “no-FAT-data” contiguous streams).
     #[inline]
     pub fn is_contiguous(chain: &[u32]) -> bool {
@@ -115,3 +166,81 @@ pub mod chain {
             .all(|(i, &c)| c == start + i as u32)
     }
 }
+
+/// Largest FAT run [`FatBatch::flush`] rewrites in one go.
+const FAT_BATCH_MAX_RUN_BYTES: usize = 256 * 1024;
+
+/// Write-back buffer for FAT entries.
+///
+/// Chains are queued in memory and [`Self::flush`] rewrites the touched FAT
+/// sectors as contiguous runs, once per FAT copy, instead of one 4-byte write
+/// per entry and copy.
+#[derive(Debug, Default)]
+pub struct FatBatch {
+    pending: BTreeMap<u32, u32>,
+}
+
+impl FatBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Queues the entries of `chain`, same values as [`chain::write_chain`].
+    pub fn set_chain<M: ClusterMeta>(&mut self, chain: &[u32]) {
+        for (i, &c) in chain.iter().enumerate() {
+            let next = chain.get(i + 1).copied().unwrap_or(M::EOC);
+            self.pending.insert(c, next & M::ENTRY_MASK);
+        }
+    }
+
+    /// Writes every queued entry to all FAT copies and empties the batch.
+    ///
+    /// Touched sectors are read from the first FAT, patched, then written to
+    /// each copy.
+    pub fn flush<IO, M>(&mut self, io: &mut IO, meta: &M, sector_size: usize) -> RimIOResult
+    where
+        IO: RimIO + ?Sized,
+        M: ClusterMeta,
+    {
+        debug_assert_eq!(M::ENTRY_SIZE, 4);
+        let per_sector = (sector_size / M::ENTRY_SIZE) as u32;
+        let max_sectors = (FAT_BATCH_MAX_RUN_BYTES / sector_size).max(1) as u32;
+        let pending = core::mem::take(&mut self.pending);
+
+        let mut entries = pending.into_iter().peekable();
+        let mut buf = Vec::new();
+        while let Some(&(first, _)) = entries.peek() {
+            // Extend the run while the next entry lands in this or the following sector.
+            let first_sector = first / per_sector;
+            let mut last_sector = first_sector;
+            let mut run = Vec::new();
+            while let Some(&(c, v)) = entries.peek() {
+                let s = c / per_sector;
+                if s > last_sector + 1 || s - first_sector >= max_sectors {
+                    break;
+                }
+                last_sector = s;
+                run.push((c, v));
+                entries.next();
+            }
+
+            let base = first_sector * per_sector;
+            buf.clear();
+            buf.resize((last_sector - first_sector + 1) as usize * sector_size, 0);
+            io.read_at(meta.fat_entry_offset(base, 0), &mut buf)?;
+            for (c, v) in run {
+                let off = (c - base) as usize * M::ENTRY_SIZE;
+                buf[off..off + 4].copy_from_slice(&v.to_le_bytes());
+            }
+            for fi in 0..meta.num_fats() {
+                io.write_at(meta.fat_entry_offset(base, fi), &buf)?;
+            }
+        }
+        Ok(())
+    }
+}
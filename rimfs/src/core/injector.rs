@@ -6,21 +6,96 @@ use ::alloc::vec::Vec;
 pub use crate::core::errors::{FsInjectorError, FsInjectorResult};
 pub use crate::core::resolver::FsNode;
 
-use crate::core::{allocator::FsHandle, resolver::attr::FileAttributes};
+use crate::core::{
+    allocator::{FsAllocator, FsHandle},
+    resolver::attr::FileAttributes,
+};
 
 pub struct FsContext<Handle: FsHandle> {
     pub handle: Handle,
+    /// Pending directory bytes not yet written to disk (at most about one unit).
     pub buf: Vec<u8>,
+    /// Number of units of `handle`'s chain already written out.
+    pub flushed: usize,
 }
 
 impl<Handle: FsHandle> FsContext<Handle> {
     pub fn new(handle: Handle, buf: Vec<u8>) -> Self {
-        Self { handle, buf }
+        Self {
+            handle,
+            buf,
+            flushed: 0,
+        }
     }
 }
 
 use rimio::prelude::RimIO;
 
+/// Directory Windows keeps at the root of FAT/exFAT volumes.
+pub const SYSTEM_VOLUME_INFORMATION: &str = "System Volume Information";
+
+/// Optional steps run around a tree injection. Everything is off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FsInjectOptions {
+    /// Create an empty hidden+system `System Volume Information` directory
+    /// at the root, as Windows does (skipped if the tree already has one).
+    pub system_volume_info: bool,
+    /// Refuse the injection, before anything is written, when it would
+    /// leave the volume more than this percentage (1..=100) full.
+    pub max_fill: Option<u8>,
+}
+
+/// Allocation-unit accounting of a volume, see [`FsNodeInjector::space`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FsSpace {
+    pub unit_size: u64,
+    pub used_units: u64,
+    pub total_units: u64,
+}
+
+impl FsSpace {
+    /// Accounting as seen by `allocator`, for volumes of `unit_size` bytes units.
+    pub fn from_allocator<H, A>(allocator: &A, unit_size: usize) -> Self
+    where
+        H: FsHandle + Clone,
+        A: FsAllocator<H> + ?Sized,
+    {
+        let used = allocator.used_units() as u64;
+        Self {
+            unit_size: unit_size as u64,
+            used_units: used,
+            total_units: used + allocator.remaining_units() as u64,
+        }
+    }
+
+    /// Units a tree needs at least: its file data plus one unit per
+    /// directory (metadata such as inodes or extent blocks is not counted).
+    pub fn units_needed(&self, node: &FsNode) -> u64 {
+        match node {
+            FsNode::File { content, .. } => (content.len() as u64).div_ceil(self.unit_size),
+            FsNode::Dir { children, .. } => {
+                1 + children.iter().map(|c| self.units_needed(c)).sum::<u64>()
+            }
+            FsNode::Container { children, .. } => {
+                children.iter().map(|c| self.units_needed(c)).sum()
+            }
+        }
+    }
+
+    /// Checks that injecting `node` keeps usage within `max_fill` percent.
+    pub fn check_fill(&self, node: &FsNode, max_fill: u8) -> FsInjectorResult {
+        let allowed_units = self.total_units * max_fill.min(100) as u64 / 100;
+        let needed_units = self.used_units + self.units_needed(node);
+        if needed_units > allowed_units {
+            return Err(FsInjectorError::MaxFillExceeded {
+                needed_units,
+                allowed_units,
+            });
+        }
+        Ok(())
+    }
+}
+
 /*
   Injector contract (simple, no pending state):
 
@@ -75,6 +150,11 @@ pub trait FsNodeInjector<Handle: FsHandle> {
         attr: &FileAttributes,
     ) -> FsInjectorResult;
 
+    /// Current space accounting of the volume, if the injector tracks it.
+    fn space(&self) -> Option<FsSpace> {
+        None
+    }
+
     /// Initialize the root context and push it on the stack.
     /// The root context's buffer should reflect existing entries (if any).
     fn set_root_context(&mut self, node: &FsNode) -> FsInjectorResult;
@@ -145,6 +225,33 @@ pub trait FsNodeInjector<Handle: FsHandle> {
         Ok(())
     }
 
+    /// Full-tree injection helper running the optional steps of `opts`.
+    #[must_use = "injection result must be checked for errors"]
+    fn inject_tree_with(&mut self, node: &FsNode, opts: &FsInjectOptions) -> FsInjectorResult {
+        if let Some(max_fill) = opts.max_fill {
+            let space = self.space().ok_or(FsInjectorError::Other(
+                "max_fill is not supported by this filesystem",
+            ))?;
+            space.check_fill(node, max_fill)?;
+        }
+        self.set_root_context(node)?;
+        if opts.system_volume_info && !has_root_child(node, SYSTEM_VOLUME_INFORMATION) {
+            self.write_system_volume_info()?;
+        }
+        self.inject_node(node, true)?;
+        self.flush()?;
+        Ok(())
+    }
+
+    /// Create an empty `System Volume Information` directory (hidden + system)
+    /// under the current directory.
+    #[must_use = "injection result must be checked for errors"]
+    fn write_system_volume_info(&mut self) -> FsInjectorResult {
+        let attr = FileAttributes::new_dir().set_hidden(true).set_system(true);
+        self.write_dir(SYSTEM_VOLUME_INFORMATION, &attr)?;
+        self.flush_current()
+    }
+
     /// Single-path injection helper (no recursion).
     #[must_use = "injection result must be checked for errors"]
     fn inject_path(&mut self, node: &FsNode) -> FsInjectorResult {
@@ -166,3 +273,19 @@ pub trait FsNodeInjector<Handle: FsHandle> {
         Ok(())
     }
 }
+
+fn has_root_child(node: &FsNode, name: &str) -> bool {
+    match node {
+        FsNode::Container { children, .. } => children
+            .iter()
+            .any(|c| !c.is_container() && c.name().eq_ignore_ascii_case(name)),
+        FsNode::Dir {
+            name: dir,
+            children,
+            ..
+        } if dir.is_empty() => children
+            .iter()
+            .any(|c| !c.is_container() && c.name().eq_ignore_ascii_case(name)),
+        _ => false,
+    }
+}
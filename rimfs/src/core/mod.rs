@@ -9,19 +9,25 @@ pub mod filesystem;
 pub mod formatter;
 pub mod injector;
 pub mod meta;
+pub mod profile;
 pub mod resolver;
 pub mod utils;
 
 pub mod fat;
 pub mod validate;
 
+#[cfg(test)]
+pub(crate) mod test_support;
+
 // Core Traits
 pub mod traits {
     pub use super::allocator::{FsAllocator, FsHandle};
     pub use super::checker::FsChecker;
     pub use super::filesystem::FsFilesystem;
     pub use super::formatter::FsFormatter;
-    pub use super::injector::{FsContext, FsNodeInjector};
+    pub use super::injector::{
+        FsContext, FsInjectOptions, FsNodeInjector, FsSpace, SYSTEM_VOLUME_INFORMATION,
+    };
     pub use super::meta::FsMeta;
     pub use super::resolver::{FsNode, FsResolver, attr::FileAttributes};
     pub use super::validate::Validate;
@@ -30,6 +36,8 @@ pub mod traits {
 // Error types
 pub use errors::*;
 
+pub use profile::Profile;
+
 // Utilities
 pub use utils::{path_utils::*, time_utils::*, volume::*};
 
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT
+
+//! Named format presets mirroring the defaults of common formatting tools.
+//!
+//! A profile picks the cluster size for a given volume size, the reserved
+//! sector count and the OEM name, so that images line up with what the
+//! corresponding tool would have produced. Each filesystem only supports the
+//! profiles that make sense for it (see `Fat32Meta::with_profile` and
+//! `ExFatMeta::with_profile`).
+
+/// Format preset applied when building filesystem metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Profile {
+    /// rim's own defaults
+    #[default]
+    Rim,
+    /// `mkfs.vfat` / `mkfs.fat` from dosfstools (FAT32 only)
+    MkfsVfat,
+    /// `mkfs.exfat` from exfatprogs (exFAT only)
+    MkfsExfat,
+    /// Windows `format` (FAT32 and exFAT)
+    Windows,
+}
@@ -1,5 +1,8 @@
 // SPDX-License-Identifier: MIT
 
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::string::String;
+
 use time::OffsetDateTime;
 
 /// Standard file metadata used across filesystem abstractions.
@@ -17,6 +20,8 @@ use time::OffsetDateTime;
 /// - `modified`: last modification timestamp (optional).
 /// - `accessed`: last access timestamp (optional).
 /// - `mode`: optional Unix-like permission bits (e.g., 0o755).
+/// - `symlink_target`: set when the entry is a symbolic link to this path.
+///   Only ext4 stores symlinks natively; FAT32/exFAT injectors reject them.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct FileAttributes {
     pub read_only: bool,
@@ -28,6 +33,7 @@ pub struct FileAttributes {
     pub modified: Option<OffsetDateTime>,
     pub accessed: Option<OffsetDateTime>,
     pub mode: Option<u32>, // UNIX-style
+    pub symlink_target: Option<String>,
 }
 
 impl FileAttributes {
@@ -47,6 +53,20 @@ impl FileAttributes {
         }
     }
 
+    /// Creates symbolic link attributes pointing to `target`.
+    pub fn new_symlink(target: impl Into<String>) -> Self {
+        Self {
+            symlink_target: Some(target.into()),
+            ..Default::default()
+        }
+    }
+
+    /// True if the entry is a symbolic link.
+    #[inline]
+    pub fn is_symlink(&self) -> bool {
+        self.symlink_target.is_some()
+    }
+
     /// Creates file attributes with current timestamp
     #[cfg(feature = "std")]
     pub fn new_file_now() -> Self {
@@ -75,6 +95,10 @@ impl FileAttributes {
             modified: other.modified.or(self.modified),
             accessed: other.accessed.or(self.accessed),
             mode: other.mode.or(self.mode),
+            symlink_target: other
+                .symlink_target
+                .clone()
+                .or_else(|| self.symlink_target.clone()),
         }
     }
 
@@ -104,6 +128,7 @@ impl FileAttributes {
             && self.system == other.system
             && self.archive == other.archive
             && self.dir == other.dir
+            && self.symlink_target == other.symlink_target
     }
 }
 
@@ -161,6 +186,7 @@ mod tests {
             modified: None,
             accessed: None,
             mode: Some(0o644),
+            symlink_target: None,
         };
 
         let override_attr = FileAttributes {
@@ -173,6 +199,7 @@ mod tests {
             modified: Some(OffsetDateTime::UNIX_EPOCH + time::Duration::hours(1)),
             accessed: Some(OffsetDateTime::UNIX_EPOCH + time::Duration::hours(2)),
             mode: None,
+            symlink_target: None,
         };
 
         let merged = base.merge(&override_attr);
@@ -119,4 +119,23 @@ pub trait FsResolver {
     }
 
     fn resolve_path(&mut self, path: &str) -> FsResolverResult<(bool, u32, usize)>;
+
+    /// Returns whether `path` names an existing file or directory.
+    ///
+    /// Any lookup error (missing component, a file used as a directory, I/O)
+    /// counts as absent. The default goes through [`Self::resolve_path`];
+    /// implementations may override it with a lookup that skips metadata reads.
+    fn exists(&mut self, path: &str) -> bool {
+        self.resolve_path(path).is_ok()
+    }
+
+    /// Returns the on-disk runs backing the file at `path`.
+    ///
+    /// Each run is a `(byte_offset, byte_length)` pair in volume order of the
+    /// file's data; lengths are whole clusters/blocks, so the last run may
+    /// extend past the end of the file. Empty files yield no runs.
+    fn block_map(&mut self, path: &str) -> FsResolverResult<Vec<(u64, u64)>> {
+        let _ = path;
+        Err(FsResolverError::Unsupported)
+    }
 }
@@ -99,6 +99,7 @@ impl FsResolver for StdResolver {
                     None
                 }
             },
+            symlink_target: None,
         })
     }
 
@@ -117,6 +118,10 @@ impl FsResolver for StdResolver {
 
         Ok((is_dir, 0, size)) // handle/unit = 0 (not used here)
     }
+
+    fn exists(&mut self, path: &str) -> bool {
+        Path::new(&clean_and_normalize_path(path)).exists()
+    }
 }
 
 #[cfg(feature = "std")]
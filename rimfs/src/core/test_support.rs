@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: MIT
+
+//! Checks shared by the per-filesystem tests.
+//!
+//! Each resolver or injector test formats a volume and hands its resolver
+//! (with [`sample_tree`] injected) or injector to the `check_*` functions, so
+//! every filesystem is held to the same expectations.
+
+use crate::core::allocator::FsHandle;
+use crate::core::injector::{FsInjectOptions, FsInjectorError, FsNodeInjector};
+use crate::core::resolver::{FsNode, FsResolver, FsResolverError, attr::FileAttributes};
+
+/// Volume size used by the resolver tests.
+pub(crate) const SAMPLE_VOLUME_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Content of `/data.bin`: three allocation units and a partial fourth.
+pub(crate) fn sample_data(unit: usize) -> Vec<u8> {
+    (0..unit * 3 + 100).map(|i| (i % 251) as u8).collect()
+}
+
+/// `/sub/a.txt`, `/data.bin` (see [`sample_data`]) and an empty `/empty.txt`.
+pub(crate) fn sample_tree(unit: usize) -> FsNode {
+    let file = |name: &str, content: Vec<u8>| FsNode::File {
+        name: name.to_string(),
+        content,
+        attr: FileAttributes::new_file(),
+    };
+    FsNode::Container {
+        attr: FileAttributes::new_dir(),
+        children: vec![
+            FsNode::Dir {
+                name: "sub".to_string(),
+                attr: FileAttributes::new_dir(),
+                children: vec![file("a.txt", b"hello".to_vec())],
+            },
+            file("data.bin", sample_data(unit)),
+            file("empty.txt", vec![]),
+        ],
+    }
+}
+
+/// Checks path lookups over [`sample_tree`].
+pub(crate) fn check_exists<R: FsResolver>(resolver: &mut R) {
+    for path in ["/", "/sub", "/sub/a.txt", "/data.bin", "/empty.txt"] {
+        assert!(resolver.exists(path), "{path}");
+    }
+    for path in ["/missing", "/sub/b.txt", "/sub/a.txt/x"] {
+        assert!(!resolver.exists(path), "{path}");
+    }
+}
+
+/// Checks `block_map` over [`sample_tree`] and returns the runs of `/data.bin`.
+pub(crate) fn check_block_map<R: FsResolver>(resolver: &mut R, unit: usize) -> Vec<(u64, u64)> {
+    let runs = resolver.block_map("/data.bin").unwrap();
+    let total: u64 = runs.iter().map(|&(_, len)| len).sum();
+    let data_len = sample_data(unit).len() as u64;
+    assert_eq!(total, data_len.div_ceil(unit as u64) * unit as u64);
+
+    assert!(resolver.block_map("/empty.txt").unwrap().is_empty());
+    assert_eq!(
+        resolver.block_map("/sub"),
+        Err(FsResolverError::Invalid("Not a file"))
+    );
+    runs
+}
+
+/// Checks that `runs` of an image read back as [`sample_data`].
+pub(crate) fn check_runs_hold_data(image: &[u8], runs: &[(u64, u64)], unit: usize) {
+    let mut on_disk = Vec::new();
+    for &(off, len) in runs {
+        on_disk.extend_from_slice(&image[off as usize..(off + len) as usize]);
+    }
+    let data = sample_data(unit);
+    assert_eq!(&on_disk[..data.len()], &data[..]);
+}
+
+/// Checks `max_fill` on a freshly formatted volume: one unit past 90 % is
+/// refused before anything is allocated, exactly 90 % fits.
+pub(crate) fn check_max_fill<H: FsHandle, I: FsNodeInjector<H>>(injector: &mut I) {
+    let tree = |units: u64, unit_size: u64| FsNode::Container {
+        attr: FileAttributes::new_dir(),
+        children: vec![FsNode::File {
+            name: "blob.bin".to_string(),
+            content: vec![0xA5; (units * unit_size) as usize],
+            attr: FileAttributes::new_file(),
+        }],
+    };
+    let opts = FsInjectOptions {
+        max_fill: Some(90),
+        ..Default::default()
+    };
+
+    let space = injector.space().unwrap();
+    let room = space.total_units * 90 / 100 - space.used_units;
+
+    let err = injector
+        .inject_tree_with(&tree(room + 1, space.unit_size), &opts)
+        .unwrap_err();
+    assert!(matches!(err, FsInjectorError::MaxFillExceeded { .. }));
+    assert_eq!(injector.space(), Some(space));
+
+    injector
+        .inject_tree_with(&tree(room, space.unit_size), &opts)
+        .unwrap();
+    let after = injector.space().unwrap();
+    assert!(after.used_units >= space.used_units + room);
+}
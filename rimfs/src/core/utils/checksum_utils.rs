@@ -77,3 +77,52 @@ pub fn checksum_u8(data: &[u8]) -> u8 {
 pub fn checksum_u32(data: &[u8]) -> u32 {
     checksum::<u32>(data)
 }
+
+/// CRC-16 (reflected 0x8005, i.e. 0xA001) as used by ext4's `gdt_csum`.
+/// No final inversion: pass `!0` to start and chain calls for split data.
+pub fn crc16(mut crc: u16, data: &[u8]) -> u16 {
+    for &b in data {
+        crc ^= b as u16;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xA001
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
+
+/// CRC-32C (Castagnoli, reflected 0x82F63B78) as used by ext4's
+/// `metadata_csum`. No final inversion: pass `!0` to start.
+pub fn crc32c(mut crc: u32, data: &[u8]) -> u32 {
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0x82F6_3B78
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc_check_values() {
+        // Standard "123456789" check values (with the usual final inversion)
+        assert_eq!(!crc32c(!0, b"123456789"), 0xE306_9283);
+        assert_eq!(crc16(0, b"123456789"), 0xBB3D); // CRC-16/ARC
+        assert_eq!(crc16(!0, b"123456789"), 0x4B37); // CRC-16/MODBUS
+        assert_eq!(
+            crc32c(crc32c(!0, b"1234"), b"56789"),
+            crc32c(!0, b"123456789")
+        );
+    }
+}
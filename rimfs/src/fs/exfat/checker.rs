@@ -7,9 +7,10 @@ use zerocopy::{FromBytes, IntoBytes};
 
 pub use crate::core::checker::*;
 
+use crate::core::cursor::ClusterMeta;
 use crate::core::fat;
-use crate::core::utils::checksum_utils::{accumulate_checksum, accumulate_checksum_with_escape};
-use crate::fs::exfat::{constant::*, meta::*, types::*};
+use crate::core::utils::checksum_utils::accumulate_checksum;
+use crate::fs::exfat::{constant::*, meta::*, types::*, utils};
 
 mod walker;
 
@@ -21,6 +22,8 @@ pub struct ExFatCheckOptions {
     pub fat_sample: u32,
     /// Deep walk on the entire FAT (detects loops/overflow) — expensive
     pub deep_fat_walk: bool,
+    /// Report VolumeDirty/MediaFailure and an inconsistent ActiveFat bit
+    pub volume_flags: bool,
 }
 
 impl Default for ExFatCheckOptions {
@@ -30,6 +33,7 @@ impl Default for ExFatCheckOptions {
             fail_fast: false,
             fat_sample: 0,
             deep_fat_walk: true,
+            volume_flags: true,
         }
     }
 }
@@ -45,18 +49,64 @@ impl VerifierOptionsLike for ExFatCheckOptions {
 pub struct ExFatChecker<'a, IO: RimIO + ?Sized> {
     io: &'a mut IO,
     meta: &'a ExFatMeta,
+    /// Single-pass walk shared by the ROOT and CROSSREF phases. The checker
+    /// holds the IO exclusively and never writes, so it stays valid.
+    walk: Option<TreeWalk>,
+}
+
+/// Everything the phases need from one traversal of the directory tree.
+struct TreeWalk {
+    crit: RootCritical,
+    crit_findings: Vec<Finding>,
+    findings: Vec<Finding>,
+    reachable_bitmap: Vec<u8>,
 }
 
 impl<'a, IO: RimIO + ?Sized> ExFatChecker<'a, IO> {
     pub fn new(io: &'a mut IO, meta: &'a ExFatMeta) -> Self {
-        Self { io, meta }
+        Self {
+            io,
+            meta,
+            walk: None,
+        }
+    }
+
+    /// Walks the tree on first use: reads every directory once, collecting
+    /// root-critical entries, entry sets, name clashes and reachability.
+    fn tree_walk(&mut self) -> FsCheckerResult<&TreeWalk> {
+        if self.walk.is_none() {
+            let mut findings = VerifyReport::default();
+            let mut walker = walker::ExFatWalker::new(self.io, self.meta);
+            let mut stats = walker::WalkerStats::default();
+            walker.walk_tree(&mut findings, &mut stats)?;
+
+            let mut crit_rep = VerifyReport::default();
+            let crit = scan_root_for_critical(&walker.root_head, self.meta, &mut crit_rep);
+
+            // Mark critical system clusters (Bitmap, Upcase) as reachable because
+            // the walker only follows the namespace; the root chain is marked by the walk.
+            let bm_fc = crit.bitmap_fc.unwrap_or(self.meta.bitmap_cluster);
+            let bm_len = crit.bitmap_len.unwrap_or(self.meta.bitmap_size_bytes);
+            walker.mark_reachable(bm_fc, bm_len)?;
+            let uc_fc = crit.upcase_fc.unwrap_or(self.meta.upcase_cluster);
+            let uc_len = crit.upcase_len.unwrap_or(EXFAT_UPCASE_FULL_LENGTH as u64);
+            walker.mark_reachable(uc_fc, uc_len)?;
+
+            self.walk = Some(TreeWalk {
+                crit,
+                crit_findings: crit_rep.findings,
+                findings: findings.findings,
+                reachable_bitmap: walker.reachable_bitmap,
+            });
+        }
+        Ok(self.walk.as_ref().unwrap())
     }
 }
 
 impl<'a, IO: RimIO + ?Sized> FsChecker for ExFatChecker<'a, IO> {
     type Options = ExFatCheckOptions;
 
-    fn check_boot(&mut self, _opt: &Self::Options, rep: &mut VerifyReport) -> FsCheckerResult<()> {
+    fn check_boot(&mut self, opt: &Self::Options, rep: &mut VerifyReport) -> FsCheckerResult<()> {
         let bps = self.meta.bytes_per_sector as usize;
 
         // Main VBR + backup checksum + mirroring (neutralizes volatile fields)
@@ -67,6 +117,9 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for ExFatChecker<'a, IO> {
         let vbr: ExFatBootSector = self.io.read_struct(EXFAT_VBR_SECTOR)?;
         let spc = self.meta.sectors_per_cluster as usize;
         check_bpb_geometry(&vbr, bps, spc, rep)?;
+        if opt.volume_flags {
+            check_volume_flags(&vbr, rep);
+        }
         Ok(())
     }
 
@@ -88,7 +141,10 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for ExFatChecker<'a, IO> {
 
     fn check_root(&mut self, _opt: &Self::Options, rep: &mut VerifyReport) -> FsCheckerResult<()> {
         // Critical root entries + Up-Case checksum
-        let crit = scan_root_for_critical_with_meta(self.io, self.meta, rep)?;
+        let walk = self.tree_walk()?;
+        rep.findings.extend_from_slice(&walk.crit_findings);
+        let crit = walk.crit.clone();
+        check_bitmap_length(&crit, self.meta, rep);
         if let (Some(fc), Some(len), Some(exp)) =
             (crit.upcase_fc, crit.upcase_len, crit.upcase_table_checksum)
         {
@@ -99,35 +155,22 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for ExFatChecker<'a, IO> {
 
     fn check_cross_reference(
         &mut self,
-        _opt: &Self::Options,
+        opt: &Self::Options,
         rep: &mut VerifyReport,
     ) -> FsCheckerResult<()> {
-        // Bitmap covers Bitmap/UpCase/Root - Scan First to satisfy borrow checker
-        let crit = scan_root_for_critical_with_meta(self.io, self.meta, rep)?;
-
-        let mut walker = walker::ExFatWalker::new(self.io, self.meta);
-        let mut stats = walker::WalkerStats::default();
-        walker.walk_tree(rep, &mut stats)?;
-
-        // Manually mark critical system clusters (Bitmap, Upcase, Root) as reachable
-        // because walker only follows namespace.
-        // Bitmap
-        let bm_fc = crit.bitmap_fc.unwrap_or(self.meta.bitmap_cluster);
-        let bm_len = crit.bitmap_len.unwrap_or(self.meta.bitmap_size_bytes);
-        walker.mark_reachable(bm_fc, bm_len)?;
-        // Upcase
-        let uc_fc = crit.upcase_fc.unwrap_or(self.meta.upcase_cluster);
-        let uc_len = crit.upcase_len.unwrap_or(EXFAT_UPCASE_FULL_LENGTH as u64);
-        walker.mark_reachable(uc_fc, uc_len)?;
-        // Root (is walked by walker, so should be marked, but ensure coverage of chain logic)
-        // Root is marked by walker.walk_tree -> ClusterCursor
-
-        let reachable_bitmap = walker.reachable_bitmap;
+        let walk = self.tree_walk()?;
+        if !opt.phases.contains(VerifyPhases::ROOT) {
+            rep.findings.extend_from_slice(&walk.crit_findings);
+        }
+        rep.findings.extend_from_slice(&walk.findings);
+        let crit = walk.crit.clone();
+        let reachable_bitmap = walk.reachable_bitmap.clone();
+
         let mut orphans = 0usize;
         let mut samples = 0usize;
 
         let bitmap_clus = crit.bitmap_fc.unwrap_or(self.meta.bitmap_cluster);
-        let bpos = self.meta.unit_offset(bitmap_clus);
+        let bpos = self.meta.try_unit_offset(bitmap_clus)?;
         let bsize = self
             .meta
             .unit_size()
@@ -191,6 +234,7 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for ExFatChecker<'a, IO> {
             fail_fast: true,
             fat_sample: 0,
             deep_fat_walk: true,
+            volume_flags: true,
         };
         let rep = self.check_with(&opt)?;
 
@@ -332,52 +376,24 @@ fn check_boot_checksum<IO: RimIO + ?Sized>(
     bps: usize,
     rep: &mut VerifyReport,
 ) -> FsCheckerResult<bool> {
-    let base = lba512 * 512;
-    let mut sum: u32 = 0;
-
-    // Single reused buffer
-    let mut sec = vec![0u8; bps];
-
-    // Sectors 0..10
-    for s in 0..=10 {
-        io.read_at(base + (bps as u64) * (s as u64), &mut sec)?;
-        accumulate_checksum_with_escape(&mut sum, &sec, |i, _b| {
-            s == 0 && (i == 106 || i == 107 || i == 112)
-        });
-    }
-
-    // Sector 11 (repeated checksum)
-    io.read_at(base + (bps as u64) * 11, &mut sec)?;
-    let mut ok = true;
-    let mut bad_off = None;
-    for (i, c) in sec.chunks_exact(4).enumerate() {
-        if u32::from_le_bytes([c[0], c[1], c[2], c[3]]) != sum {
-            ok = false;
-            bad_off = Some(i * 4);
-            break;
-        }
-    }
+    let (sum, bad_off) = utils::read_boot_checksum(io, lba512 * 512, bps)?;
 
     let what = if lba512 == 0 {
         "VBR(main)"
     } else {
         "VBR(backup)"
     };
-    if ok {
-        rep.push(Finding::info(
+    match bad_off {
+        None => rep.push(Finding::info(
             "VBR.CHK",
             format!("{what} checksum OK (0x{sum:08X})"),
-        ));
-    } else {
-        match bad_off {
-            Some(off) => rep.push(Finding::err(
-                "VBR.CHK",
-                format!("{what} checksum mismatch @+{off} (expected 0x{sum:08X})"),
-            )),
-            None => rep.push(Finding::err("VBR.CHK", format!("{what} checksum mismatch"))),
-        }
+        )),
+        Some(off) => rep.push(Finding::err(
+            "VBR.CHK",
+            format!("{what} checksum mismatch @+{off} (expected 0x{sum:08X})"),
+        )),
     }
-    Ok(ok)
+    Ok(bad_off.is_none())
 }
 
 /// Compares sectors 0 (main vs backup) while neutralizing volatile fields
@@ -417,6 +433,27 @@ fn compare_vbr_main_backup<IO: RimIO + ?Sized>(
     }
 }
 
+/// VolumeFlags sanity: dirty / media-failure warnings, and ActiveFat
+/// pointing at a second FAT that doesn't exist.
+fn check_volume_flags(vbr: &ExFatBootSector, rep: &mut VerifyReport) {
+    let flags = vbr.volume_flags;
+    if flags.is_dirty() {
+        rep.push(Finding::warn(
+            "BPB.DIRTY",
+            "VolumeDirty set (volume was not cleanly unmounted)",
+        ));
+    }
+    if flags.has_media_failure() {
+        rep.push(Finding::warn(
+            "BPB.MEDIA",
+            "MediaFailure set (volume has reported bad media)",
+        ));
+    }
+    if vbr.number_of_fats == 1 && flags.contains(VolumeFlags::ACTIVE_FAT) {
+        rep.push(Finding::err("BPB.ACTFAT", "ActiveFat=1 but NumberOfFats=1"));
+    }
+}
+
 /// BPB / geometry consistency validation
 fn check_bpb_geometry(
     vbr: &ExFatBootSector,
@@ -511,21 +548,10 @@ fn sample_fat<IO: RimIO + ?Sized>(
 
 /* -------------------- ROOT & CRITICAL ENTRIES -------------------- */
 
-fn scan_root_for_critical_with_meta<IO: RimIO + ?Sized>(
-    io: &mut IO,
-    meta: &ExFatMeta,
-    rep: &mut VerifyReport,
-) -> FsCheckerResult<RootCritical> {
+/// Scans the first root directory cluster (`dir`) for the critical entries.
+fn scan_root_for_critical(dir: &[u8], meta: &ExFatMeta, rep: &mut VerifyReport) -> RootCritical {
     let mut out = RootCritical::default();
 
-    let bps = meta.bytes_per_sector as usize;
-    let spc = meta.sectors_per_cluster as usize;
-    let fc = meta.root_unit();
-    let bytes_per_cluster = bps * spc;
-
-    let mut dir = vec![0u8; bytes_per_cluster];
-    io.read_at(meta.unit_offset(fc), &mut dir)?;
-
     let mut i = 0usize;
     while i + 32 <= dir.len() {
         let et = dir[i];
@@ -602,7 +628,29 @@ fn scan_root_for_critical_with_meta<IO: RimIO + ?Sized>(
         ));
     }
 
-    Ok(out)
+    out
+}
+
+/// The Bitmap entry must declare exactly one bit per cluster, rounded up to a byte.
+fn check_bitmap_length(crit: &RootCritical, meta: &ExFatMeta, rep: &mut VerifyReport) {
+    let Some(len) = crit.bitmap_len else {
+        return;
+    };
+    let expected = (meta.cluster_count as u64).div_ceil(8);
+    if len == expected {
+        rep.push(Finding::info(
+            "ROOT.BMLEN",
+            format!("Bitmap length {len} bytes matches cluster count"),
+        ));
+    } else {
+        rep.push(Finding::err(
+            "ROOT.BMLEN",
+            format!(
+                "Bitmap length {len} bytes, expected {expected} for {} clusters",
+                meta.cluster_count
+            ),
+        ));
+    }
 }
 
 fn verify_upcase_checksum_over_file<IO: RimIO + ?Sized>(
@@ -640,14 +688,9 @@ fn verify_upcase_checksum_over_file<IO: RimIO + ?Sized>(
     let mut walked = 0usize;
 
     while remain > 0 {
-        // Basic bounds check
-        if cur < EXFAT_FIRST_CLUSTER || cur >= meta.last_data_unit() {
-            return Err(FsCheckerError::Invalid("Up-Case cluster out of range"));
-        }
-
         // Read current cluster
         let mut buf = vec![0u8; bytes_per_cluster];
-        io.read_at(meta.unit_offset(cur), &mut buf)?;
+        io.read_at(meta.try_unit_offset(cur)?, &mut buf)?;
 
         let take = remain.min(bytes_per_cluster);
         accumulate_checksum(&mut sum, &buf[..take]);
@@ -740,7 +783,7 @@ fn bitmap_has_cluster_meta<IO: RimIO + ?Sized>(
 
     if byte_index < cluster_size {
         let mut clus = vec![0u8; cluster_size as usize];
-        io.read_at(meta.unit_offset(bfc), &mut clus)?;
+        io.read_at(meta.try_unit_offset(bfc)?, &mut clus)?;
         let byte = clus[byte_index as usize];
         let bit = (idx % 8) as u8;
         Ok((byte & (1 << bit)) != 0)
@@ -785,3 +828,300 @@ fn check_file_entry_set(raw: &[u8]) -> (bool, usize, String) {
     }
     (true, off, format!("File entry set OK ({names} FileName)"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::formatter::FsFormatter;
+    use crate::fs::exfat::formatter::ExFatFormatter;
+
+    #[test]
+    fn check_all_runs_through_read_only_io() {
+        let meta = ExFatMeta::new(32 * 1024 * 1024, Some("RO")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut mem = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut mem, &meta).format(false).unwrap();
+
+        let mut io = ReadOnlyRimIO::new(&mut mem);
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!rep.has_error(), "{rep}");
+
+        assert_eq!(io.write_at(0, &[0u8; 512]), Err(RimIOError::ReadOnly));
+    }
+
+    fn flagged(set: u16) -> VerifyReport {
+        let meta = ExFatMeta::new(32 * 1024 * 1024, Some("FLAGS")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        // VolumeFlags live at offset 106 and are excluded from the boot checksum.
+        io.write_u16_at(106, set).unwrap();
+        ExFatChecker::new(&mut io, &meta).check_all().unwrap()
+    }
+
+    #[test]
+    fn volume_flags_are_reported() {
+        let has = |rep: &VerifyReport, code: &str| rep.findings.iter().any(|f| f.code == code);
+
+        let clean = flagged(0);
+        assert!(!has(&clean, "BPB.DIRTY") && !has(&clean, "BPB.MEDIA"));
+
+        let dirty = flagged(VolumeFlags::VOLUME_DIRTY);
+        assert!(has(&dirty, "BPB.DIRTY"));
+        assert!(!dirty.has_error(), "{dirty}");
+        assert_eq!(dirty.outcome(), Outcome::Warnings);
+
+        let media = flagged(VolumeFlags::MEDIA_FAILURE);
+        assert!(has(&media, "BPB.MEDIA"));
+
+        let active = flagged(VolumeFlags::ACTIVE_FAT);
+        assert!(has(&active, "BPB.ACTFAT"));
+        assert!(active.has_error());
+    }
+
+    #[test]
+    fn mark_clean_clears_dirty_flag() {
+        let meta = ExFatMeta::new(32 * 1024 * 1024, Some("CLEAN")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        let bps = meta.bytes_per_sector as u64;
+        for sector in [EXFAT_VBR_SECTOR, EXFAT_VBR_BACKUP_SECTOR] {
+            io.write_u16_at(sector * bps + 106, VolumeFlags::VOLUME_DIRTY)
+                .unwrap();
+        }
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        assert!(rep.findings.iter().any(|f| f.code == "BPB.DIRTY"));
+
+        crate::fs::exfat::filesystem::ExFat::mark_clean(&mut io, &meta).unwrap();
+
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!rep.findings.iter().any(|f| f.code == "BPB.DIRTY"), "{rep}");
+        assert!(!rep.has_error(), "{rep}");
+        let vbr: ExFatBootSector = io.read_struct(EXFAT_VBR_BACKUP_SECTOR * bps).unwrap();
+        assert!(!vbr.volume_flags.is_dirty());
+    }
+
+    #[derive(Default)]
+    struct ReadLog(Vec<(u64, usize)>);
+
+    impl IOTracer for ReadLog {
+        fn on_read(&mut self, off: u64, len: usize) {
+            self.0.push((off, len));
+        }
+    }
+
+    #[test]
+    fn check_all_reads_each_dir_cluster_once() {
+        use crate::core::{injector::FsNodeInjector, resolver::FsResolver};
+        use crate::fs::exfat::prelude::*;
+
+        let meta = ExFatMeta::new(32 * 1024 * 1024, Some("WALK")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut mem = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut mem, &meta).format(false).unwrap();
+
+        let file = |name: &str| FsNode::File {
+            name: name.to_string(),
+            content: b"data".to_vec(),
+            attr: FileAttributes::new_file(),
+        };
+        let dir = |name: &str, children| FsNode::Dir {
+            name: name.to_string(),
+            attr: FileAttributes::new_dir(),
+            children,
+        };
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![
+                dir("a", vec![file("x.txt"), file("y.txt")]),
+                dir("b", vec![dir("c", vec![file("z.txt")])]),
+                file("top.txt"),
+            ],
+        };
+        let mut allocator = ExFatAllocator::new(&meta);
+        ExFatInjector::new(&mut mem, &mut allocator, &meta)
+            .unwrap()
+            .inject_tree(&tree)
+            .unwrap();
+
+        let mut dirs = vec![meta.root_unit()];
+        let mut resolver = ExFatResolver::new(&mut mem, &meta);
+        for path in ["/a", "/b", "/b/c"] {
+            let (is_dir, cluster, _) = resolver.resolve_path(path).unwrap();
+            assert!(is_dir);
+            dirs.push(cluster);
+        }
+
+        let mut io = TracingIO::new(&mut mem, ReadLog::default());
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!rep.has_error(), "{rep}");
+
+        let (_, log) = io.into_parts();
+        let cs = meta.unit_size() as u64;
+        for c in dirs {
+            let start = meta.unit_offset(c);
+            let reads = log
+                .0
+                .iter()
+                .filter(|&&(off, len)| off < start + cs && off + len as u64 > start)
+                .count();
+            assert_eq!(reads, 1, "dir cluster {c} read {reads} times");
+        }
+    }
+
+    #[test]
+    fn wrong_bitmap_length_is_reported() {
+        let meta = ExFatMeta::new(32 * 1024 * 1024, Some("BMLEN")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        let bmlen = |rep: &VerifyReport| {
+            rep.findings
+                .iter()
+                .find(|f| f.code == "ROOT.BMLEN")
+                .cloned()
+                .unwrap()
+        };
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        assert_eq!(bmlen(&rep).sev, Severity::Info, "{rep}");
+
+        // Bump DataLength (offset 24) of the Bitmap entry in the root directory.
+        let root = meta.unit_offset(meta.root_unit());
+        let mut dir = vec![0u8; meta.unit_size()];
+        io.read_at(root, &mut dir).unwrap();
+        let pos = dir
+            .chunks(32)
+            .position(|e| e[0] == EXFAT_ENTRY_BITMAP)
+            .unwrap() as u64
+            * 32;
+        io.write_u64_at(root + pos + 24, meta.bitmap_size_bytes + 1)
+            .unwrap();
+
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        let finding = bmlen(&rep);
+        assert_eq!(finding.sev, Severity::Error);
+        assert!(
+            finding
+                .msg
+                .contains(&format!("expected {}", meta.bitmap_size_bytes)),
+            "{rep}"
+        );
+    }
+
+    /// Formats a volume holding one 10 000-byte file and returns the offset
+    /// of that file's Stream Extension entry.
+    fn volume_with_file(io: &mut MemRimIO, meta: &ExFatMeta) -> u64 {
+        use crate::core::injector::FsNodeInjector;
+        use crate::fs::exfat::prelude::*;
+
+        ExFatFormatter::new(io, meta).format(false).unwrap();
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![FsNode::File {
+                name: "data.bin".to_string(),
+                content: vec![0xA5; 10_000],
+                attr: FileAttributes::new_file(),
+            }],
+        };
+        let mut allocator = ExFatAllocator::new(meta);
+        ExFatInjector::new(io, &mut allocator, meta)
+            .unwrap()
+            .inject_tree(&tree)
+            .unwrap();
+
+        let root = meta.unit_offset(meta.root_unit());
+        let mut dir = vec![0u8; meta.unit_size()];
+        io.read_at(root, &mut dir).unwrap();
+        let pos = dir
+            .chunks(32)
+            .position(|e| e[0] == EXFAT_ENTRY_STREAM)
+            .unwrap();
+        root + pos as u64 * 32
+    }
+
+    #[test]
+    fn valid_data_length_beyond_data_length_is_reported() {
+        let meta = ExFatMeta::new(32 * 1024 * 1024, Some("VDL")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        let stream = volume_with_file(&mut io, &meta);
+
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!rep.has_error(), "{rep}");
+
+        // ValidDataLength lives at offset 8 of the Stream Extension entry.
+        io.write_u64_at(stream + 8, 10_001).unwrap();
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        let vdl: Vec<_> = rep
+            .findings
+            .iter()
+            .filter(|f| f.code == "WALK.VDL")
+            .collect();
+        assert_eq!(vdl.len(), 1, "{rep}");
+        assert_eq!(vdl[0].sev, Severity::Error);
+        assert!(vdl[0].msg.contains("data.bin"));
+    }
+
+    #[test]
+    fn data_length_must_match_cluster_chain() {
+        let meta = ExFatMeta::new(32 * 1024 * 1024, Some("CHAIN")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        let stream = volume_with_file(&mut io, &meta);
+
+        // Follow the FAT chain instead of NoFatChain, then claim one more cluster.
+        let mut flags = [0u8];
+        io.read_at(stream + 1, &mut flags).unwrap();
+        io.write_at(stream + 1, &[flags[0] & !0x02]).unwrap();
+        let len = 10_000 + meta.unit_size() as u64;
+        io.write_u64_at(stream + 8, len).unwrap();
+        io.write_u64_at(stream + 24, len).unwrap();
+
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        let expected = len.div_ceil(meta.unit_size() as u64);
+        assert!(
+            rep.findings
+                .iter()
+                .any(|f| f.code == "WALK.CLUSTERS" && f.msg.contains(&format!("needs {expected}"))),
+            "{rep}"
+        );
+    }
+
+    #[test]
+    fn duplicate_names_are_reported() {
+        use crate::core::injector::FsNodeInjector;
+        use crate::fs::exfat::prelude::*;
+
+        let meta = ExFatMeta::new(32 * 1024 * 1024, Some("DUP")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        let file = |name: &str| FsNode::File {
+            name: name.to_string(),
+            content: b"data".to_vec(),
+            attr: FileAttributes::new_file(),
+        };
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![file("dup.txt"), file("DUP.TXT"), file("other.txt")],
+        };
+        let mut allocator = ExFatAllocator::new(&meta);
+        ExFatInjector::new(&mut io, &mut allocator, &meta)
+            .unwrap()
+            .inject_tree(&tree)
+            .unwrap();
+
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        let dups: Vec<_> = rep
+            .findings
+            .iter()
+            .filter(|f| f.code == "WALK.DUPNAME")
+            .collect();
+        assert_eq!(dups.len(), 1, "{rep}");
+    }
+}
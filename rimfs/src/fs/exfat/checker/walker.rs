@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: MIT
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
-use alloc::{format, vec, vec::Vec};
+use alloc::{format, string::String, vec, vec::Vec};
 
 use crate::FsMeta;
 use crate::core::cursor::ClusterCursor;
@@ -16,6 +16,8 @@ pub struct ExFatWalker<'a, IO: RimIO + ?Sized> {
     io: &'a mut IO,
     meta: &'a ExFatMeta,
     pub reachable_bitmap: Vec<u8>,
+    /// First cluster of the root directory, as read during the walk.
+    pub root_head: Vec<u8>,
 }
 
 impl<'a, IO: RimIO + ?Sized> ExFatWalker<'a, IO> {
@@ -25,6 +27,7 @@ impl<'a, IO: RimIO + ?Sized> ExFatWalker<'a, IO> {
             io,
             meta,
             reachable_bitmap: vec![0u8; bit_size],
+            root_head: Vec::new(),
         }
     }
 
@@ -76,6 +79,7 @@ impl<'a, IO: RimIO + ?Sized> ExFatWalker<'a, IO> {
 
             // ReadDir entries
             let children = self.scan_directory(dir_cluster, rep, stats)?;
+            check_unique_names(dir_cluster, &children, rep);
 
             // Mark this directory's chain itself as reachable
             // (Note: children might have marked parts of it, but we ensure full coverage here)
@@ -92,6 +96,7 @@ impl<'a, IO: RimIO + ?Sized> ExFatWalker<'a, IO> {
             })?;
 
             for child in children {
+                self.check_stream_lengths(&child, rep);
                 if child.is_dir() {
                     // Check if loop
                     let first = child.first_cluster();
@@ -124,6 +129,47 @@ impl<'a, IO: RimIO + ?Sized> ExFatWalker<'a, IO> {
         Ok(())
     }
 
+    /// Checks `ValidDataLength <= DataLength` and that a FAT-chained stream
+    /// owns exactly `ceil(DataLength / cluster_size)` clusters.
+    fn check_stream_lengths(&mut self, entry: &ExFatEntries, rep: &mut VerifyReport) {
+        let name = entry.name().unwrap_or_default();
+        let stream = &entry.stream;
+        let (valid, len) = (stream.valid_data_length, stream.data_length);
+        if valid > len {
+            rep.push(Finding::err(
+                "WALK.VDL",
+                format!("'{name}': ValidDataLength {valid} exceeds DataLength {len}"),
+            ));
+        }
+
+        // NoFatChain streams are contiguous by definition, their FAT entries are unused.
+        if len == 0 || stream.first_cluster == 0 || stream.is_contiguous() {
+            return;
+        }
+        let expected = len.div_ceil(self.meta.unit_size() as u64);
+        let mut clusters = 0u64;
+        let walked = ClusterCursor::new(self.meta, stream.first_cluster).for_each_run(
+            self.io,
+            |_io, _start, run| {
+                clusters += run as u64;
+                Ok(())
+            },
+        );
+        match walked {
+            Ok(()) if clusters != expected => rep.push(Finding::err(
+                "WALK.CLUSTERS",
+                format!(
+                    "'{name}': {clusters} clusters allocated, DataLength {len} needs {expected}"
+                ),
+            )),
+            Ok(()) => {}
+            Err(e) => rep.push(Finding::err(
+                "WALK.CLUSTERS",
+                format!("'{name}': cluster chain unreadable: {e}"),
+            )),
+        }
+    }
+
     fn scan_directory(
         &mut self,
         dir_cluster: u32,
@@ -139,6 +185,8 @@ impl<'a, IO: RimIO + ?Sized> ExFatWalker<'a, IO> {
         let mut raw_stream: Option<[u8; 32]> = None;
 
         let mut cur = ClusterCursor::new(self.meta, dir_cluster);
+        let keep_head = dir_cluster == self.meta.root_unit() && self.root_head.is_empty();
+        let mut head: Option<Vec<u8>> = None;
 
         // We iterate blindly over the chain.
         cur.for_each_run(self.io, |io, run_start, run_len| {
@@ -151,6 +199,9 @@ impl<'a, IO: RimIO + ?Sized> ExFatWalker<'a, IO> {
             // Directories are rarely huge.
             let mut buf = vec![0u8; total];
             io.read_block_best_effort(off0, &mut buf, total)?;
+            if keep_head && head.is_none() {
+                head = Some(buf[..cs].to_vec());
+            }
 
             for chunk in buf.chunks_exact(32) {
                 stats.entries_scanned += 1;
@@ -204,6 +255,10 @@ impl<'a, IO: RimIO + ?Sized> ExFatWalker<'a, IO> {
             Ok(())
         })?;
 
+        if let Some(head) = head {
+            self.root_head = head;
+        }
+
         // Flush final
         if let (Some(p), Some(s)) = (raw_primary, raw_stream)
             && let Ok(e) = ExFatEntries::from_raw(&lfn_stack, &p, &s)
@@ -214,3 +269,21 @@ impl<'a, IO: RimIO + ?Sized> ExFatWalker<'a, IO> {
         Ok(entries)
     }
 }
+
+/// Reports names appearing more than once in one directory (ASCII case-insensitive).
+fn check_unique_names(dir_cluster: u32, children: &[ExFatEntries], rep: &mut VerifyReport) {
+    let mut names: Vec<String> = children
+        .iter()
+        .filter_map(|c| c.name().ok())
+        .map(|n| n.to_ascii_uppercase())
+        .collect();
+    names.sort_unstable();
+    for pair in names.windows(2) {
+        if pair[0] == pair[1] {
+            rep.push(Finding::err(
+                "WALK.DUPNAME",
+                format!("Duplicate name '{}' in dir cluster {dir_cluster}", pair[0]),
+            ));
+        }
+    }
+}
@@ -2,10 +2,21 @@ pub use crate::core::traits::*;
 
 use rimio::RimIO;
 
+use crate::core::errors::FsResult;
+
 use crate::fs::exfat::traits::*;
 
 pub struct ExFat;
 
+impl ExFat {
+    /// Marks the volume as cleanly unmounted (clears `VolumeDirty` in the
+    /// main and backup VBR and recomputes the boot checksum).
+    pub fn mark_clean<IO: RimIO + ?Sized>(io: &mut IO, meta: &ExFatMeta) -> FsResult {
+        crate::fs::exfat::utils::mark_clean(io, meta)?;
+        Ok(())
+    }
+}
+
 impl<'a> FsFilesystem<'a> for ExFat {
     type Meta = ExFatMeta;
     type AllocUnit = u32;
@@ -45,9 +45,10 @@ impl<'a, IO: RimIO + ?Sized> FsFormatter for ExFatFormatter<'a, IO> {
         let (upcase_len, upcase_checksum) = self.write_upcase_table()?;
         self.write_root_dir_cluster(upcase_len, upcase_checksum)?;
         self.allocate_system_clusters()?;
+        utils::mark_clean(self.io, self.meta)?;
 
         self.io.flush()?;
-        Ok(())
+        self.verify_boot_regions()
     }
 }
 
@@ -56,6 +57,23 @@ impl<'a, IO: RimIO + ?Sized> ExFatFormatter<'a, IO> {
         Self { io, meta }
     }
 
+    /// Re-reads both boot regions: each must match its checksum sector and the
+    /// backup must carry the main checksum, so lost or torn writes surface here.
+    fn verify_boot_regions(&mut self) -> FsFormatterResult {
+        let bps = self.meta.bytes_per_sector as usize;
+        let (main, main_bad) =
+            utils::read_boot_checksum(self.io, EXFAT_VBR_SECTOR * bps as u64, bps)?;
+        let (backup, backup_bad) =
+            utils::read_boot_checksum(self.io, EXFAT_VBR_BACKUP_SECTOR * bps as u64, bps)?;
+
+        if main_bad.is_some() || backup_bad.is_some() || main != backup {
+            return Err(FsFormatterError::Invalid(
+                "Boot region checksum mismatch after format",
+            ));
+        }
+        Ok(())
+    }
+
     fn write_vbr(&mut self) -> FsFormatterResult {
         let mut buf = Vec::with_capacity(12 * self.meta.bytes_per_sector as usize);
 
@@ -72,7 +90,7 @@ impl<'a, IO: RimIO + ?Sized> ExFatFormatter<'a, IO> {
             i == 106 || i == 107 || i == 112
         });
         // Sectors 1-8: Extended Boot Sectors
-        let ex = ExFatExBootSector::new();
+        let ex = ExFatExBootSector::new(self.meta.oem_name);
         for _i in 1..=8 {
             ex.to_raw_buffer(&mut buf);
             accumulate_checksum(&mut checksum, ex.as_bytes());
@@ -155,8 +173,8 @@ impl<'a, IO: RimIO + ?Sized> ExFatFormatter<'a, IO> {
         ExFatUpcaseEntry::new(self.meta.upcase_cluster, upcase_len, upcase_checksum)
             .to_raw_buffer(&mut buf);
         ExFatVolumeLabelEntry::new(self.meta.volume_label).to_raw_buffer(&mut buf);
-        if self.meta.volume_guid.is_some() {
-            ExFatGuidEntry::new(self.meta.volume_guid.unwrap()).to_raw_buffer(&mut buf);
+        if let Some(guid) = self.meta.volume_guid {
+            ExFatGuidEntry::new(guid).to_raw_buffer(&mut buf);
         }
 
         ExFatEodEntry::new().to_raw_buffer(&mut buf);
@@ -194,7 +212,10 @@ impl<'a, IO: RimIO + ?Sized> ExFatFormatter<'a, IO> {
 mod test {
     use crate::{
         core::cursor::ClusterMeta,
-        fs::exfat::{constant::EXFAT_FIRST_CLUSTER, prelude::*},
+        fs::exfat::{
+            constant::{EXFAT_FIRST_CLUSTER, EXFAT_VBR_BACKUP_SECTOR},
+            prelude::*,
+        },
     };
 
     fn hexdump(label: &str, data: &[u8]) {
@@ -495,6 +516,58 @@ mod test {
         hexdump("Root Directory", &root[..160]);
     }
 
+    #[test]
+    fn test_exfat_format_through_cache() {
+        let meta = ExFatMeta::new(SIZE_BYTES, Some("CACHED")).unwrap();
+
+        let mut plain = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut plain);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        let mut cached = vec![0u8; SIZE_BYTES as usize];
+        let mut mem = MemRimIO::new(&mut cached);
+        let mut io = CachedRimIO::new(&mut mem);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+        io.flush().unwrap();
+        drop(io);
+
+        assert!(plain == cached, "cached format differs from uncached run");
+    }
+
+    #[test]
+    fn test_exfat_format_growable() {
+        let meta = ExFatMeta::new(SIZE_BYTES, Some("GROW")).unwrap();
+
+        let mut sized = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut sized);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        let mut io = MemRimIO::new_growable();
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+        let grown = io.into_inner();
+
+        assert!(grown.len() <= sized.len());
+        assert!(grown[..] == sized[..grown.len()]);
+        assert!(sized[grown.len()..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_exfat_format_tee() {
+        let meta = ExFatMeta::new(SIZE_BYTES, Some("TEE")).unwrap();
+        let mut primary = vec![0u8; SIZE_BYTES as usize];
+        let mut mirror = MemRimIO::new_growable();
+        {
+            let mut io = MemRimIO::new(&mut primary);
+            let mut tee = TeeRimIO::new(&mut io, &mut mirror);
+            ExFatFormatter::new(&mut tee, &meta).format(false).unwrap();
+        }
+        let mirror = mirror.into_inner();
+
+        assert!(!mirror.is_empty());
+        assert!(primary[..mirror.len()] == mirror[..]);
+        assert!(primary[mirror.len()..].iter().all(|&b| b == 0));
+    }
+
     #[test]
     fn test_exfat_format() {
         let meta = ExFatMeta::new(SIZE_BYTES, Some("TESTVOL")).unwrap();
@@ -529,4 +602,64 @@ mod test {
             .collect::<Vec<u8>>();
         hexdump("FAT[2..10]", &fat_bytes);
     }
+
+    #[test]
+    fn format_catches_dropped_boot_writes() {
+        let meta = ExFatMeta::new(SIZE_BYTES, Some("TESTVOL")).unwrap();
+        let mut buffer = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buffer);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        // Losing the backup checksum sector leaves a region that doesn't validate.
+        let bps = meta.bytes_per_sector as u64;
+        let sector = (EXFAT_VBR_BACKUP_SECTOR + 11) * bps;
+        let mut buffer = vec![0u8; SIZE_BYTES as usize];
+        let mut mem = MemRimIO::new(&mut buffer);
+        let mut io = FaultyRimIO::new(&mut mem).drop_writes(sector..sector + bps);
+        assert!(matches!(
+            ExFatFormatter::new(&mut io, &meta).format(false),
+            Err(FsFormatterError::Invalid(_))
+        ));
+
+        // Losing an extended boot sector of the backup only: mark_clean rewrites a
+        // matching checksum, but it no longer agrees with the main region.
+        let sector = (EXFAT_VBR_BACKUP_SECTOR + 3) * bps;
+        let mut buffer = vec![0u8; SIZE_BYTES as usize];
+        let mut mem = MemRimIO::new(&mut buffer);
+        let mut io = FaultyRimIO::new(&mut mem).drop_writes(sector..sector + bps);
+        assert!(ExFatFormatter::new(&mut io, &meta).format(false).is_err());
+    }
+
+    #[test]
+    fn mkfs_exfat_profile_matches_cluster_table() {
+        use crate::core::{checker::FsChecker, profile::Profile};
+        use crate::fs::exfat::checker::ExFatChecker;
+
+        const MB: u64 = 1024 * 1024;
+        for (size, cluster) in [
+            (64 * MB, 4 * 1024),
+            (512 * MB, 32 * 1024),
+            (64 * 1024 * MB, 128 * 1024),
+        ] {
+            let meta = ExFatMeta::with_profile(size, None, Profile::MkfsExfat).unwrap();
+            assert_eq!(meta.bytes_per_cluster, cluster, "{size}");
+        }
+        assert!(ExFatMeta::with_profile(64 * MB, None, Profile::MkfsVfat).is_err());
+
+        let meta = ExFatMeta::with_profile(64 * MB, Some("PROFILE"), Profile::MkfsExfat).unwrap();
+        let mut buffer = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut buffer);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        // Extended boot sectors carry nothing but their signature
+        let bps = meta.bytes_per_sector as usize;
+        let mut ex = vec![0u8; bps];
+        io.read_at(bps as u64, &mut ex).unwrap();
+        assert!(ex[..bps - 2].iter().all(|&b| b == 0));
+        assert_eq!(&ex[bps - 2..], &[0x55, 0xAA]);
+        assert_eq!(ExFatMeta::from_io(&mut io).unwrap().oem_name, None);
+
+        let rep = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!rep.has_error(), "{rep}");
+    }
 }
@@ -60,33 +60,85 @@ impl<'a, IO: RimIO + ?Sized> ExFatInjector<'a, IO> {
         Ok(())
     }
 
-    fn write_chain_buffer(&mut self, handle: &ExFatHandle, buf: &[u8]) -> FsInjectorResult {
+    /// Write every complete cluster sitting in `ctx.buf` to disk, growing the
+    /// directory chain as needed, so the buffer never holds more than one
+    /// cluster plus the entry set that overflowed it.
+    fn spill_full_units(&mut self, ctx: &mut FsContext<ExFatHandle>) -> FsInjectorResult {
         let cs = self.meta.unit_size();
+        while ctx.buf.len() >= cs {
+            self.ensure_chain_capacity(&mut ctx.handle, ctx.flushed + 1)?;
+            let c = ctx.handle.cluster_chain[ctx.flushed];
+            self.io.write_at(self.meta.unit_offset(c), &ctx.buf[..cs])?;
+            ctx.buf.drain(..cs);
+            ctx.flushed += 1;
+        }
+        Ok(())
+    }
 
-        if handle.cluster_chain.len() > 1 {
-            let offsets: Vec<u64> = handle
-                .cluster_chain
-                .iter()
-                .map(|&c| self.meta.unit_offset(c))
-                .collect();
-
-            let mut full = vec![0u8; handle.cluster_chain.len() * cs];
-            full[..buf.len()].copy_from_slice(buf);
-            self.io.write_multi_at(&offsets, cs, &full)?;
-        } else {
-            let c = handle.cluster_chain[0];
+    /// Spill the top-of-stack context if it has grown past a cluster.
+    fn spill_current(&mut self) -> FsInjectorResult {
+        if let Some(mut ctx) = self.stack.pop() {
+            let res = self.spill_full_units(&mut ctx);
+            self.stack.push(ctx);
+            res?;
+        }
+        Ok(())
+    }
+
+    /// Terminate the directory, write its last (partial) cluster, commit FAT +
+    /// bitmap for the whole chain and link it into its parent if pending.
+    fn finish_dir(&mut self, mut ctx: FsContext<ExFatHandle>) -> FsInjectorResult {
+        let cs = self.meta.unit_size();
+        let total = ctx.flushed * cs + ctx.buf.len();
+        let ends_with_eod = ctx.buf.len() >= 32 && ctx.buf[ctx.buf.len() - 32] == EXFAT_EOD;
+        if total >= 32 && !ends_with_eod {
+            ExFatEodEntry::new().to_raw_buffer(&mut ctx.buf);
+        }
+        self.spill_full_units(&mut ctx)?;
+
+        let need_clusters = (ctx.flushed + ctx.buf.len().div_ceil(cs)).max(1);
+        self.ensure_chain_capacity(&mut ctx.handle, need_clusters)?;
+
+        if ctx.flushed < need_clusters {
+            let c = ctx.handle.cluster_chain[ctx.flushed];
             self.io
-                .write_block_best_effort(self.meta.unit_offset(c), buf, cs)?;
+                .write_block_best_effort(self.meta.unit_offset(c), &ctx.buf, cs)?;
         }
 
         // Update FAT + bitmap for the entire chain
-        fat::chain::write_chain::<IO, ExFatMeta>(self.io, self.meta, &handle.cluster_chain)?;
-        utils::write_bitmap(self.io, self.meta, &handle.cluster_chain)?;
+        fat::chain::write_chain::<IO, ExFatMeta>(self.io, self.meta, &ctx.handle.cluster_chain)?;
+        utils::write_bitmap(self.io, self.meta, &ctx.handle.cluster_chain)?;
+
+        let pending = self.pending_dirs.pop().unwrap_or(None);
+
+        if let Some(pd) = pending
+            && let Some(parent) = self.stack.last_mut()
+        {
+            let data_len = (need_clusters * cs) as u64;
+
+            ExFatEntries::dir_with_len(
+                &pd.name,
+                pd.first_cluster,
+                &pd.attr,
+                data_len,
+                &self.upcase,
+            )
+            .map_err(FsResolverError::Parsing)?
+            .to_raw_buffer(&mut parent.buf);
+            self.spill_current()?;
+        }
         Ok(())
     }
 }
 
 impl<'a, IO: RimIO + ?Sized> FsNodeInjector<ExFatHandle> for ExFatInjector<'a, IO> {
+    fn space(&self) -> Option<FsSpace> {
+        Some(FsSpace::from_allocator::<ExFatHandle, _>(
+            &*self.allocator,
+            self.meta.unit_size(),
+        ))
+    }
+
     fn set_root_context(&mut self, _: &FsNode) -> FsInjectorResult {
         let offset = self.meta.unit_offset(self.meta.root_unit());
 
@@ -132,6 +184,12 @@ impl<'a, IO: RimIO + ?Sized> FsNodeInjector<ExFatHandle> for ExFatInjector<'a, I
         size: u64,
         attr: &FileAttributes,
     ) -> FsInjectorResult {
+        if attr.is_symlink() {
+            return Err(FsInjectorError::Invalid(
+                "Symlinks are not supported on exFAT",
+            ));
+        }
+
         let cs = self.meta.unit_size();
         let need = (size as usize).div_ceil(cs).max(1);
 
@@ -165,82 +223,21 @@ impl<'a, IO: RimIO + ?Sized> FsNodeInjector<ExFatHandle> for ExFatInjector<'a, I
 
             entry.to_raw_buffer(&mut ctx.buf);
         }
-        Ok(())
+        self.spill_current()
     }
 
     fn flush_current(&mut self) -> FsInjectorResult {
-        if let Some(mut ctx) = self.stack.pop() {
-            // Check if the last entry is an EOD marker
-            if ctx.buf.len() >= 32 && ctx.buf[ctx.buf.len() - 32] != EXFAT_EOD {
-                ExFatEodEntry::new().to_raw_buffer(&mut ctx.buf);
-            }
-
-            let cs = self.meta.unit_size();
-            let used = ctx.buf.len();
-            let need_clusters = used.div_ceil(cs).max(1);
-
-            self.ensure_chain_capacity(&mut ctx.handle, need_clusters)?;
-            self.write_chain_buffer(&ctx.handle, &ctx.buf)?;
-
-            let pending = self.pending_dirs.pop().unwrap_or(None);
-
-            if let Some(pd) = pending
-                && let Some(parent) = self.stack.last_mut()
-            {
-                let bytes_used = ctx.buf.len() as u64;
-                let cluster_size = self.meta.unit_size();
-                // Round up to the next cluster
-                let data_len = bytes_used.div_ceil(cluster_size as u64) * cluster_size as u64;
-
-                ExFatEntries::dir_with_len(
-                    &pd.name,
-                    pd.first_cluster,
-                    &pd.attr,
-                    data_len,
-                    &self.upcase,
-                )
-                .map_err(FsResolverError::Parsing)?
-                .to_raw_buffer(&mut parent.buf);
-            }
+        if let Some(ctx) = self.stack.pop() {
+            self.finish_dir(ctx)?;
         }
         Ok(())
     }
 
     fn flush(&mut self) -> FsInjectorResult {
-        while let Some(mut ctx) = self.stack.pop() {
-            // Check if the last entry is an EOD marker
-            if ctx.buf.len() >= 32 && ctx.buf[ctx.buf.len() - 32] != EXFAT_EOD {
-                ExFatEodEntry::new().to_raw_buffer(&mut ctx.buf);
-            }
-
-            let cs = self.meta.unit_size();
-            let used = ctx.buf.len();
-            let need_clusters = used.div_ceil(cs).max(1);
-
-            self.ensure_chain_capacity(&mut ctx.handle, need_clusters)?;
-            self.write_chain_buffer(&ctx.handle, &ctx.buf)?;
-
-            let pending = self.pending_dirs.pop().unwrap_or(None);
-
-            if let Some(pd) = pending
-                && let Some(parent) = self.stack.last_mut()
-            {
-                let bytes_used = ctx.buf.len() as u64;
-                let cluster_size = self.meta.unit_size();
-                // Round up to the next cluster
-                let data_len = bytes_used.div_ceil(cluster_size as u64) * cluster_size as u64;
-
-                ExFatEntries::dir_with_len(
-                    &pd.name,
-                    pd.first_cluster,
-                    &pd.attr,
-                    data_len,
-                    &self.upcase,
-                )
-                .map_err(FsResolverError::Parsing)?
-                .to_raw_buffer(&mut parent.buf);
-            }
+        while let Some(ctx) = self.stack.pop() {
+            self.finish_dir(ctx)?;
         }
+        utils::mark_clean(self.io, self.meta)?;
         self.io.flush()?;
         Ok(())
     }
@@ -435,4 +432,115 @@ mod tests {
             assert!(is_set, "Cluster {cluster} should be marked in bitmap");
         }
     }
+
+    #[test]
+    fn test_exfat_system_volume_info_stub() {
+        const SIZE_BYTES: u64 = 32 * 1024 * 1024;
+        let meta = ExFatMeta::new(SIZE_BYTES, Some("TESTFS")).unwrap();
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![FsNode::File {
+                name: "readme.md".to_string(),
+                content: b"Test Readme".to_vec(),
+                attr: FileAttributes::new_file(),
+            }],
+        };
+        let opts = FsInjectOptions {
+            system_volume_info: true,
+            ..Default::default()
+        };
+
+        let mut allocator = ExFatAllocator::new(&meta);
+        let mut injector = ExFatInjector::new(&mut io, &mut allocator, &meta).unwrap();
+        injector.inject_tree_with(&tree, &opts).unwrap();
+
+        let root = ExFatResolver::new(&mut io, &meta).parse_tree("/*").unwrap();
+        let FsNode::Container { children, .. } = &root else {
+            panic!("root should be a container");
+        };
+        let svi = children
+            .iter()
+            .find(|c| c.name() == SYSTEM_VOLUME_INFORMATION)
+            .expect("System Volume Information missing");
+        assert!(svi.is_dir());
+        assert!(svi.attr().hidden && svi.attr().system);
+        assert!(children.iter().any(|c| c.name() == "readme.md"));
+
+        let report = ExFatChecker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!report.has_error(), "{report}");
+    }
+
+    #[test]
+    fn large_dir_is_streamed_cluster_by_cluster() {
+        use crate::core::traits::FsNodeInjector;
+
+        const SIZE_BYTES: u64 = 64 * 1024 * 1024;
+        const FILES: usize = 1500;
+        let meta = ExFatMeta::new(SIZE_BYTES, Some("BIGDIR")).unwrap();
+        let cs = meta.unit_size();
+
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        let mut allocator = ExFatAllocator::new(&meta);
+        let mut injector = ExFatInjector::new(&mut io, &mut allocator, &meta).unwrap();
+
+        injector
+            .set_root_context(&FsNode::Container {
+                attr: FileAttributes::new_dir(),
+                children: vec![],
+            })
+            .unwrap();
+        injector
+            .write_dir("big", &FileAttributes::new_dir())
+            .unwrap();
+
+        for i in 0..FILES {
+            let mut content = [0x5Au8; 1];
+            let mut src = MemRimIO::new(&mut content);
+            injector
+                .write_file(
+                    &format!("a_rather_long_file_name_{i:05}.bin"),
+                    &mut src,
+                    1,
+                    &FileAttributes::new_file(),
+                )
+                .unwrap();
+            let pending = injector.stack.last().unwrap().buf.len();
+            assert!(pending < cs, "dir buffer grew to {pending} bytes");
+        }
+        assert!(injector.stack.last().unwrap().flushed > 1);
+
+        injector.flush_current().unwrap();
+        injector.flush().unwrap();
+
+        let mut resolver = ExFatResolver::new(&mut io, &meta);
+        let names = resolver.read_dir("/big").unwrap();
+        assert_eq!(
+            names
+                .iter()
+                .filter(|n| n.starts_with("a_rather_long_file_name_"))
+                .count(),
+            FILES
+        );
+        assert!(resolver.read_dir("/").unwrap().iter().any(|n| n == "big"));
+    }
+
+    #[test]
+    fn test_exfat_max_fill_rejects_before_writing() {
+        const SIZE_BYTES: u64 = 32 * 1024 * 1024;
+        let meta = ExFatMeta::new(SIZE_BYTES, Some("TESTFS")).unwrap();
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+
+        let mut allocator = ExFatAllocator::new(&meta);
+        let mut injector = ExFatInjector::new(&mut io, &mut allocator, &meta).unwrap();
+        crate::core::test_support::check_max_fill(&mut injector);
+    }
 }
@@ -10,7 +10,7 @@ use zerocopy::FromBytes;
 pub use crate::core::meta::*;
 
 use crate::{
-    core::{FsError, FsResult, cursor::ClusterMeta},
+    core::{FsError, FsResult, cursor::ClusterMeta, profile::Profile},
     fs::exfat::{constant::*, types::*, upcase::UpcaseFlavor},
 };
 
@@ -20,6 +20,8 @@ pub struct ExFatMeta {
     pub volume_guid: Option<[u8; 16]>,
 
     pub volume_label: [u16; 11],
+    /// Tag written to the extended boot sectors, `None` leaves them zeroed
+    pub oem_name: Option<[u8; 8]>,
 
     pub bytes_per_sector: u16,
     pub sectors_per_cluster: u32,
@@ -63,6 +65,25 @@ impl ExFatMeta {
         )
     }
 
+    /// Builds the metadata with the cluster size and boot-sector tag
+    /// `profile` would pick for a volume of `size_bytes`.
+    pub fn with_profile(
+        size_bytes: u64,
+        volume_label: Option<&str>,
+        profile: Profile,
+    ) -> FsResult<Self> {
+        // exfatprogs and Windows share the cluster size table and leave the
+        // extended boot sectors blank.
+        let oem = match profile {
+            Profile::Rim => Some(oem_name()),
+            Profile::MkfsExfat | Profile::Windows => None,
+            Profile::MkfsVfat => return Err(FsError::Invalid("profile does not apply to exFAT")),
+        };
+        let mut meta = Self::new(size_bytes, volume_label)?;
+        meta.oem_name = oem;
+        Ok(meta)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn new_custom(
         volume_size_bytes: u64,
@@ -134,6 +155,7 @@ impl ExFatMeta {
             volume_id: vol_id,
             volume_guid: Some(guid),
             volume_label: volume_label_safe,
+            oem_name: Some(oem_name()),
             bytes_per_sector,
             sectors_per_cluster,
             bytes_per_cluster,
@@ -216,10 +238,18 @@ impl ExFatMeta {
 
         let guid = found_guid.map(|f| Some(f.guid)).unwrap_or(None);
 
+        let ex: ExFatExBootSector =
+            io.read_struct((EXFAT_VBR_SECTOR + 1) * bytes_per_sector as u64)?;
+        let oem_name = ex.reserved[..8]
+            .try_into()
+            .ok()
+            .filter(|tag: &[u8; 8]| tag.iter().any(|&b| b != 0));
+
         Ok(Self {
             volume_id: vbr.volume_serial,
             volume_guid: guid,
             volume_label,
+            oem_name,
             bytes_per_sector: bytes_per_sector as u16,
             sectors_per_cluster,
             bytes_per_cluster,
@@ -551,4 +581,26 @@ mod tests {
             meta.cluster_count, meta.bitmap_size_bytes
         );
     }
+
+    #[test]
+    fn test_try_unit_offset_rejects_out_of_range() {
+        use crate::core::{cursor::ClusterMeta, errors::FsCursorError};
+
+        let meta = ExFatMeta::new(8 * 1024 * 1024, Some("RANGE")).unwrap();
+        let last = meta.last_data_unit();
+
+        // System clusters sit below first_data_unit() but are still valid.
+        assert_eq!(
+            meta.try_unit_offset(meta.bitmap_cluster),
+            Ok(meta.unit_offset(meta.bitmap_cluster))
+        );
+        assert_eq!(meta.try_unit_offset(last), Ok(meta.unit_offset(last)));
+
+        for bad in [0, 1, last + 1, u32::MAX] {
+            assert_eq!(
+                meta.try_unit_offset(bad),
+                Err(FsCursorError::InvalidCluster(bad))
+            );
+        }
+    }
 }
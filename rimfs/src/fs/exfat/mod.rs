@@ -27,6 +27,7 @@ pub mod prelude {
     #[cfg(feature = "std")]
     pub use crate::core::StdResolver;
     pub use crate::core::errors::*;
+    pub use crate::core::profile::Profile;
     pub use crate::core::traits::*;
     pub use rimio::prelude::*;
 }
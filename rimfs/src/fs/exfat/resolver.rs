@@ -67,7 +67,7 @@ impl<'a, IO: RimIO + ?Sized> FsResolver for ExFatResolver<'a, IO> {
 
     fn read_file(&mut self, path: &str) -> FsResolverResult<Vec<u8>> {
         let entry = self.resolve_entry(path)?;
-        crate::ensure!(!entry.is_dir(), FsResolverError::Invalid("Expected a file"));
+        crate::ensure!(!entry.is_dir(), FsResolverError::Invalid("Not a file"));
 
         let size = entry.size();
         if size == 0 {
@@ -125,6 +125,48 @@ impl<'a, IO: RimIO + ?Sized> FsResolver for ExFatResolver<'a, IO> {
         Ok((entry.is_dir(), entry.first_cluster(), entry.size()))
     }
 
+    fn block_map(&mut self, path: &str) -> FsResolverResult<Vec<(u64, u64)>> {
+        let entry = self.resolve_entry(path)?;
+        crate::ensure!(!entry.is_dir(), FsResolverError::Invalid("Not a file"));
+
+        let size = entry.size() as u64;
+        let first_cluster = entry.first_cluster();
+        if size == 0 || first_cluster == 0 {
+            return Ok(Vec::new());
+        }
+
+        let cs = self.meta.unit_size() as u64;
+        let mut remaining = size.div_ceil(cs);
+        let mut runs = Vec::new();
+        let mut push_run = |start: u32, len: u32| {
+            if remaining == 0 {
+                return;
+            }
+            let take = core::cmp::min(len as u64, remaining);
+            runs.push((self.meta.unit_offset(start), take * cs));
+            remaining -= take;
+        };
+
+        if entry.stream.is_contiguous() {
+            let mut cur = LinearCursor::from_len_bytes_safe(self.meta, first_cluster, size);
+            cur.for_each_run(self.io, |_, start, len| {
+                push_run(start, len);
+                Ok(())
+            })?;
+        } else {
+            let mut cur = ClusterCursor::new_safe(self.meta, first_cluster);
+            cur.for_each_run(self.io, |_, start, len| {
+                push_run(start, len);
+                Ok(())
+            })?;
+        }
+
+        if remaining > 0 {
+            crate::bail!("short_chain");
+        }
+        Ok(runs)
+    }
+
     fn read_attributes(&mut self, path: &str) -> FsResolverResult<FileAttributes> {
         if path.is_empty() || path == "/" {
             return Ok(FileAttributes::new_dir());
@@ -334,3 +376,40 @@ pub fn find_in_dir<IO: RimIO + ?Sized>(
         Err(e) => Err(FsResolverError::Cursor(e)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::core::test_support::*;
+    use crate::fs::exfat::prelude::*;
+
+    /// A fresh volume holding [`sample_tree`].
+    fn sample_volume() -> (Vec<u8>, ExFatMeta) {
+        let meta = ExFatMeta::new(SAMPLE_VOLUME_BYTES, Some("MAP")).unwrap();
+        let unit = meta.unit_size();
+
+        let mut buf = vec![0u8; SAMPLE_VOLUME_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        ExFatFormatter::new(&mut io, &meta).format(false).unwrap();
+        let mut allocator = ExFatAllocator::new(&meta);
+        let mut injector = ExFatInjector::new(&mut io, &mut allocator, &meta).unwrap();
+        injector.inject_tree(&sample_tree(unit)).unwrap();
+        injector.flush().unwrap();
+        (buf, meta)
+    }
+
+    #[test]
+    fn exists_checks_paths() {
+        let (mut buf, meta) = sample_volume();
+        let mut io = MemRimIO::new(&mut buf);
+        check_exists(&mut ExFatResolver::new(&mut io, &meta));
+    }
+
+    #[test]
+    fn block_map_covers_file_data() {
+        let (mut buf, meta) = sample_volume();
+        let unit = meta.unit_size();
+        let mut io = MemRimIO::new(&mut buf);
+        let runs = check_block_map(&mut ExFatResolver::new(&mut io, &meta), unit);
+        check_runs_hold_data(&buf, &runs, unit);
+    }
+}
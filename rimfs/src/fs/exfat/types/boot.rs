@@ -229,10 +229,12 @@ pub struct ExFatExBootSector {
 }
 
 impl ExFatExBootSector {
-    pub fn new() -> Self {
+    pub fn new(oem_name: Option<[u8; 8]>) -> Self {
         let mut reserved = [0u8; 510];
-        reserved[..8].copy_from_slice(&oem_name());
-        reserved[502..].copy_from_slice(&oem_name());
+        if let Some(tag) = oem_name {
+            reserved[..8].copy_from_slice(&tag);
+            reserved[502..].copy_from_slice(&tag);
+        }
         Self {
             reserved,
             signature: EXFAT_SIGNATURE,
@@ -247,7 +249,7 @@ impl ExFatExBootSector {
 
 impl Default for ExFatExBootSector {
     fn default() -> Self {
-        Self::new()
+        Self::new(Some(oem_name()))
     }
 }
 
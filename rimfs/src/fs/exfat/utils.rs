@@ -3,8 +3,14 @@
 use alloc::vec;
 
 use crate::{
-    core::{resolver::*, utils::time_utils},
-    fs::exfat::meta::*,
+    core::{
+        resolver::*,
+        utils::{
+            checksum_utils::{accumulate_checksum, accumulate_checksum_with_escape},
+            time_utils,
+        },
+    },
+    fs::exfat::{constant::*, meta::*, types::VolumeFlags},
 };
 use rimio::prelude::*;
 use time::OffsetDateTime;
@@ -77,3 +83,58 @@ pub fn write_bitmap<IO: RimIO + ?Sized>(
     }
     Ok(())
 }
+
+/// Boot checksum of a 12-sector boot region held in `region` (the last
+/// sector, which stores the checksum, is not covered).
+fn boot_region_checksum(region: &[u8], bps: usize) -> u32 {
+    let mut checksum = 0u32;
+    accumulate_checksum_with_escape(&mut checksum, &region[..bps], |i, _b| {
+        i == 106 || i == 107 || i == 112
+    });
+    accumulate_checksum(&mut checksum, &region[bps..11 * bps]);
+    checksum
+}
+
+/// Reads the boot region starting at byte `base` and recomputes its checksum.
+///
+/// Returns the computed checksum and the offset of the first word of the
+/// checksum sector that disagrees with it (`None` when the region is valid).
+pub fn read_boot_checksum<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    base: u64,
+    bps: usize,
+) -> RimIOResult<(u32, Option<usize>)> {
+    let mut region = vec![0u8; 12 * bps];
+    io.read_at(base, &mut region)?;
+
+    let checksum = boot_region_checksum(&region, bps);
+    let bad = region[11 * bps..]
+        .chunks_exact(4)
+        .position(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]) != checksum)
+        .map(|i| i * 4);
+    Ok((checksum, bad))
+}
+
+/// Clears `VolumeDirty` in the main and backup boot regions and rewrites
+/// their boot checksum sectors.
+pub fn mark_clean<IO: RimIO + ?Sized>(io: &mut IO, meta: &ExFatMeta) -> RimIOResult {
+    let bps = meta.bytes_per_sector as usize;
+
+    for sector in [EXFAT_VBR_SECTOR, EXFAT_VBR_BACKUP_SECTOR] {
+        let base = sector * bps as u64;
+        let mut region = vec![0u8; 12 * bps];
+        io.read_at(base, &mut region)?;
+
+        let flags = VolumeFlags::from_bits(u16::from_le_bytes([region[106], region[107]]));
+        region[106..108].copy_from_slice(&flags.mark_clean().bits().to_le_bytes());
+
+        let checksum = boot_region_checksum(&region, bps);
+        for word in region[11 * bps..].chunks_exact_mut(4) {
+            word.copy_from_slice(&checksum.to_le_bytes());
+        }
+
+        io.write_at(base, &region[..bps])?;
+        io.write_at(base + 11 * bps as u64, &region[11 * bps..])?;
+    }
+    Ok(())
+}
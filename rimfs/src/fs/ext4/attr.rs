@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: MIT
 
 use crate::core::traits::FileAttributes;
+use crate::fs::ext4::constant::*;
 
 bitflags::bitflags! {
     #[derive(Debug, Clone, Copy)]
@@ -34,9 +35,11 @@ bitflags::bitflags! {
 impl FileAttributes {
     pub fn as_ext4_file_type(&self) -> u8 {
         if self.dir {
-            2 // directory
+            EXT4_FT_DIR
+        } else if self.is_symlink() {
+            EXT4_FT_SYMLINK
         } else {
-            1 // regular file
+            EXT4_FT_REG_FILE
         }
     }
 
@@ -44,6 +47,8 @@ impl FileAttributes {
         // Determine type
         let type_bits = if self.dir {
             Ext4Mode::DIR
+        } else if self.is_symlink() {
+            Ext4Mode::SYMLINK
         } else {
             Ext4Mode::REGULAR
         };
@@ -87,8 +92,10 @@ impl FileAttributes {
                 bits
             })
             .unwrap_or_else(|| {
-                // Default perms: 0755 for dir, 0644 for file
-                if self.dir {
+                // Default perms: 0755 for dir, 0777 for symlink, 0644 for file
+                if self.is_symlink() {
+                    Ext4Mode::from_bits_truncate(0o777)
+                } else if self.dir {
                     Ext4Mode::OWNER_R
                         | Ext4Mode::OWNER_W
                         | Ext4Mode::OWNER_X
@@ -8,7 +8,7 @@ use crate::core::checker::*;
 use crate::fs::ext4::{constant::*, group_layout::GroupLayout, meta::Ext4Meta};
 mod walker;
 
-use rimio::RimIO;
+use rimio::{RimIO, RimIOExt};
 
 use core::convert::TryInto;
 
@@ -24,6 +24,9 @@ pub struct Ext4CheckOptions {
     pub check_root_dir: bool,
     /// Verify superblock backups (sparse super groups)
     pub verify_sb_backups: bool,
+    /// Number of in-use inodes whose data blocks are read back during the
+    /// CONTENT phase (0 = disabled)
+    pub content_sample: u32,
 }
 
 impl Default for Ext4CheckOptions {
@@ -35,6 +38,7 @@ impl Default for Ext4CheckOptions {
             check_inode_bitmaps: true,
             check_root_dir: true,
             verify_sb_backups: true,
+            content_sample: 0,
         }
     }
 }
@@ -133,6 +137,17 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for Ext4Checker<'a, IO> {
         Ok(())
     }
 
+    fn check_content(
+        &mut self,
+        opt: &Self::Options,
+        rep: &mut VerifyReport,
+    ) -> FsCheckerResult<()> {
+        if opt.content_sample > 0 {
+            check_content_sample(self.io, self.meta, opt.content_sample, rep)?;
+        }
+        Ok(())
+    }
+
     fn fast_check(&mut self) -> FsCheckerResult {
         let opt = Ext4CheckOptions {
             phases: VerifyPhases::BOOT | VerifyPhases::GEOMETRY | VerifyPhases::ROOT,
@@ -141,6 +156,7 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for Ext4Checker<'a, IO> {
             check_inode_bitmaps: false,
             check_root_dir: true,
             verify_sb_backups: false,
+            content_sample: 0,
         };
         let rep = self.check_with(&opt)?;
         if rep.has_error() {
@@ -638,6 +654,123 @@ fn check_inode_bitmap<IO: RimIO + ?Sized>(
     Ok(())
 }
 
+/* =========================================================================
+   Content Sampling
+========================================================================= */
+
+/// Reads back the data blocks of up to `sample` in-use inodes, spread evenly
+/// over the inode tables, and reports every block that fails to read.
+fn check_content_sample<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    meta: &Ext4Meta,
+    sample: u32,
+    rep: &mut VerifyReport,
+) -> FsCheckerResult<()> {
+    let inode_size = EXT4_DEFAULT_INODE_SIZE as usize;
+    let block_size = meta.block_size as usize;
+
+    // 1. Collect in-use inodes that map data through extents
+    let mut candidates: Vec<(u32, [u8; EXT4_DEFAULT_INODE_SIZE as usize])> = Vec::new();
+    for group in 0..meta.group_count {
+        let layout = GroupLayout::compute(meta, group);
+        let mut table = vec![0u8; meta.inodes_per_group as usize * inode_size];
+        let offset = layout.inode_table_block as u64 * meta.block_size as u64;
+        if let Err(e) = io.read_at(offset, &mut table) {
+            rep.push(Finding::err(
+                "CONTENT.ITABLE",
+                format!("Group {group}: inode table unreadable ({e})"),
+            ));
+            continue;
+        }
+
+        for (i, raw) in table.chunks_exact(inode_size).enumerate() {
+            let i_mode = u16::from_le_bytes(raw[0..2].try_into().unwrap());
+            let i_links = u16::from_le_bytes(raw[26..28].try_into().unwrap());
+            let i_flags = u32::from_le_bytes(raw[32..36].try_into().unwrap());
+            if i_mode != 0 && i_links != 0 && i_flags & EXT4_INODE_FLAG_EXTENTS != 0 {
+                let inode_num = group * meta.inodes_per_group + i as u32 + 1;
+                candidates.push((inode_num, raw.try_into().unwrap()));
+            }
+        }
+    }
+
+    // 2. Pick an evenly spread subset
+    let step = (candidates.len() / sample as usize).max(1);
+    let mut sampled = 0u32;
+    let mut blocks_read = 0u64;
+    let mut unreadable = 0u64;
+    let mut block_buf = vec![0u8; block_size];
+
+    for (inode_num, raw) in candidates.iter().step_by(step).take(sample as usize) {
+        sampled += 1;
+
+        let eh_magic = u16::from_le_bytes(raw[40..42].try_into().unwrap());
+        let eh_entries = u16::from_le_bytes(raw[42..44].try_into().unwrap());
+        let eh_depth = u16::from_le_bytes(raw[46..48].try_into().unwrap());
+        if eh_magic != EXT4_EXTENT_HEADER_MAGIC {
+            rep.push(Finding::err(
+                "CONTENT.EXT",
+                format!("Inode {inode_num}: invalid extent header magic 0x{eh_magic:04X}"),
+            ));
+            continue;
+        }
+        if eh_depth != 0 {
+            rep.push(Finding::warn(
+                "CONTENT.EXT",
+                format!("Inode {inode_num}: extent tree depth {eh_depth} not sampled"),
+            ));
+            continue;
+        }
+
+        // 3. Resolve extents and read their blocks
+        for e in 0..(eh_entries as usize).min(4) {
+            let ext = 52 + e * 12;
+            let ee_len = u16::from_le_bytes(raw[ext + 4..ext + 6].try_into().unwrap());
+            let ee_start_hi = u16::from_le_bytes(raw[ext + 6..ext + 8].try_into().unwrap());
+            let ee_start_lo = u32::from_le_bytes(raw[ext + 8..ext + 12].try_into().unwrap());
+
+            // Lengths above 32768 mark unwritten extents: nothing to read back
+            if ee_len > 32768 {
+                continue;
+            }
+            let start = ((ee_start_hi as u64) << 32) | ee_start_lo as u64;
+
+            for block in start..start + ee_len as u64 {
+                if block >= meta.block_count as u64 {
+                    rep.push(Finding::err(
+                        "CONTENT.RANGE",
+                        format!("Inode {inode_num}: block {block} beyond end of filesystem"),
+                    ));
+                    unreadable += 1;
+                    break;
+                }
+                blocks_read += 1;
+                if let Err(e) = io.read_block_best_effort(
+                    block * meta.block_size as u64,
+                    &mut block_buf,
+                    block_size,
+                ) {
+                    rep.push(Finding::err(
+                        "CONTENT.READ",
+                        format!("Inode {inode_num}: block {block} unreadable ({e})"),
+                    ));
+                    unreadable += 1;
+                }
+            }
+        }
+    }
+
+    rep.push(Finding::info(
+        "CONTENT.SAMPLE",
+        format!(
+            "Sampled {sampled} of {} inodes, read {blocks_read} blocks, {unreadable} unreadable",
+            candidates.len()
+        ),
+    ));
+
+    Ok(())
+}
+
 /* =========================================================================
    Helpers
 ========================================================================= */
@@ -673,3 +806,63 @@ fn is_sparse_super_group(group: u32) -> bool {
     }
     n == 1
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::traits::FsResolver;
+    use crate::fs::ext4::prelude::*;
+
+    const SIZE_BYTES: u64 = 16 * 1024 * 1024;
+
+    #[test]
+    fn content_sample_flags_unreadable_block() {
+        let meta = Ext4Meta::new(SIZE_BYTES, Some("SAMPLE"));
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        Ext4Formatter::new(&mut io, &meta).format(false).unwrap();
+        let mut allocator = Ext4Allocator::new(&meta);
+        let mut injector = Ext4Injector::new(&mut io, &mut allocator, &meta);
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![FsNode::File {
+                name: "data.bin".to_string(),
+                content: vec![0xA5; 3 * meta.block_size as usize],
+                attr: FileAttributes::new_file(),
+            }],
+        };
+        injector.inject_tree(&tree).unwrap();
+        injector.flush().unwrap();
+
+        // Locate the second data block of the file
+        let mut resolver = Ext4Resolver::new(&mut io, &meta);
+        let (_, inode_num, _) = resolver.resolve_path("/data.bin").unwrap();
+        let raw = resolver.read_inode(inode_num).unwrap();
+        let extents = resolver.read_extents(&raw).unwrap();
+        let bad_block = extents[0].ee_start_lo as u64 + 1;
+        let bs = meta.block_size as u64;
+
+        let opt = Ext4CheckOptions {
+            phases: VerifyPhases::CONTENT,
+            content_sample: 64,
+            ..Default::default()
+        };
+
+        let rep = Ext4Checker::new(&mut io, &meta).check_with(&opt).unwrap();
+        assert!(!rep.has_error());
+
+        let mut faulty = FaultyRimIO::new(&mut io).fail_reads(bad_block * bs..(bad_block + 1) * bs);
+        let rep = Ext4Checker::new(&mut faulty, &meta)
+            .check_with(&opt)
+            .unwrap();
+        let hits: Vec<_> = rep
+            .findings
+            .iter()
+            .filter(|f| f.code == "CONTENT.READ")
+            .collect();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].msg.contains(&format!("block {bad_block}")));
+        assert!(hits[0].msg.contains(&format!("Inode {inode_num}")));
+    }
+}
@@ -27,6 +27,10 @@ pub const EXT4_SUPERBLOCK_SIZE: usize = 1024;
 
 pub const EXT4_SUPERBLOCK_OFFSET: u64 = 1024;
 
+// Fields past s_desc_size (inside s_reserved), as byte offsets in the superblock
+pub const EXT4_SB_CHECKSUM_SEED_OFFSET: usize = 0x270;
+pub const EXT4_SB_CHECKSUM_OFFSET: usize = 0x3FC;
+
 // Logical block where the superblock is located (in disk image)
 pub const EXT4_SUPERBLOCK_BLOCK_NUMBER: u32 = 0;
 
@@ -54,6 +58,10 @@ pub const EXT4_DEFAULT_INODES_PER_GROUP: u32 = 256;
 
 // BGDT entry size
 pub const EXT4_BGDT_ENTRY_SIZE: usize = 64;
+// Descriptor size without the 64bit feature
+pub const EXT4_MIN_BGDT_ENTRY_SIZE: usize = 32;
+// Offset of bg_checksum in a descriptor
+pub const EXT4_BG_CHECKSUM_OFFSET: usize = 0x1E;
 
 // Default UID / GID
 
@@ -98,6 +106,7 @@ pub const EXT4_FEATURE_INCOMPAT_MMP: u32 = 0x0100;
 pub const EXT4_FEATURE_INCOMPAT_FLEX_BG: u32 = 0x0200;
 pub const EXT4_FEATURE_INCOMPAT_EA_INODE: u32 = 0x0400;
 pub const EXT4_FEATURE_INCOMPAT_DIRDATA: u32 = 0x1000;
+pub const EXT4_FEATURE_INCOMPAT_CSUM_SEED: u32 = 0x2000;
 
 // Read-only compatible features
 pub const EXT4_FEATURE_RO_COMPAT_SPARSE_SUPER: u32 = 0x0001;
@@ -107,6 +116,7 @@ pub const EXT4_FEATURE_RO_COMPAT_HUGE_FILE: u32 = 0x0008;
 pub const EXT4_FEATURE_RO_COMPAT_GDT_CSUM: u32 = 0x0010;
 pub const EXT4_FEATURE_RO_COMPAT_DIR_NLINK: u32 = 0x0020;
 pub const EXT4_FEATURE_RO_COMPAT_EXTRA_ISIZE: u32 = 0x0040;
+pub const EXT4_FEATURE_RO_COMPAT_METADATA_CSUM: u32 = 0x0400;
 
 // Backup Groups
 
@@ -130,3 +140,6 @@ pub const EXT4_FT_BLKDEV: u8 = 4;
 pub const EXT4_FT_FIFO: u8 = 5;
 pub const EXT4_FT_SOCK: u8 = 6;
 pub const EXT4_FT_SYMLINK: u8 = 7;
+
+/// Symlink targets shorter than this are stored inline in the inode (`i_block`).
+pub const EXT4_FAST_SYMLINK_MAX: usize = 60;
@@ -2,11 +2,25 @@
 
 use rimio::RimIO;
 
+use crate::core::errors::FsResult;
 use crate::core::traits::*;
 use crate::fs::ext4::traits::*;
 
 pub struct Ext4;
 
+impl Ext4 {
+    /// Renames the volume, in the primary superblock and every backup copy.
+    pub fn set_label<IO: RimIO + ?Sized>(io: &mut IO, label: &str) -> FsResult {
+        crate::fs::ext4::utils::set_label(io, label)
+    }
+
+    /// Changes the filesystem UUID, in the primary superblock and every
+    /// backup copy, keeping metadata checksums valid.
+    pub fn set_uuid<IO: RimIO + ?Sized>(io: &mut IO, uuid: [u8; 16]) -> FsResult {
+        crate::fs::ext4::utils::set_uuid(io, uuid)
+    }
+}
+
 impl<'a> FsFilesystem<'a> for Ext4 {
     type Meta = Ext4Meta;
     type AllocUnit = u32;
@@ -8,7 +8,12 @@ use core::convert::TryInto;
 
 use crate::core::allocator::FsAllocator;
 use crate::{
-    core::{FsInjectorError, FsInjectorResult, injector::FsNodeInjector, traits::FileAttributes},
+    core::{
+        FsInjectorError, FsInjectorResult,
+        injector::{FsNodeInjector, FsSpace},
+        meta::FsMeta,
+        traits::FileAttributes,
+    },
     fs::ext4::{
         allocator::{Ext4Allocator, Ext4Handle},
         constant::*,
@@ -313,6 +318,56 @@ impl<'a, IO: RimIO + ?Sized> Ext4Injector<'a, IO> {
         Ok(())
     }
 
+    /// Write a symlink inode and link it into the current directory.
+    ///
+    /// Short targets become fast symlinks stored in the inode itself, longer
+    /// ones get a single data block.
+    fn write_symlink(
+        &mut self,
+        name: &str,
+        target: &str,
+        attr: &FileAttributes,
+    ) -> FsInjectorResult {
+        let target = target.as_bytes();
+        let block_size = self.meta.block_size;
+        if target.is_empty() {
+            return Err(FsInjectorError::Invalid("Empty symlink target"));
+        }
+        if target.len() >= block_size as usize {
+            return Err(FsInjectorError::Invalid("Symlink target too long"));
+        }
+
+        let (inode, inode_data) = if target.len() < EXT4_FAST_SYMLINK_MAX {
+            let handle = self
+                .allocator
+                .allocate_chain(0)
+                .map_err(|_| FsInjectorError::Other("Allocation failed"))?;
+            (handle.inode, Ext4Inode::fast_symlink(attr, target))
+        } else {
+            let handle = self
+                .allocator
+                .allocate_chain(1)
+                .map_err(|_| FsInjectorError::Other("Allocation failed"))?;
+            let block = handle.blocks[0];
+            self.write_block(block, target)?;
+            let inode = Ext4Inode::from_attr(
+                attr,
+                target.len() as u64,
+                1,
+                block_size.div_ceil(512),
+                &[Ext4Extent::new(0, block, 1)],
+            );
+            (handle.inode, inode)
+        };
+        self.write_metadata(inode, &inode_data.to_bytes())?;
+
+        let entry = Ext4DirEntry::from_attr(inode, name, attr);
+        if let Some(ctx) = self.stack.last_mut() {
+            entry.to_raw_buffer(&mut ctx.buf);
+        }
+        Ok(())
+    }
+
     fn create_lost_found(&mut self) -> FsInjectorResult {
         // If we are here, lost+found is MISSING from Root.
         // We need to:
@@ -361,6 +416,13 @@ impl<'a, IO: RimIO + ?Sized> Ext4Injector<'a, IO> {
 }
 
 impl<'a, IO: RimIO + ?Sized> FsNodeInjector<Ext4Handle> for Ext4Injector<'a, IO> {
+    fn space(&self) -> Option<FsSpace> {
+        Some(FsSpace::from_allocator::<Ext4Handle, _>(
+            &*self.allocator,
+            self.meta.unit_size(),
+        ))
+    }
+
     fn set_root_context(&mut self, _root: &crate::core::traits::FsNode) -> FsInjectorResult {
         // Use the pre-formatted root inode (inode 2), not allocating a new one.
         // The root directory was already written by the formatter.
@@ -516,6 +578,10 @@ impl<'a, IO: RimIO + ?Sized> FsNodeInjector<Ext4Handle> for Ext4Injector<'a, IO>
         size: u64,
         attr: &FileAttributes,
     ) -> FsInjectorResult {
+        if let Some(target) = &attr.symlink_target {
+            return self.write_symlink(name, target, attr);
+        }
+
         // Allocate inode and blocks
         let total_size = size as u32;
         let block_size = self.meta.block_size;
@@ -887,4 +953,66 @@ mod tests {
 
         println!("✓ Empty file injection verified");
     }
+
+    #[test]
+    fn test_ext4_symlink_roundtrip() {
+        let meta = Ext4Meta::new(SIZE_BYTES, Some("TESTFS"));
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Ext4Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        let long_target = "../".repeat(30) + "usr/lib/firmware";
+        let mut tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![
+                FsNode::File {
+                    name: "target.txt".to_string(),
+                    content: b"pointee".to_vec(),
+                    attr: FileAttributes::new_file(),
+                },
+                FsNode::File {
+                    name: "short".to_string(),
+                    content: vec![],
+                    attr: FileAttributes::new_symlink("target.txt"),
+                },
+                FsNode::File {
+                    name: "long".to_string(),
+                    content: vec![],
+                    attr: FileAttributes::new_symlink(long_target.clone()),
+                },
+            ],
+        };
+
+        let mut allocator = Ext4Allocator::new(&meta);
+        let mut injector = Ext4Injector::new(&mut io, &mut allocator, &meta);
+        injector.inject_tree(&tree).unwrap();
+
+        let mut resolver = Ext4Resolver::new(&mut io, &meta);
+        assert_eq!(resolver.read_link("/short").unwrap(), "target.txt");
+        assert_eq!(resolver.read_link("/long").unwrap(), long_target);
+        assert!(resolver.read_link("/target.txt").is_err());
+
+        let mut parsed = resolver.parse_tree("/*").unwrap();
+        if let FsNode::Container { children, .. } = &mut parsed {
+            children.retain(|c| c.name() != "lost+found");
+        }
+        tree.sort_children_recursively();
+        parsed.sort_children_recursively();
+        assert!(tree.structural_eq(&parsed), "{parsed}");
+
+        let report = Ext4Checker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!report.has_error(), "{report}");
+    }
+
+    #[test]
+    fn test_ext4_max_fill_rejects_before_writing() {
+        let meta = Ext4Meta::new(SIZE_BYTES, Some("TESTFS"));
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Ext4Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        let mut allocator = Ext4Allocator::new(&meta);
+        let mut injector = Ext4Injector::new(&mut io, &mut allocator, &meta);
+        crate::core::test_support::check_max_fill(&mut injector);
+    }
 }
@@ -4,7 +4,7 @@
 use alloc::string::{String, ToString};
 
 use crate::{
-    core::{FsResult, traits::FsMeta, utils::volume::generate_volume_id_128},
+    core::{FsParsingError, FsResult, traits::FsMeta, utils::volume::generate_volume_id_128},
     fs::ext4::{constant::*, types::Ext4Superblock},
 };
 use rimio::{RimIO, RimIOStructExt};
@@ -94,6 +94,23 @@ impl Ext4Meta {
             sb.s_inodes_per_group,
         ))
     }
+
+    /// Reads the filesystem UUID (`s_uuid`) from the primary superblock.
+    pub fn read_uuid<IO: RimIO + ?Sized>(io: &mut IO) -> FsResult<[u8; 16]> {
+        Ok(read_superblock(io)?.s_uuid)
+    }
+
+    /// Reads the volume name (`s_volume_name`) from the primary superblock,
+    /// without its NUL padding.
+    pub fn read_label<IO: RimIO + ?Sized>(io: &mut IO) -> FsResult<String> {
+        let sb = read_superblock(io)?;
+        let end = sb
+            .s_volume_name
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(sb.s_volume_name.len());
+        Ok(String::from_utf8_lossy(&sb.s_volume_name[..end]).to_string())
+    }
 }
 
 impl FsMeta<u32> for Ext4Meta {
@@ -132,6 +149,15 @@ impl FsMeta<u32> for Ext4Meta {
     }
 }
 
+pub(crate) fn read_superblock<IO: RimIO + ?Sized>(io: &mut IO) -> FsResult<Ext4Superblock> {
+    let sb: Ext4Superblock = io.read_struct(EXT4_SUPERBLOCK_OFFSET)?;
+    crate::ensure!(
+        sb.is_valid(),
+        FsParsingError::Invalid("Invalid superblock magic")
+    );
+    Ok(sb)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,4 +309,128 @@ mod tests {
 
         println!("✓ FsMeta trait implementation verified");
     }
+
+    #[test]
+    fn uuid_and_label_read_back_from_superblock() {
+        use crate::core::formatter::FsFormatter;
+        use crate::fs::ext4::formatter::Ext4Formatter;
+        use rimio::prelude::MemRimIO;
+
+        const SIZE_BYTES: u64 = 32 * 1024 * 1024;
+        let uuid: [u8; 16] = core::array::from_fn(|i| 0xA0 + i as u8);
+        let meta = Ext4Meta::new_custom(
+            SIZE_BYTES,
+            Some("rootfs"),
+            Some(uuid),
+            EXT4_DEFAULT_BLOCK_SIZE,
+            EXT4_DEFAULT_INODES_PER_GROUP,
+        );
+
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        assert!(Ext4Meta::read_label(&mut io).is_err());
+
+        Ext4Formatter::new(&mut io, &meta).format(false).unwrap();
+        assert_eq!(Ext4Meta::read_uuid(&mut io).unwrap(), uuid);
+        assert_eq!(Ext4Meta::read_label(&mut io).unwrap(), "rootfs");
+    }
+
+    #[test]
+    fn set_label_and_uuid_update_backup_superblocks() {
+        use crate::core::formatter::FsFormatter;
+        use crate::core::utils::checksum_utils::crc32c;
+        use crate::fs::ext4::{filesystem::Ext4, formatter::Ext4Formatter};
+        use rimio::prelude::*;
+
+        // Two groups: group 1 holds a sparse superblock copy
+        const SIZE_BYTES: u64 = 64 * 1024 * 1024;
+        let meta = Ext4Meta::new(SIZE_BYTES, Some("before"));
+        assert_eq!(meta.group_count, 2);
+        let backup =
+            (meta.first_data_block + meta.blocks_per_group) as u64 * meta.block_size as u64;
+
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Ext4Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        Ext4::set_label(&mut io, "after").unwrap();
+        assert_eq!(Ext4Meta::read_label(&mut io).unwrap(), "after");
+        let copy: Ext4Superblock = io.read_struct(backup).unwrap();
+        assert_eq!(&copy.s_volume_name, b"after\0\0\0\0\0\0\0\0\0\0\0");
+
+        let uuid = [0x42; 16];
+        Ext4::set_uuid(&mut io, uuid).unwrap();
+        assert_eq!(Ext4Meta::read_uuid(&mut io).unwrap(), uuid);
+        let copy: Ext4Superblock = io.read_struct(backup).unwrap();
+        assert_eq!(copy.s_uuid, uuid);
+
+        // With metadata_csum, the old UUID keeps seeding the checksums
+        for off in [EXT4_SUPERBLOCK_OFFSET, backup] {
+            let mut sb: Ext4Superblock = io.read_struct(off).unwrap();
+            sb.s_feature_ro_compat |= EXT4_FEATURE_RO_COMPAT_METADATA_CSUM;
+            sb.update_checksum();
+            io.write_struct(off, &sb).unwrap();
+        }
+        Ext4::set_uuid(&mut io, [0x17; 16]).unwrap();
+        Ext4::set_label(&mut io, "csum").unwrap();
+        for off in [EXT4_SUPERBLOCK_OFFSET, backup] {
+            let sb: Ext4Superblock = io.read_struct(off).unwrap();
+            assert_eq!(sb.s_uuid, [0x17; 16]);
+            assert_eq!(&sb.s_volume_name[..5], b"csum\0");
+            assert_ne!(sb.s_feature_incompat & EXT4_FEATURE_INCOMPAT_CSUM_SEED, 0);
+            assert_eq!(sb.checksum_seed(), crc32c(!0, &uuid));
+            assert_eq!(sb.checksum(), sb.compute_checksum());
+        }
+
+        assert!(matches!(
+            Ext4::set_label(&mut io, "seventeen-bytes!!"),
+            Err(crate::core::FsError::Invalid(_))
+        ));
+        assert_eq!(Ext4Meta::read_label(&mut io).unwrap(), "csum");
+    }
+
+    #[test]
+    fn set_uuid_rewrites_gdt_checksums() {
+        use crate::core::formatter::FsFormatter;
+        use crate::core::utils::checksum_utils::crc16;
+        use crate::fs::ext4::{filesystem::Ext4, formatter::Ext4Formatter};
+        use rimio::prelude::*;
+
+        const SIZE_BYTES: u64 = 64 * 1024 * 1024;
+        let meta = Ext4Meta::new(SIZE_BYTES, Some("gdt"));
+        let block = meta.block_size as u64;
+        let backup = (meta.first_data_block + meta.blocks_per_group) as u64 * block;
+
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Ext4Formatter::new(&mut io, &meta).format(false).unwrap();
+        for off in [EXT4_SUPERBLOCK_OFFSET, backup] {
+            let mut sb: Ext4Superblock = io.read_struct(off).unwrap();
+            sb.s_feature_ro_compat |= EXT4_FEATURE_RO_COMPAT_GDT_CSUM;
+            io.write_struct(off, &sb).unwrap();
+        }
+
+        let uuid = [0x5A; 16];
+        Ext4::set_uuid(&mut io, uuid).unwrap();
+
+        // Each GDT copy sits in the block after its superblock
+        let sb = read_superblock(&mut io).unwrap();
+        let desc_size = sb.desc_size();
+        for sb_off in [EXT4_SUPERBLOCK_OFFSET, backup] {
+            let table_off = (sb_off / block + 1) * block;
+            let mut table = vec![0u8; meta.group_count as usize * desc_size];
+            io.read_at(table_off, &mut table).unwrap();
+            for (group, desc) in table.chunks_exact(desc_size).enumerate() {
+                let mut crc = crc16(!0, &uuid);
+                crc = crc16(crc, &(group as u32).to_le_bytes());
+                crc = crc16(crc, &desc[..EXT4_BG_CHECKSUM_OFFSET]);
+                crc = crc16(crc, &desc[EXT4_BG_CHECKSUM_OFFSET + 2..]);
+                let stored = u16::from_le_bytes([
+                    desc[EXT4_BG_CHECKSUM_OFFSET],
+                    desc[EXT4_BG_CHECKSUM_OFFSET + 1],
+                ]);
+                assert_eq!(stored, crc, "group {group} of GDT at {table_off}");
+            }
+        }
+    }
 }
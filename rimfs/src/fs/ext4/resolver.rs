@@ -66,6 +66,17 @@ impl<'a, IO: RimIO + ?Sized> Ext4Resolver<'a, IO> {
         false
     }
 
+    /// Parse inode mode to determine if it's a symbolic link
+    pub(crate) fn inode_is_symlink(&self, inode_buf: &[u8]) -> bool {
+        if let Some(chunk) = inode_buf.get(0..2)
+            && let Ok(arr) = chunk.try_into()
+        {
+            let i_mode = u16::from_le_bytes(arr);
+            return (i_mode & 0xF000) == Ext4Mode::SYMLINK.bits();
+        }
+        false
+    }
+
     /// Get inode size (lower 32 bits)
     pub(crate) fn inode_size(&self, inode_buf: &[u8]) -> u32 {
         if let Some(chunk) = inode_buf.get(4..8)
@@ -120,12 +131,47 @@ impl<'a, IO: RimIO + ?Sized> Ext4Resolver<'a, IO> {
         Ok(extents)
     }
 
+    /// Read the target of a symlink inode (inline for fast symlinks, else from its data block)
+    fn read_link_target(&mut self, inode_buf: &[u8]) -> FsResolverResult<String> {
+        let size = self.inode_size(inode_buf) as usize;
+        let bytes = if size < EXT4_FAST_SYMLINK_MAX {
+            inode_buf[40..40 + size].to_vec()
+        } else {
+            let extents = self.read_extents(inode_buf)?;
+            let extent = extents
+                .first()
+                .ok_or(FsResolverError::Invalid("Symlink without data block"))?;
+            let mut buf = vec![0u8; size];
+            self.io
+                .read_at(
+                    extent.ee_start_lo as u64 * self.meta.block_size as u64,
+                    &mut buf,
+                )
+                .map_err(FsResolverError::IO)?;
+            buf
+        };
+        String::from_utf8(bytes)
+            .map_err(|_| FsResolverError::Invalid("Symlink target is not UTF-8"))
+    }
+
+    /// Returns the target of the symlink at `path`.
+    pub fn read_link(&mut self, path: &str) -> FsResolverResult<String> {
+        let (_, inode, _) = self.resolve_path(path)?;
+        let inode_buf = self.read_inode(inode)?;
+        if !self.inode_is_symlink(&inode_buf) {
+            return Err(FsResolverError::Invalid("Not a symlink"));
+        }
+        self.read_link_target(&inode_buf)
+    }
+
     /// Read file content given inode number
+    ///
+    /// Symlinks have no content: their target is exposed through the attributes.
     fn read_file_content(&mut self, inode_num: u32) -> FsResolverResult<Vec<u8>> {
         let inode_buf = self.read_inode(inode_num)?;
         let size = self.inode_size(&inode_buf) as usize;
 
-        if size == 0 {
+        if size == 0 || self.inode_is_symlink(&inode_buf) {
             return Ok(Vec::new());
         }
 
@@ -275,6 +321,22 @@ impl<'a, IO: RimIO + ?Sized> Ext4Resolver<'a, IO> {
         Ok(entries)
     }
 
+    /// Walks `path` through directory entries, returning `(is_dir, inode)`.
+    fn lookup(&mut self, path: &str) -> FsResolverResult<(bool, u32)> {
+        let components = split_path(path);
+        let mut current = (true, EXT4_ROOT_INODE);
+
+        for comp in components {
+            crate::ensure!(current.0, FsResolverError::Invalid("Expected a directory"));
+            let entry = self
+                .find_in_dir(current.1, comp)?
+                .ok_or(FsResolverError::NotFound)?;
+            current = (entry.is_dir(), entry.inode);
+        }
+
+        Ok(current)
+    }
+
     /// Find an entry by name in a directory
     fn find_in_dir(
         &mut self,
@@ -344,7 +406,12 @@ impl<'a, IO: RimIO + ?Sized> FsResolver for Ext4Resolver<'a, IO> {
             if i == components.len() - 1 {
                 // Last component: read inode for attributes
                 let inode_buf = self.read_inode(entry.inode)?;
-                return Ok(self.parse_attributes(&inode_buf, entry.is_dir()));
+                let mut attr = self.parse_attributes(&inode_buf, entry.is_dir());
+                if self.inode_is_symlink(&inode_buf) {
+                    attr.archive = false;
+                    attr.symlink_target = Some(self.read_link_target(&inode_buf)?);
+                }
+                return Ok(attr);
             }
 
             if !entry.is_dir() {
@@ -358,37 +425,58 @@ impl<'a, IO: RimIO + ?Sized> FsResolver for Ext4Resolver<'a, IO> {
         Err(FsResolverError::Invalid("Invalid path"))
     }
 
-    fn resolve_path(&mut self, path: &str) -> FsResolverResult<(bool, u32, usize)> {
-        if path.is_empty() || path == "/" {
-            return Ok((true, EXT4_ROOT_INODE, 0));
+    fn block_map(&mut self, path: &str) -> FsResolverResult<Vec<(u64, u64)>> {
+        let (is_dir, inode, _) = self.resolve_path(path)?;
+        if is_dir {
+            return Err(FsResolverError::Invalid("Not a file"));
         }
 
-        let components = split_path(path);
-        let mut current_inode = EXT4_ROOT_INODE;
-
-        for (i, comp) in components.iter().enumerate() {
-            let entry = self
-                .find_in_dir(current_inode, comp)?
-                .ok_or(FsResolverError::NotFound)?;
+        let inode_buf = self.read_inode(inode)?;
+        let size = self.inode_size(&inode_buf) as u64;
+        if size == 0 || self.inode_is_symlink(&inode_buf) {
+            return Ok(Vec::new());
+        }
 
-            let is_last = i == components.len() - 1;
-            let is_dir = entry.is_dir();
+        let block_size = self.meta.block_size as u64;
+        let mut remaining = size.div_ceil(block_size);
+        let mut runs: Vec<(u64, u64)> = Vec::new();
 
-            if is_last {
-                // Get size from inode
-                let inode_buf = self.read_inode(entry.inode)?;
-                let size = self.inode_size(&inode_buf) as usize;
-                return Ok((is_dir, entry.inode, size));
+        for extent in self.read_extents(&inode_buf)? {
+            if remaining == 0 {
+                break;
             }
-
-            if !is_dir {
-                return Err(FsResolverError::Invalid("Expected a directory"));
+            let take = core::cmp::min(extent.ee_len as u64, remaining);
+            let offset = extent.ee_start_lo as u64 * block_size;
+            let len = take * block_size;
+            // Physically adjacent extents form a single run
+            match runs.last_mut() {
+                Some((o, l)) if *o + *l == offset => *l += len,
+                _ => runs.push((offset, len)),
             }
+            remaining -= take;
+        }
 
-            current_inode = entry.inode;
+        if remaining > 0 {
+            return Err(FsResolverError::Invalid("Extents shorter than file size"));
         }
+        Ok(runs)
+    }
 
-        Err(FsResolverError::Invalid("Invalid path"))
+    fn resolve_path(&mut self, path: &str) -> FsResolverResult<(bool, u32, usize)> {
+        if path.is_empty() || path == "/" {
+            return Ok((true, EXT4_ROOT_INODE, 0));
+        }
+
+        let (is_dir, inode) = self.lookup(path)?;
+        // Get size from inode
+        let inode_buf = self.read_inode(inode)?;
+        let size = self.inode_size(&inode_buf) as usize;
+        Ok((is_dir, inode, size))
+    }
+
+    /// Only walks directory entries, the target inode itself is never read.
+    fn exists(&mut self, path: &str) -> bool {
+        self.lookup(path).is_ok()
     }
 }
 
@@ -444,3 +532,40 @@ impl<'a, IO: RimIO + ?Sized> Ext4Resolver<'a, IO> {
         attr
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::core::test_support::*;
+    use crate::fs::ext4::prelude::*;
+
+    /// A fresh volume holding [`sample_tree`].
+    fn sample_volume() -> (Vec<u8>, Ext4Meta) {
+        let meta = Ext4Meta::new(SAMPLE_VOLUME_BYTES, Some("MAP"));
+        let unit = meta.block_size as usize;
+
+        let mut buf = vec![0u8; SAMPLE_VOLUME_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Ext4Formatter::new(&mut io, &meta).format(false).unwrap();
+        let mut allocator = Ext4Allocator::new(&meta);
+        let mut injector = Ext4Injector::new(&mut io, &mut allocator, &meta);
+        injector.inject_tree(&sample_tree(unit)).unwrap();
+        injector.flush().unwrap();
+        (buf, meta)
+    }
+
+    #[test]
+    fn exists_checks_paths() {
+        let (mut buf, meta) = sample_volume();
+        let mut io = MemRimIO::new(&mut buf);
+        check_exists(&mut Ext4Resolver::new(&mut io, &meta));
+    }
+
+    #[test]
+    fn block_map_covers_file_data() {
+        let (mut buf, meta) = sample_volume();
+        let unit = meta.block_size as usize;
+        let mut io = MemRimIO::new(&mut buf);
+        let runs = check_block_map(&mut Ext4Resolver::new(&mut io, &meta), unit);
+        check_runs_hold_data(&buf, &runs, unit);
+    }
+}
@@ -134,6 +134,16 @@ impl Ext4Inode {
         inode
     }
 
+    /// Create a fast symlink inode: the target is stored inline in `i_block`
+    /// (no extents, no data block). `target` must be shorter than 60 bytes.
+    pub fn fast_symlink(attr: &crate::core::traits::FileAttributes, target: &[u8]) -> Self {
+        let mut inode = Self::from_attr(attr, target.len() as u64, 1, 0, &[]);
+        inode.i_flags = 0;
+        inode.i_block = [0; 60];
+        inode.i_block[..target.len()].copy_from_slice(target);
+        inode
+    }
+
     /// Set the extent header and first extent in i_block
     pub fn set_extent(&mut self, extent: Ext4Extent) {
         let header = Ext4ExtentHeader {
@@ -179,6 +189,11 @@ impl Ext4Inode {
         (self.i_mode & 0xF000) == 0x4000
     }
 
+    /// Check if this is a symbolic link
+    pub fn is_symlink(&self) -> bool {
+        (self.i_mode & 0xF000) == 0xA000
+    }
+
     /// Check if this is a regular file
     pub fn is_file(&self) -> bool {
         (self.i_mode & 0xF000) == 0x8000
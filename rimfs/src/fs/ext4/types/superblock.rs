@@ -3,6 +3,7 @@
 
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
+use crate::core::utils::checksum_utils::crc32c;
 use crate::fs::ext4::constant::*;
 
 /// EXT4 Superblock structure (1024 bytes)
@@ -208,6 +209,63 @@ impl Ext4Superblock {
         1024 << self.s_log_block_size
     }
 
+    /// Number of block groups
+    pub fn group_count(&self) -> u32 {
+        self.s_blocks_count_lo
+            .saturating_sub(self.s_first_data_block)
+            .div_ceil(self.s_blocks_per_group.max(1))
+    }
+
+    /// Size of one block group descriptor on disk
+    pub fn desc_size(&self) -> usize {
+        if self.s_feature_incompat & EXT4_FEATURE_INCOMPAT_64BIT != 0 {
+            (self.s_desc_size as usize).max(EXT4_MIN_BGDT_ENTRY_SIZE)
+        } else {
+            EXT4_MIN_BGDT_ENTRY_SIZE
+        }
+    }
+
+    /// Whether `metadata_csum` is enabled
+    pub fn has_metadata_csum(&self) -> bool {
+        self.s_feature_ro_compat & EXT4_FEATURE_RO_COMPAT_METADATA_CSUM != 0
+    }
+
+    /// Seed of the metadata checksums (`s_checksum_seed`)
+    pub fn checksum_seed(&self) -> u32 {
+        self.reserved_u32(EXT4_SB_CHECKSUM_SEED_OFFSET)
+    }
+
+    pub fn set_checksum_seed(&mut self, seed: u32) {
+        self.set_reserved_u32(EXT4_SB_CHECKSUM_SEED_OFFSET, seed);
+    }
+
+    /// Stored superblock checksum (`s_checksum`)
+    pub fn checksum(&self) -> u32 {
+        self.reserved_u32(EXT4_SB_CHECKSUM_OFFSET)
+    }
+
+    /// CRC32C of the superblock up to `s_checksum`
+    pub fn compute_checksum(&self) -> u32 {
+        crc32c(!0, &self.as_bytes()[..EXT4_SB_CHECKSUM_OFFSET])
+    }
+
+    /// Refreshes `s_checksum` after an edit
+    pub fn update_checksum(&mut self) {
+        let crc = self.compute_checksum();
+        self.set_reserved_u32(EXT4_SB_CHECKSUM_OFFSET, crc);
+    }
+
+    fn reserved_u32(&self, offset: usize) -> u32 {
+        let at = offset - core::mem::offset_of!(Self, s_reserved);
+        let r = self.s_reserved;
+        u32::from_le_bytes([r[at], r[at + 1], r[at + 2], r[at + 3]])
+    }
+
+    fn set_reserved_u32(&mut self, offset: usize, value: u32) {
+        let at = offset - core::mem::offset_of!(Self, s_reserved);
+        self.s_reserved[at..at + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
     /// Encode to raw bytes
     pub fn to_bytes(&self) -> [u8; EXT4_SUPERBLOCK_SIZE] {
         // Safe: Ext4Superblock is exactly EXT4_SUPERBLOCK_SIZE bytes by layout and static assert
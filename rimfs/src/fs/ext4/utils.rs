@@ -1,6 +1,16 @@
 // SPDX-License-Identifier: MIT
 
-use crate::fs::ext4::{constant::*, group_layout::GroupLayout, meta::Ext4Meta};
+use alloc::{vec, vec::Vec};
+
+use rimio::{RimIO, RimIOStructExt};
+
+use crate::core::{FsError, FsResult, utils::checksum_utils::*};
+use crate::fs::ext4::{
+    constant::*,
+    group_layout::GroupLayout,
+    meta::{Ext4Meta, read_superblock},
+    types::Ext4Superblock,
+};
 
 pub fn is_sparse_super_group(group_id: u32) -> bool {
     if group_id == 0 {
@@ -43,3 +53,114 @@ pub fn compute_used_inodes_in_group(group_id: u32) -> u32 {
         0
     }
 }
+
+/// Byte offsets of the superblock copies: the primary one first, then the
+/// backup of every group that carries one.
+fn superblock_offsets(sb: &Ext4Superblock) -> Vec<u64> {
+    let block_size = sb.block_size() as u64;
+    let sparse = sb.s_feature_ro_compat & EXT4_FEATURE_RO_COMPAT_SPARSE_SUPER != 0;
+
+    let mut out = vec![EXT4_SUPERBLOCK_OFFSET];
+    for group in 1..sb.group_count() {
+        if !sparse || is_sparse_super_group(group) {
+            let start = sb.s_first_data_block as u64 + group as u64 * sb.s_blocks_per_group as u64;
+            out.push(start * block_size);
+        }
+    }
+    out
+}
+
+/// Applies `edit` to every superblock copy. Copies are patched in place so
+/// they keep their own `s_block_group_nr`; their checksum is refreshed when
+/// `metadata_csum` is on. Returns the offsets that were rewritten.
+fn rewrite_superblocks<IO, F>(io: &mut IO, mut edit: F) -> FsResult<Vec<u64>>
+where
+    IO: RimIO + ?Sized,
+    F: FnMut(&mut Ext4Superblock),
+{
+    let primary = read_superblock(io)?;
+    let mut written = Vec::new();
+    for off in superblock_offsets(&primary) {
+        let mut sb: Ext4Superblock = io.read_struct(off)?;
+        if !sb.is_valid() {
+            continue;
+        }
+        edit(&mut sb);
+        if sb.has_metadata_csum() {
+            sb.update_checksum();
+        }
+        io.write_struct(off, &sb)?;
+        written.push(off);
+    }
+    Ok(written)
+}
+
+/// Recomputes the `gdt_csum` checksum of every group descriptor in the
+/// table that follows each superblock copy at `sb_offsets`.
+fn rewrite_gdt_checksums<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sb: &Ext4Superblock,
+    sb_offsets: &[u64],
+) -> FsResult {
+    let block_size = sb.block_size() as u64;
+    let desc_size = sb.desc_size();
+    let mut table = vec![0u8; sb.group_count() as usize * desc_size];
+
+    for &off in sb_offsets {
+        let table_off = (off / block_size + 1) * block_size;
+        io.read_at(table_off, &mut table)?;
+        for (group, desc) in table.chunks_exact_mut(desc_size).enumerate() {
+            let mut crc = crc16(!0, &sb.s_uuid);
+            crc = crc16(crc, &(group as u32).to_le_bytes());
+            crc = crc16(crc, &desc[..EXT4_BG_CHECKSUM_OFFSET]);
+            crc = crc16(crc, &desc[EXT4_BG_CHECKSUM_OFFSET + 2..]);
+            desc[EXT4_BG_CHECKSUM_OFFSET..EXT4_BG_CHECKSUM_OFFSET + 2]
+                .copy_from_slice(&crc.to_le_bytes());
+        }
+        io.write_at(table_off, &table)?;
+    }
+    Ok(())
+}
+
+/// Rewrites the volume name in the primary superblock and its backups.
+///
+/// Labels longer than the 16-byte `s_volume_name` are rejected.
+pub fn set_label<IO: RimIO + ?Sized>(io: &mut IO, label: &str) -> FsResult {
+    let mut name = [0u8; 16];
+    if label.len() > name.len() {
+        return Err(FsError::Invalid("ext4 label longer than 16 bytes"));
+    }
+    name[..label.len()].copy_from_slice(label.as_bytes());
+
+    rewrite_superblocks(io, |sb| sb.s_volume_name = name)?;
+    io.flush()?;
+    Ok(())
+}
+
+/// Rewrites the filesystem UUID in the primary superblock and its backups.
+///
+/// Under `metadata_csum` every checksum is seeded from the UUID, so the old
+/// seed is pinned with `metadata_csum_seed` (as `tune2fs -U` does) instead of
+/// rewriting all metadata. Under `gdt_csum` the group descriptor checksums,
+/// which cover the UUID, are recomputed.
+pub fn set_uuid<IO: RimIO + ?Sized>(io: &mut IO, uuid: [u8; 16]) -> FsResult {
+    let primary = read_superblock(io)?;
+    let pin_seed = primary.has_metadata_csum()
+        && primary.s_feature_incompat & EXT4_FEATURE_INCOMPAT_CSUM_SEED == 0;
+    let seed = crc32c(!0, &primary.s_uuid);
+
+    let written = rewrite_superblocks(io, |sb| {
+        if pin_seed {
+            sb.s_feature_incompat |= EXT4_FEATURE_INCOMPAT_CSUM_SEED;
+            sb.set_checksum_seed(seed);
+        }
+        sb.s_uuid = uuid;
+    })?;
+
+    if primary.s_feature_ro_compat & EXT4_FEATURE_RO_COMPAT_GDT_CSUM != 0 {
+        let sb = read_superblock(io)?;
+        rewrite_gdt_checksums(io, &sb, &written)?;
+    }
+    io.flush()?;
+    Ok(())
+}
@@ -16,7 +16,6 @@ pub fn check_boot<IO: RimIO + ?Sized>(
         Err(e) => rep.push(Finding::err("VBR.INVALID", e.msg())),
     }
     boot_compare_main_backup(io, meta, rep)?;
-    boot_geometry_sanity(meta, rep);
     Ok(())
 }
 
@@ -39,7 +38,7 @@ pub fn boot_compare_main_backup<IO: RimIO + ?Sized>(
     }
     Ok(())
 }
-pub fn boot_geometry_sanity(meta: &Fat32Meta, rep: &mut VerifyReport) {
+pub fn check_geometry(meta: &Fat32Meta, rep: &mut VerifyReport) {
     let bps = meta.bytes_per_sector as usize;
     let spc = meta.sectors_per_cluster as usize;
     if bps == 0 || (bps & (bps - 1)) != 0 {
@@ -60,6 +59,24 @@ pub fn boot_geometry_sanity(meta: &Fat32Meta, rep: &mut VerifyReport) {
     if meta.root_unit() < 2 {
         rep.push(Finding::err("BPB.ROOT", "Root cluster < 2"));
     }
+    if meta.cluster_count > FAT32_MAX_CLUSTERS {
+        rep.push(Finding::err(
+            "BPB.CLUSTERS",
+            format!(
+                "Cluster count {} above FAT32 maximum {}",
+                meta.cluster_count, FAT32_MAX_CLUSTERS
+            ),
+        ));
+    } else if meta.cluster_count < FAT32_MIN_CLUSTERS {
+        // Small volumes are still mounted as FAT32 by most drivers.
+        rep.push(Finding::warn(
+            "BPB.CLUSTERS",
+            format!(
+                "Cluster count {} below FAT32 minimum {} (FAT16 range)",
+                meta.cluster_count, FAT32_MIN_CLUSTERS
+            ),
+        ));
+    }
     rep.push(Finding::info(
         "BPB.OK",
         format!(
@@ -91,19 +108,13 @@ pub fn check_fsinfo_consistency<IO: RimIO + ?Sized>(
         return Ok(());
     }
 
-    // Real measurement (entire FAT scan) — O(n)
+    // Real measurement (entire FAT scan, streamed) — O(n)
     let mut free_measured: u32 = 0;
-    let start = 2u32;
-    let end = start + meta.cluster_count - 1;
-    let mut c = start;
-
-    while c <= end {
-        let e = fat::chain::read_entry(io, meta, c, 0)?;
-        if e == 0 {
+    fat::chain::for_each_entry(io, meta, 0, |c, e| {
+        if c >= FAT_FIRST_CLUSTER && e == 0 {
             free_measured += 1;
         }
-        c += 1;
-    }
+    })?;
 
     let diff = advertised.abs_diff(free_measured);
     let tol = ((meta.cluster_count as u64 * tol_percent as u64) / 100) as u32;
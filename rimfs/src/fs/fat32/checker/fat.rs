@@ -107,6 +107,7 @@ pub fn deep_walk<IO: RimIO + ?Sized>(io: &mut IO, meta: &Fat32Meta) -> FsChecker
     let first = meta.first_data_unit();
     let last = meta.last_data_unit();
     let span = (last - first) as usize;
+    let table = fat::chain::load_fat(io, meta, 0)?;
 
     let mut visited = vec![0u8; span.div_ceil(8)];
     #[inline(always)]
@@ -136,7 +137,7 @@ pub fn deep_walk<IO: RimIO + ?Sized>(io: &mut IO, meta: &Fat32Meta) -> FsChecker
             }
             mark(&mut visited, first, cur);
 
-            let next = fat::chain::read_entry(io, meta, cur, 0)?;
+            let next = table[cur as usize];
             len += 1;
             if len > meta.cluster_count as usize {
                 return Err(FsCheckerError::Invalid("Invalid FAT chain length"));
@@ -3,6 +3,7 @@
 use ::alloc::vec;
 
 pub use crate::core::checker::*;
+use crate::core::cursor::ClusterMeta;
 use crate::core::fat::chain as fat_chain;
 use crate::fs::fat32::meta::*;
 use rimio::prelude::*;
@@ -77,6 +78,15 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for Fat32Checker<'a, IO> {
         Ok(())
     }
 
+    fn check_geometry(
+        &mut self,
+        _opt: &Self::Options,
+        rep: &mut VerifyReport,
+    ) -> FsCheckerResult<()> {
+        boot::check_geometry(self.meta, rep);
+        Ok(())
+    }
+
     fn check_chain(&mut self, opt: &Self::Options, rep: &mut VerifyReport) -> FsCheckerResult<()> {
         if opt.fat_sample > 0 {
             fat::fat_sample(self.io, self.meta, opt.fat_sample, rep)?;
@@ -99,8 +109,9 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for Fat32Checker<'a, IO> {
         let bps = self.meta.bytes_per_sector as usize;
         let spc = self.meta.sectors_per_cluster as usize;
         let mut buf = vec![0u8; bps * spc];
+        let root = self.meta.try_unit_offset(self.meta.root_unit())?;
         self.io
-            .read_at(self.meta.unit_offset(self.meta.root_unit()), &mut buf)
+            .read_at(root, &mut buf)
             .map_err(FsCheckerError::IO)?;
         rep.push(Finding::info("ROOT.IO", "Root cluster readable"));
 
@@ -137,7 +148,10 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for Fat32Checker<'a, IO> {
 
     fn fast_check(&mut self) -> FsCheckerResult {
         let opt = Fat32CheckOptions {
-            phases: VerifyPhases::BOOT | VerifyPhases::CHAIN | VerifyPhases::ROOT,
+            phases: VerifyPhases::BOOT
+                | VerifyPhases::GEOMETRY
+                | VerifyPhases::CHAIN
+                | VerifyPhases::ROOT,
             fail_fast: true,
             fat_sample: 0,
             deep_fat_walk: true,
@@ -157,3 +171,86 @@ impl<'a, IO: RimIO + ?Sized> FsChecker for Fat32Checker<'a, IO> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::fat32::constant::FAT32_MAX_CLUSTERS;
+
+    #[test]
+    fn geometry_phase_flags_cluster_count_out_of_range() {
+        let mut meta = Fat32Meta::new(64 * 1024 * 1024, Some("GEOM")).unwrap();
+        meta.cluster_count = FAT32_MAX_CLUSTERS + 1;
+
+        // Geometry is meta-only: no formatted image needed.
+        let mut buf = vec![0u8; 512];
+        let mut io = MemRimIO::new(&mut buf);
+        let opt = Fat32CheckOptions {
+            phases: VerifyPhases::GEOMETRY,
+            ..Default::default()
+        };
+        let rep = Fat32Checker::new(&mut io, &meta).check_with(&opt).unwrap();
+
+        let f = rep
+            .findings
+            .iter()
+            .find(|f| f.sev == Severity::Error)
+            .expect("expected a geometry error");
+        assert_eq!(f.code, "BPB.CLUSTERS");
+        assert!(!rep.findings.iter().any(|f| f.code.starts_with("VBR.")));
+    }
+
+    #[test]
+    fn check_all_runs_through_read_only_io() {
+        use crate::core::formatter::FsFormatter;
+        use crate::fs::fat32::formatter::Fat32Formatter;
+
+        let meta = Fat32Meta::new(32 * 1024 * 1024, Some("RO")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut mem = MemRimIO::new(&mut buf);
+        Fat32Formatter::new(&mut mem, &meta).format(false).unwrap();
+
+        let mut io = ReadOnlyRimIO::new(&mut mem);
+        let rep = Fat32Checker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!rep.has_error(), "{rep}");
+
+        let err = Fat32Formatter::new(&mut io, &meta)
+            .format(false)
+            .unwrap_err();
+        assert!(err.to_string().contains("read-only"), "{err}");
+    }
+
+    #[test]
+    fn load_fat_matches_per_entry_reads() {
+        use crate::core::formatter::FsFormatter;
+        use crate::fs::fat32::formatter::Fat32Formatter;
+
+        let meta = Fat32Meta::new(32 * 1024 * 1024, Some("BULK")).unwrap();
+        let mut buf = vec![0u8; meta.volume_size_bytes as usize];
+        let mut mem = MemRimIO::new(&mut buf);
+        Fat32Formatter::new(&mut mem, &meta).format(false).unwrap();
+        fat_chain::write_chain::<_, Fat32Meta>(&mut mem, &meta, &[5, 6, 9, 7]).unwrap();
+
+        let mut io = IOCounter::new(&mut mem);
+        let table = fat_chain::load_fat(&mut io, &meta, 0).unwrap();
+        let bulk_reads = io.stats().reads;
+
+        assert_eq!(table.len(), meta.last_data_unit() as usize + 1);
+        let before = *io.stats();
+        for (c, &v) in table.iter().enumerate() {
+            assert_eq!(
+                fat_chain::read_entry(&mut io, &meta, c as u32, 0).unwrap(),
+                v
+            );
+        }
+        let single_reads = io.stats().delta(&before).reads;
+
+        assert_eq!(table[6], 9);
+        assert_eq!(table[9], 7);
+        assert_eq!(single_reads, table.len() as u64);
+        assert!(
+            bulk_reads * 100 < single_reads,
+            "{bulk_reads} vs {single_reads}"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: MIT
 
 pub const FAT_OEM_NAME: &[u8; 8] = b"RIM     "; // BS_OEMName
+pub const FAT_OEM_NAME_MKFS: &[u8; 8] = b"mkfs.fat"; // dosfstools
+pub const FAT_OEM_NAME_WINDOWS: &[u8; 8] = b"MSDOS5.0"; // Windows format
 
 pub fn oem_name() -> [u8; 8] {
     const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -35,6 +37,8 @@ pub const FAT_EOC: u32 = 0x0FFFFFF8;
 pub const FAT_FIRST_CLUSTER: u32 = 2;
 pub const FAT_ROOT_CLUSTER: u32 = 2; // BPB_RootClus
 pub const FAT_BAD_CLUSTER: u32 = 0x0FFFFFF7;
+pub const FAT32_MIN_CLUSTERS: u32 = 65_525; // below this the volume is FAT16 by spec
+pub const FAT32_MAX_CLUSTERS: u32 = 268_435_444; // 0x0FFFFFF4 usable entries
 
 // Special Sector Numbers
 
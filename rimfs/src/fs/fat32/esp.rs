@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT
+
+//! EFI System Partition helpers.
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::{string::ToString, vec, vec::Vec};
+
+use rimio::{RimIO, RimIOStructExt};
+
+use crate::core::{errors::*, injector::FsNodeInjector, resolver::*, validate::Validate};
+use crate::fs::fat32::{allocator::*, constant::*, injector::*, meta::*, types::*};
+
+/// Default removable-media boot file name for the PE machine type of `image`.
+///
+/// Reads the COFF header of the EFI binary (`MZ` stub → `PE\0\0` → machine).
+pub fn efi_boot_file_name(image: &[u8]) -> FsResult<&'static str> {
+    let u16_at = |off: usize| {
+        image
+            .get(off..off + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+    };
+
+    if u16_at(0) != Some(0x5A4D) {
+        return Err(FsError::Invalid("EFI binary: missing MZ header"));
+    }
+    let pe = image
+        .get(0x3C..0x40)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+        .ok_or(FsError::Invalid("EFI binary: truncated DOS header"))?;
+    if image.get(pe..pe + 4) != Some(b"PE\0\0".as_slice()) {
+        return Err(FsError::Invalid("EFI binary: missing PE signature"));
+    }
+
+    match u16_at(pe + 4) {
+        Some(0x8664) => Ok("BOOTX64.EFI"),
+        Some(0x014C) => Ok("BOOTIA32.EFI"),
+        Some(0xAA64) => Ok("BOOTAA64.EFI"),
+        Some(0x01C2) | Some(0x01C4) => Ok("BOOTARM.EFI"),
+        Some(0x5064) => Ok("BOOTRISCV64.EFI"),
+        Some(0x6264) => Ok("BOOTLOONGARCH64.EFI"),
+        _ => Err(FsError::Invalid("EFI binary: unknown machine type")),
+    }
+}
+
+/// Injects `efi_image` as `/EFI/BOOT/BOOT<arch>.EFI` on a freshly formatted FAT32 volume.
+///
+/// The boot sector is validated first, so a non-FAT32 volume is rejected
+/// before anything is written.
+pub fn populate_esp_bytes<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    meta: &Fat32Meta,
+    efi_image: &[u8],
+) -> FsResult<()> {
+    let vbr: Fat32Vbr = io.read_struct(FAT_VBR_SECTOR * meta.bytes_per_sector as u64)?;
+    vbr.validate(meta)?;
+
+    let file_name = efi_boot_file_name(efi_image)?;
+
+    let tree = FsNode::Container {
+        attr: FileAttributes::new_dir(),
+        children: vec![FsNode::Dir {
+            name: "EFI".to_string(),
+            attr: FileAttributes::new_dir(),
+            children: vec![FsNode::Dir {
+                name: "BOOT".to_string(),
+                attr: FileAttributes::new_dir(),
+                children: vec![FsNode::File {
+                    name: file_name.to_string(),
+                    content: Vec::from(efi_image),
+                    attr: FileAttributes::new_file(),
+                }],
+            }],
+        }],
+    };
+
+    let mut allocator = Fat32Allocator::new(meta);
+    let mut injector = Fat32Injector::new(io, &mut allocator, meta);
+    injector.inject_tree(&tree)?;
+    Ok(())
+}
+
+/// Reads the EFI binary at `efi_binary_path` and injects it with [`populate_esp_bytes`].
+#[cfg(feature = "std")]
+pub fn populate_esp<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    meta: &Fat32Meta,
+    efi_binary_path: &std::path::Path,
+) -> FsResult<()> {
+    let image = std::fs::read(efi_binary_path).map_err(RimIOError::from)?;
+    populate_esp_bytes(io, meta, &image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::fat32::prelude::*;
+
+    fn fake_pe(machine: u16) -> Vec<u8> {
+        let mut img = vec![0u8; 1024];
+        img[0..2].copy_from_slice(b"MZ");
+        img[0x3C..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        img[0x80..0x84].copy_from_slice(b"PE\0\0");
+        img[0x84..0x86].copy_from_slice(&machine.to_le_bytes());
+        for (i, b) in img[0x100..].iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        img
+    }
+
+    #[test]
+    fn populate_esp_places_boot_binary() {
+        let meta = Fat32Meta::new(32 * 1024 * 1024, Some("ESP")).unwrap();
+        let mut img = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut img);
+        Fat32Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        let efi = fake_pe(0x8664);
+        populate_esp_bytes(&mut io, &meta, &efi).unwrap();
+
+        let mut resolver = Fat32Resolver::new(&mut io, &meta);
+        let content = resolver.read_file("/EFI/BOOT/BOOTX64.EFI").unwrap();
+        assert_eq!(content, efi);
+
+        assert_eq!(
+            efi_boot_file_name(&fake_pe(0xAA64)).unwrap(),
+            "BOOTAA64.EFI"
+        );
+        assert!(efi_boot_file_name(b"not a PE image").is_err());
+    }
+
+    #[test]
+    fn populate_esp_rejects_non_fat_volume() {
+        let meta = Fat32Meta::new(32 * 1024 * 1024, Some("ESP")).unwrap();
+        let mut img = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut img);
+
+        assert!(populate_esp_bytes(&mut io, &meta, &fake_pe(0x8664)).is_err());
+        assert!(img.iter().all(|&b| b == 0));
+    }
+}
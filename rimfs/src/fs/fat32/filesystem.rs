@@ -2,10 +2,40 @@ pub use crate::core::traits::*;
 
 use rimio::RimIO;
 
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+
+use crate::core::errors::FsResult;
+
 use crate::fs::fat32::traits::*;
 
 pub struct Fat32;
 
+impl Fat32 {
+    /// Reads the whole content of the file at `path`, e.g.
+    /// `/EFI/BOOT/BOOTX64.EFI`.
+    ///
+    /// Fails with `NotFound` when a component is missing and with `Invalid`
+    /// when `path` names a directory.
+    pub fn read_file<IO: RimIO + ?Sized>(
+        io: &mut IO,
+        meta: &Fat32Meta,
+        path: &str,
+    ) -> FsResult<Vec<u8>> {
+        Ok(Fat32Resolver::new(io, meta).read_file(path)?)
+    }
+
+    /// Deletes the file or empty directory at `path` and frees its clusters.
+    pub fn delete_file<IO: RimIO + ?Sized>(io: &mut IO, meta: &Fat32Meta, path: &str) -> FsResult {
+        crate::fs::fat32::utils::delete_file(io, meta, path)
+    }
+
+    /// Bytes still free on the volume (FSINFO hint, or a full FAT scan).
+    pub fn free_bytes<IO: RimIO + ?Sized>(io: &mut IO, meta: &Fat32Meta) -> FsResult<u64> {
+        crate::fs::fat32::utils::free_bytes(io, meta)
+    }
+}
+
 impl<'a> FsFilesystem<'a> for Fat32 {
     type Meta = Fat32Meta;
     type AllocUnit = u32;
@@ -44,3 +74,86 @@ impl<'a> FsFilesystem<'a> for Fat32 {
         "fat32"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::fs::fat32::prelude::*;
+
+    #[test]
+    fn delete_file_frees_clusters() {
+        use crate::core::fat;
+        use crate::fs::fat32::{constant::FAT_FSINFO_SECTOR, types::Fat32FsInfo};
+
+        const SIZE_BYTES: u64 = 32 * 1024 * 1024;
+        let meta = Fat32Meta::new(SIZE_BYTES, Some("DELETE")).unwrap();
+
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Fat32Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![FsNode::Dir {
+                name: "sub".to_string(),
+                attr: FileAttributes::new_dir(),
+                children: vec![
+                    FsNode::File {
+                        name: "a long file name.bin".to_string(),
+                        content: vec![0xA5; meta.unit_size() * 3 + 1],
+                        attr: FileAttributes::new_file(),
+                    },
+                    FsNode::File {
+                        name: "keep.txt".to_string(),
+                        content: b"keep".to_vec(),
+                        attr: FileAttributes::new_file(),
+                    },
+                ],
+            }],
+        };
+        let mut allocator = Fat32Allocator::new(&meta);
+        let mut injector = Fat32Injector::new(&mut io, &mut allocator, &meta);
+        injector.inject_tree(&tree).unwrap();
+
+        // Pretend FSINFO tracks the free count so the update is visible
+        let fsi_off = FAT_FSINFO_SECTOR * 512;
+        let mut fsi: Fat32FsInfo = io.read_struct(fsi_off).unwrap();
+        fsi.free_cluster_count = 1000;
+        io.write_struct(fsi_off, &fsi).unwrap();
+
+        let path = "/sub/a long file name.bin";
+        let (_, first, _) = Fat32Resolver::new(&mut io, &meta)
+            .resolve_path(path)
+            .unwrap();
+        let chain = fat::chain::read_chain(&mut io, &meta, first).unwrap();
+        assert_eq!(chain.len(), 4);
+
+        assert!(matches!(
+            Fat32::delete_file(&mut io, &meta, "/sub"),
+            Err(FsError::Invalid(_))
+        ));
+        Fat32::delete_file(&mut io, &meta, path).unwrap();
+
+        for &c in &chain {
+            for fi in 0..meta.num_fats {
+                assert_eq!(fat::chain::read_entry(&mut io, &meta, c, fi).unwrap(), 0);
+            }
+        }
+        let fsi: Fat32FsInfo = io.read_struct(fsi_off).unwrap();
+        assert_eq!({ fsi.free_cluster_count }, 1004);
+
+        let mut resolver = Fat32Resolver::new(&mut io, &meta);
+        assert!(!resolver.exists(path));
+        assert_eq!(resolver.read_dir("/sub").unwrap(), ["keep.txt"]);
+        assert!(matches!(
+            Fat32::delete_file(&mut io, &meta, path),
+            Err(FsError::Resolver(FsResolverError::NotFound))
+        ));
+
+        Fat32::delete_file(&mut io, &meta, "/sub/keep.txt").unwrap();
+        Fat32::delete_file(&mut io, &meta, "/sub").unwrap();
+        assert!(!Fat32Resolver::new(&mut io, &meta).exists("/sub"));
+
+        let rep = Fat32Checker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!rep.has_error(), "{rep}");
+    }
+}
@@ -136,6 +136,40 @@ mod tests {
         Fat32Meta::new(SIZE, Some("TESTFS")).unwrap()
     }
 
+    #[test]
+    fn test_format_io_counts() {
+        #[derive(Default)]
+        struct BootWrites(Vec<(u64, usize)>);
+        impl IOTracer for BootWrites {
+            fn on_write(&mut self, off: u64, len: usize) {
+                if off < 8 * 512 {
+                    self.0.push((off / 512, len));
+                }
+            }
+        }
+
+        let meta = make_meta_32mb();
+        let mut img = vec![0u8; meta.volume_size_bytes as usize];
+        let mut mem = MemRimIO::new(&mut img);
+        let mut io = IOCounter::new(&mut mem);
+        {
+            let mut traced = TracingIO::new(&mut io, BootWrites::default());
+            Fat32Formatter::new(&mut traced, &meta)
+                .format(false)
+                .unwrap();
+
+            // VBR, backup VBR, FSINFO, backup FSINFO: one sector each, nothing else
+            let (_, boot) = traced.into_parts();
+            assert_eq!(boot.0, vec![(0, 512), (6, 512), (1, 512), (7, 512)]);
+        }
+
+        let stats = io.stats();
+        assert!(stats.writes > 4);
+        assert!(stats.write_bytes >= meta.fat_size_sectors as u64 * 512 * meta.num_fats as u64);
+        assert_eq!(stats.flushes, 1);
+        assert_eq!(stats.reads, 0);
+    }
+
     #[test]
     fn test_format_writes_vbr_fsinfo_fat_and_root() {
         let meta = make_meta_32mb();
@@ -235,4 +269,83 @@ mod tests {
         assert_eq!(fsi.struct_signature, FAT_FSINFO_STRUCT_SIGNATURE);
         assert_eq!(fsi.trail_signature, FAT_FSINFO_TRAIL_SIGNATURE);
     }
+
+    #[test]
+    fn test_format_inside_subregion() {
+        use crate::core::checker::FsChecker;
+        use crate::fs::fat32::checker::Fat32Checker;
+
+        const GUARD: usize = 1024 * 1024;
+        let meta = make_meta_32mb();
+        let size = meta.volume_size_bytes;
+        let mut img = vec![0x5Au8; GUARD + size as usize + GUARD];
+        let mut disk = MemRimIO::new(&mut img);
+        {
+            let mut part = disk.subregion(GUARD as u64, size);
+            Fat32Formatter::new(&mut part, &meta).format(false).unwrap();
+            let rep = Fat32Checker::new(&mut part, &meta).check_all().unwrap();
+            assert!(!rep.has_error(), "{rep}");
+        }
+
+        assert!(img[..GUARD].iter().all(|&b| b == 0x5A));
+        assert!(img[GUARD + size as usize..].iter().all(|&b| b == 0x5A));
+        assert_eq!(&img[GUARD + 82..GUARD + 90], FAT_FS_TYPE);
+    }
+
+    #[test]
+    fn windows_profile_matches_cluster_table() {
+        use crate::core::{checker::FsChecker, profile::Profile};
+        use crate::fs::fat32::checker::Fat32Checker;
+
+        const MB: u64 = 1024 * 1024;
+        for (profile, size, cluster) in [
+            (Profile::Windows, 64 * MB, 512),
+            (Profile::Windows, 200 * MB, 2048),
+            (Profile::Windows, 1024 * MB, 4096),
+            (Profile::MkfsVfat, 200 * MB, 512),
+            (Profile::MkfsVfat, 12 * 1024 * MB, 8192),
+        ] {
+            let meta = Fat32Meta::with_profile(size, None, profile).unwrap();
+            assert_eq!(meta.bytes_per_cluster, cluster, "{profile:?} {size}");
+        }
+        assert!(Fat32Meta::with_profile(64 * MB, None, Profile::MkfsExfat).is_err());
+
+        let meta = Fat32Meta::with_profile(64 * MB, Some("PROFILE"), Profile::Windows).unwrap();
+        let mut img = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut img);
+        Fat32Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        let vbr: Fat32Vbr = io.read_struct(0).unwrap();
+        assert_eq!(&vbr.oem_name, FAT_OEM_NAME_WINDOWS);
+        assert_eq!({ vbr.reserved_sectors }, meta.reserved_sectors());
+        assert_eq!(vbr.sectors_per_cluster, 1);
+
+        let rep = Fat32Checker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!rep.has_error(), "{rep}");
+    }
+
+    #[test]
+    fn fresh_volume_reports_free_bytes() {
+        use crate::fs::fat32::filesystem::Fat32;
+
+        let meta = make_meta_32mb();
+        let mut img = vec![0u8; meta.volume_size_bytes as usize];
+        let mut io = MemRimIO::new(&mut img);
+        Fat32Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        // FSINFO starts out unknown: the FAT is scanned
+        let free_clusters = meta.cluster_count - meta.system_used_clusters();
+        let expected = free_clusters as u64 * meta.bytes_per_cluster as u64;
+        assert_eq!(Fat32::free_bytes(&mut io, &meta).unwrap(), expected);
+
+        // A known FSINFO count is trusted as is
+        let off = FAT_FSINFO_SECTOR * meta.bytes_per_sector as u64;
+        let mut fsi: Fat32FsInfo = io.read_struct(off).unwrap();
+        fsi.free_cluster_count = 10;
+        io.write_struct(off, &fsi).unwrap();
+        assert_eq!(
+            Fat32::free_bytes(&mut io, &meta).unwrap(),
+            10 * meta.bytes_per_cluster as u64
+        );
+    }
 }
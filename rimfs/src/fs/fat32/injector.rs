@@ -16,6 +16,8 @@ pub struct Fat32Injector<'a, IO: RimIO + ?Sized> {
     meta: &'a Fat32Meta,
     // Stack of open directory contexts (top = current dir)
     stack: Vec<FsContext<Fat32Handle>>,
+    // FAT updates held back until `flush` (None = write through)
+    fat: Option<fat::FatBatch>,
 }
 
 impl<'a, IO: RimIO + ?Sized> Fat32Injector<'a, IO> {
@@ -25,9 +27,25 @@ impl<'a, IO: RimIO + ?Sized> Fat32Injector<'a, IO> {
             allocator,
             meta,
             stack: vec![],
+            fat: Some(fat::FatBatch::new()),
         }
     }
 
+    /// Batch FAT updates until [`FsNodeInjector::flush`] (the default), or
+    /// write each chain to both FATs as soon as it is allocated.
+    pub fn with_fat_batching(mut self, enabled: bool) -> Self {
+        self.fat = enabled.then(fat::FatBatch::new);
+        self
+    }
+
+    fn write_chain(&mut self, chain: &[u32]) -> FsInjectorResult {
+        match &mut self.fat {
+            Some(batch) => batch.set_chain::<Fat32Meta>(chain),
+            None => fat::chain::write_chain::<IO, Fat32Meta>(self.io, self.meta, chain)?,
+        }
+        Ok(())
+    }
+
     fn ensure_chain_capacity(
         &mut self,
         handle: &mut Fat32Handle,
@@ -42,34 +60,65 @@ impl<'a, IO: RimIO + ?Sized> Fat32Injector<'a, IO> {
         Ok(())
     }
 
-    fn write_chain_buffer(&mut self, handle: &Fat32Handle, buf: &[u8]) -> FsInjectorResult {
+    /// Write every complete cluster sitting in `ctx.buf` to disk, growing the
+    /// directory chain as needed, so the buffer never holds more than one
+    /// cluster plus the entry set that overflowed it.
+    fn spill_full_units(&mut self, ctx: &mut FsContext<Fat32Handle>) -> FsInjectorResult {
         let cs = self.meta.unit_size();
+        while ctx.buf.len() >= cs {
+            self.ensure_chain_capacity(&mut ctx.handle, ctx.flushed + 1)?;
+            let c = ctx.handle.cluster_chain[ctx.flushed];
+            self.io.write_at(self.meta.unit_offset(c), &ctx.buf[..cs])?;
+            ctx.buf.drain(..cs);
+            ctx.flushed += 1;
+        }
+        Ok(())
+    }
 
-        if handle.cluster_chain.len() > 1 {
-            // Write full chain in one go
-            let offsets: Vec<u64> = handle
-                .cluster_chain
-                .iter()
-                .map(|&c| self.meta.unit_offset(c))
-                .collect();
-
-            let mut full = vec![0u8; handle.cluster_chain.len() * cs];
-            full[..buf.len()].copy_from_slice(buf);
-            self.io.write_multi_at(&offsets, cs, &full)?;
-        } else {
-            // Single-cluster directory/data
-            let c = handle.cluster_chain[0];
+    /// Spill the top-of-stack context if it has grown past a cluster.
+    fn spill_current(&mut self) -> FsInjectorResult {
+        if let Some(mut ctx) = self.stack.pop() {
+            let res = self.spill_full_units(&mut ctx);
+            self.stack.push(ctx);
+            res?;
+        }
+        Ok(())
+    }
+
+    /// Terminate the directory, write its last (partial) cluster and commit
+    /// the FAT chain for all of its clusters.
+    fn finish_dir(&mut self, mut ctx: FsContext<Fat32Handle>) -> FsInjectorResult {
+        let cs = self.meta.unit_size();
+        let total = ctx.flushed * cs + ctx.buf.len();
+        let ends_with_eod = ctx.buf.len() >= 32 && ctx.buf[ctx.buf.len() - 32] == FAT_EOD;
+        if total >= 32 && !ends_with_eod {
+            Fat32EodEntry::new().to_raw_buffer(&mut ctx.buf);
+        }
+        self.spill_full_units(&mut ctx)?;
+
+        let need_clusters = (ctx.flushed + ctx.buf.len().div_ceil(cs)).max(1);
+        self.ensure_chain_capacity(&mut ctx.handle, need_clusters)?;
+
+        if ctx.flushed < need_clusters {
+            let c = ctx.handle.cluster_chain[ctx.flushed];
             self.io
-                .write_block_best_effort(self.meta.unit_offset(c), buf, cs)?;
+                .write_block_best_effort(self.meta.unit_offset(c), &ctx.buf, cs)?;
         }
 
         // Update FAT for the full chain (safe even if already reserved as EOC).
-        fat::chain::write_chain::<IO, Fat32Meta>(self.io, self.meta, &handle.cluster_chain)?;
+        self.write_chain(&ctx.handle.cluster_chain)?;
         Ok(())
     }
 }
 
 impl<'a, IO: RimIO + ?Sized> FsNodeInjector<Fat32Handle> for Fat32Injector<'a, IO> {
+    fn space(&self) -> Option<FsSpace> {
+        Some(FsSpace::from_allocator::<Fat32Handle, _>(
+            &*self.allocator,
+            self.meta.unit_size(),
+        ))
+    }
+
     fn set_root_context(&mut self, _: &FsNode) -> FsInjectorResult {
         // Load root cluster’s existing entries, strip trailing EOD region
         let offset = self.meta.unit_offset(self.meta.root_unit());
@@ -94,7 +143,7 @@ impl<'a, IO: RimIO + ?Sized> FsNodeInjector<Fat32Handle> for Fat32Injector<'a, I
     fn write_dir(&mut self, name: &str, attr: &FileAttributes) -> FsInjectorResult {
         // Allocate and IMMEDIATELY reserve the child dir’s first cluster in FAT (EOC).
         let handle: Fat32Handle = self.allocator.allocate_unit()?;
-        fat::chain::write_chain::<IO, Fat32Meta>(self.io, self.meta, &handle.cluster_chain)?;
+        self.write_chain(&handle.cluster_chain)?;
 
         // Resolve parent cluster robustly.
         let parent_cluster = self
@@ -119,6 +168,7 @@ impl<'a, IO: RimIO + ?Sized> FsNodeInjector<Fat32Handle> for Fat32Injector<'a, I
         if let Some(parent) = self.stack.last_mut() {
             Fat32Entries::dir(name, handle.cluster_id, attr).to_raw_buffer(&mut parent.buf)
         }
+        self.spill_current()?;
 
         // Push child context (we will write it at flush_current/flush).
         self.stack.push(FsContext::new(handle, child_buf));
@@ -132,13 +182,19 @@ impl<'a, IO: RimIO + ?Sized> FsNodeInjector<Fat32Handle> for Fat32Injector<'a, I
         size: u64,
         attr: &FileAttributes,
     ) -> FsInjectorResult {
+        if attr.is_symlink() {
+            return Err(FsInjectorError::Invalid(
+                "Symlinks are not supported on FAT32",
+            ));
+        }
+
         // Allocate content chain and write file data first (best locality).
         let cs = self.meta.unit_size();
         let need = (size as usize).div_ceil(cs).max(1);
 
         let handle: Fat32Handle = self.allocator.allocate_chain(need)?;
 
-        fat::chain::write_chain::<IO, Fat32Meta>(self.io, self.meta, &handle.cluster_chain)?;
+        self.write_chain(&handle.cluster_chain)?;
 
         use crate::core::utils::stream_copy::write_stream_to_units;
 
@@ -153,38 +209,24 @@ impl<'a, IO: RimIO + ?Sized> FsNodeInjector<Fat32Handle> for Fat32Injector<'a, I
             Fat32Entries::file(name, handle.cluster_id, size as u32, attr)
                 .to_raw_buffer(&mut ctx.buf)
         }
-        Ok(())
+        self.spill_current()
     }
 
     fn flush_current(&mut self) -> FsInjectorResult {
-        // Write ONLY the current directory buffer; no parent linking here.
-        if let Some(mut ctx) = self.stack.pop() {
-            if ctx.buf.len() >= 32 && ctx.buf[ctx.buf.len() - 32] != FAT_EOD {
-                Fat32EodEntry::new().to_raw_buffer(&mut ctx.buf);
-            }
-
-            let cs = self.meta.unit_size();
-            let used = ctx.buf.len();
-            let need_clusters = used.div_ceil(cs).max(1);
-
-            self.ensure_chain_capacity(&mut ctx.handle, need_clusters)?;
-            self.write_chain_buffer(&ctx.handle, &ctx.buf)?;
+        // Write ONLY the current directory; no parent linking here.
+        if let Some(ctx) = self.stack.pop() {
+            self.finish_dir(ctx)?;
         }
         Ok(())
     }
 
     fn flush(&mut self) -> FsInjectorResult {
         // Drain remaining directory contexts; again, only data writes here.
-        while let Some(mut ctx) = self.stack.pop() {
-            if ctx.buf.len() >= 32 && ctx.buf[ctx.buf.len() - 32] != FAT_EOD {
-                Fat32EodEntry::new().to_raw_buffer(&mut ctx.buf);
-            }
-            let cs = self.meta.unit_size();
-            let used = ctx.buf.len();
-            let need_clusters = used.div_ceil(cs).max(1);
-
-            self.ensure_chain_capacity(&mut ctx.handle, need_clusters)?;
-            self.write_chain_buffer(&ctx.handle, &ctx.buf)?;
+        while let Some(ctx) = self.stack.pop() {
+            self.finish_dir(ctx)?;
+        }
+        if let Some(batch) = &mut self.fat {
+            batch.flush(self.io, self.meta, self.meta.bytes_per_sector as usize)?;
         }
         self.io.flush()?;
         Ok(())
@@ -241,4 +283,259 @@ mod tests {
         println!("{parsed_tree}");
         assert!(tree.structural_eq(&parsed_tree), "Tree structure mismatch");
     }
+
+    #[test]
+    fn test_fat32_system_volume_info_stub() {
+        const SIZE_BYTES: u64 = 32 * 1024 * 1024;
+        let meta = Fat32Meta::new(SIZE_BYTES, Some("TESTFS")).unwrap();
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Fat32Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![FsNode::File {
+                name: "readme.md".to_string(),
+                content: b"Test Readme".to_vec(),
+                attr: FileAttributes::new_file(),
+            }],
+        };
+        let opts = FsInjectOptions {
+            system_volume_info: true,
+            ..Default::default()
+        };
+
+        let mut allocator = Fat32Allocator::new(&meta);
+        let mut injector = Fat32Injector::new(&mut io, &mut allocator, &meta);
+        injector.inject_tree_with(&tree, &opts).unwrap();
+
+        let root = Fat32Resolver::new(&mut io, &meta).parse_tree("/*").unwrap();
+        let FsNode::Container { children, .. } = &root else {
+            panic!("root should be a container");
+        };
+        let svi = children
+            .iter()
+            .find(|c| c.name() == SYSTEM_VOLUME_INFORMATION)
+            .expect("System Volume Information missing");
+        assert!(svi.is_dir());
+        assert!(svi.attr().hidden && svi.attr().system);
+        assert!(children.iter().any(|c| c.name() == "readme.md"));
+
+        let report = Fat32Checker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!report.has_error(), "{report}");
+    }
+
+    #[test]
+    fn test_fat32_rejects_symlink() {
+        const SIZE_BYTES: u64 = 32 * 1024 * 1024;
+        let meta = Fat32Meta::new(SIZE_BYTES, Some("TESTFS")).unwrap();
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Fat32Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![FsNode::File {
+                name: "link".to_string(),
+                content: vec![],
+                attr: FileAttributes::new_symlink("target"),
+            }],
+        };
+        let mut allocator = Fat32Allocator::new(&meta);
+        let mut injector = Fat32Injector::new(&mut io, &mut allocator, &meta);
+        assert!(matches!(
+            injector.inject_tree(&tree),
+            Err(FsInjectorError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn large_dir_is_streamed_cluster_by_cluster() {
+        use crate::core::traits::FsNodeInjector;
+
+        const SIZE_BYTES: u64 = 64 * 1024 * 1024;
+        const FILES: usize = 1500;
+        let meta = Fat32Meta::new(SIZE_BYTES, Some("BIGDIR")).unwrap();
+        let cs = meta.unit_size();
+
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let mut allocator = Fat32Allocator::new(&meta);
+        let mut injector = Fat32Injector::new(&mut io, &mut allocator, &meta);
+
+        injector
+            .set_root_context(&FsNode::Container {
+                attr: FileAttributes::new_dir(),
+                children: vec![],
+            })
+            .unwrap();
+        injector
+            .write_dir("big", &FileAttributes::new_dir())
+            .unwrap();
+
+        for i in 0..FILES {
+            let mut content = [0x5Au8; 1];
+            let mut src = MemRimIO::new(&mut content);
+            injector
+                .write_file(
+                    &format!("a_rather_long_file_name_{i:05}.bin"),
+                    &mut src,
+                    1,
+                    &FileAttributes::new_file(),
+                )
+                .unwrap();
+            let pending = injector.stack.last().unwrap().buf.len();
+            assert!(pending < cs, "dir buffer grew to {pending} bytes");
+        }
+        assert!(injector.stack.last().unwrap().flushed > 1);
+
+        injector.flush_current().unwrap();
+        injector.flush().unwrap();
+
+        let mut resolver = Fat32Resolver::new(&mut io, &meta);
+        let names = resolver.read_dir("/big").unwrap();
+        assert_eq!(
+            names
+                .iter()
+                .filter(|n| n.starts_with("a_rather_long_file_name_"))
+                .count(),
+            FILES
+        );
+        assert!(resolver.read_dir("/").unwrap().iter().any(|n| n == "big"));
+    }
+
+    #[test]
+    fn batched_fat_writes_match_per_entry_writes() {
+        const SIZE_BYTES: u64 = 32 * 1024 * 1024;
+        let meta = Fat32Meta::new(SIZE_BYTES, Some("BATCH")).unwrap();
+        let fat_region = meta.fat_offset_bytes..meta.cluster_heap_offset_bytes;
+
+        struct FatWrites(core::ops::Range<u64>, usize);
+        impl IOTracer for FatWrites {
+            fn on_write(&mut self, off: u64, _len: usize) {
+                if self.0.contains(&off) {
+                    self.1 += 1;
+                }
+            }
+        }
+
+        // Fixed timestamps so both images can be compared byte for byte.
+        let t = time::OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let attr = FileAttributes {
+            created: Some(t),
+            modified: Some(t),
+            accessed: Some(t),
+            ..FileAttributes::new_file()
+        };
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: (0..200)
+                .map(|i| FsNode::File {
+                    name: format!("f{i}.bin"),
+                    content: vec![i as u8; 5000],
+                    attr: attr.clone(),
+                })
+                .collect(),
+        };
+
+        // Format once: the label entry carries a timestamp too.
+        let mut formatted = vec![0u8; SIZE_BYTES as usize];
+        Fat32Formatter::new(&mut MemRimIO::new(&mut formatted), &meta)
+            .format(false)
+            .unwrap();
+
+        let build = |batched: bool| {
+            let mut buf = formatted.clone();
+            let mut mem = MemRimIO::new(&mut buf);
+            let mut io = TracingIO::new(&mut mem, FatWrites(fat_region.clone(), 0));
+            let mut allocator = Fat32Allocator::new(&meta);
+            Fat32Injector::new(&mut io, &mut allocator, &meta)
+                .with_fat_batching(batched)
+                .inject_tree(&tree)
+                .unwrap();
+            let writes = io.into_parts().1.1;
+            (buf, writes)
+        };
+
+        let (direct, direct_writes) = build(false);
+        let (batched, batched_writes) = build(true);
+        assert!(direct == batched, "on-disk images differ");
+        assert!(
+            batched_writes * 50 < direct_writes,
+            "{batched_writes} vs {direct_writes}"
+        );
+
+        let mut buf = batched;
+        let mut io = MemRimIO::new(&mut buf);
+        let rep = Fat32Checker::new(&mut io, &meta).check_all().unwrap();
+        assert!(!rep.has_error(), "{rep}");
+    }
+
+    #[test]
+    fn read_file_returns_injected_bytes() {
+        const SIZE_BYTES: u64 = 32 * 1024 * 1024;
+        let meta = Fat32Meta::new(SIZE_BYTES, Some("READ")).unwrap();
+
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Fat32Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        // Spans several clusters and ends mid-cluster
+        let content: Vec<u8> = (0..meta.unit_size() * 3 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let tree = FsNode::Container {
+            attr: FileAttributes::new_dir(),
+            children: vec![FsNode::Dir {
+                name: "EFI".to_string(),
+                attr: FileAttributes::new_dir(),
+                children: vec![
+                    FsNode::File {
+                        name: "BOOTX64.EFI".to_string(),
+                        content: content.clone(),
+                        attr: FileAttributes::new_file(),
+                    },
+                    FsNode::File {
+                        name: "empty".to_string(),
+                        content: Vec::new(),
+                        attr: FileAttributes::new_file(),
+                    },
+                ],
+            }],
+        };
+        let mut allocator = Fat32Allocator::new(&meta);
+        let mut injector = Fat32Injector::new(&mut io, &mut allocator, &meta);
+        injector.inject_tree(&tree).unwrap();
+
+        let read = Fat32::read_file(&mut io, &meta, "/EFI/BOOTX64.EFI").unwrap();
+        assert_eq!(read, content);
+        assert!(
+            Fat32::read_file(&mut io, &meta, "/EFI/empty")
+                .unwrap()
+                .is_empty()
+        );
+
+        assert!(matches!(
+            Fat32::read_file(&mut io, &meta, "/EFI/missing"),
+            Err(FsError::Resolver(FsResolverError::NotFound))
+        ));
+        assert!(matches!(
+            Fat32::read_file(&mut io, &meta, "/EFI"),
+            Err(FsError::Resolver(FsResolverError::Invalid(_)))
+        ));
+    }
+
+    #[test]
+    fn test_fat32_max_fill_rejects_before_writing() {
+        const SIZE_BYTES: u64 = 32 * 1024 * 1024;
+        let meta = Fat32Meta::new(SIZE_BYTES, Some("TESTFS")).unwrap();
+        let mut buf = vec![0u8; SIZE_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Fat32Formatter::new(&mut io, &meta).format(false).unwrap();
+
+        let mut allocator = Fat32Allocator::new(&meta);
+        let mut injector = Fat32Injector::new(&mut io, &mut allocator, &meta);
+        crate::core::test_support::check_max_fill(&mut injector);
+    }
 }
@@ -5,6 +5,7 @@ use alloc::string::{String, ToString};
 
 use crate::core::errors::{FsError, FsResult};
 pub use crate::core::meta::*;
+use crate::core::profile::Profile;
 
 use crate::{core::cursor::ClusterMeta, fs::fat32::constant::*};
 
@@ -12,6 +13,7 @@ use crate::{core::cursor::ClusterMeta, fs::fat32::constant::*};
 pub struct Fat32Meta {
     pub volume_id: u32,
     pub volume_label: [u8; 11],
+    pub oem_name: [u8; 8],
 
     pub(crate) bytes_per_sector: u16,
     pub(crate) sectors_per_cluster: u8,
@@ -43,6 +45,32 @@ impl Fat32Meta {
         )
     }
 
+    /// Builds the metadata with the cluster size, reserved sectors and OEM
+    /// name `profile` would pick for a volume of `size_bytes`.
+    pub fn with_profile(
+        size_bytes: u64,
+        volume_label: Option<&str>,
+        profile: Profile,
+    ) -> FsResult<Self> {
+        let oem = match profile {
+            Profile::Rim => oem_name(),
+            Profile::MkfsVfat => *FAT_OEM_NAME_MKFS,
+            Profile::Windows => *FAT_OEM_NAME_WINDOWS,
+            Profile::MkfsExfat => return Err(FsError::Invalid("profile does not apply to FAT32")),
+        };
+        let mut meta = Self::new_custom(
+            size_bytes,
+            volume_label,
+            generate_volume_id_32(),
+            FAT_NUM_FATS,
+            FAT_SECTOR_SIZE,
+            profile_cluster_size(profile, size_bytes),
+            DEFAULT_FAT_RESERVED_SECTORS as u32,
+        )?;
+        meta.oem_name = oem;
+        Ok(meta)
+    }
+
     pub fn new_custom(
         volume_size_bytes: u64,
         volume_label: Option<&str>,
@@ -84,6 +112,7 @@ impl Fat32Meta {
         Ok(Self {
             volume_id,
             volume_label: volume_label_safe,
+            oem_name: oem_name(),
             bytes_per_sector,
             sectors_per_cluster,
             bytes_per_cluster,
@@ -98,6 +127,12 @@ impl Fat32Meta {
         })
     }
 
+    /// Sectors before the first FAT (BPB_RsvdSecCnt).
+    #[inline]
+    pub fn reserved_sectors(&self) -> u16 {
+        (self.fat_offset_bytes / self.bytes_per_sector as u64) as u16
+    }
+
     #[inline]
     pub fn root_clusters(&self) -> u32 {
         1
@@ -171,6 +206,33 @@ impl ClusterMeta for Fat32Meta {
     }
 }
 
+/// Cluster size `profile` picks for a volume of `size_bytes`.
+///
+/// dosfstools and Windows both follow the table from the FAT specification,
+/// Windows just steps through 1K and 2K clusters on small volumes.
+fn profile_cluster_size(profile: Profile, size_bytes: u64) -> u32 {
+    const MB: u64 = 1024 * 1024;
+    const GB: u64 = 1024 * MB;
+
+    let small = match profile {
+        Profile::MkfsVfat if size_bytes <= 260 * MB => 512,
+        Profile::MkfsVfat => 4 * 1024,
+        Profile::Windows => match size_bytes {
+            n if n <= 64 * MB => 512,
+            n if n <= 128 * MB => 1024,
+            n if n <= 256 * MB => 2048,
+            _ => 4 * 1024,
+        },
+        Profile::Rim | Profile::MkfsExfat => return FAT_CLUSTER_SIZE,
+    };
+    match size_bytes {
+        n if n <= 8 * GB => small,
+        n if n <= 16 * GB => 8 * 1024,
+        n if n <= 32 * GB => 16 * 1024,
+        _ => 32 * 1024,
+    }
+}
+
 /// Computes the FAT size and cluster count for a given FAT configuration.
 ///
 /// This function performs convergence to determine the optimal FAT size (`fat_size`)
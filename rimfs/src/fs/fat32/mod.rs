@@ -2,6 +2,7 @@ pub mod allocator;
 pub mod attr;
 pub mod checker;
 pub mod constant;
+pub mod esp;
 pub mod filesystem;
 pub mod formatter;
 pub mod injector;
@@ -21,11 +22,15 @@ pub mod traits {
 }
 
 pub mod prelude {
+    #[cfg(feature = "std")]
+    pub use super::esp::populate_esp;
+    pub use super::esp::{efi_boot_file_name, populate_esp_bytes};
     pub use super::filesystem::Fat32;
     pub use super::traits::*;
     #[cfg(feature = "std")]
     pub use crate::core::StdResolver;
     pub use crate::core::errors::*;
+    pub use crate::core::profile::Profile;
     pub use crate::core::traits::*;
     pub use rimio::prelude::*;
 }
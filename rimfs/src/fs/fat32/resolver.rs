@@ -44,31 +44,7 @@ impl<'a, IO: RimIO + ?Sized> FsResolver for Fat32Resolver<'a, IO> {
         if is_dir {
             return Err(FsResolverError::Invalid("Not a file"));
         }
-        if size == 0 {
-            return Ok(Vec::new());
-        }
-
-        let cs = self.meta.unit_size();
-        let mut out = vec![0u8; size];
-        let mut written = 0usize;
-
-        let mut cur = ClusterCursor::new_safe(self.meta, first_cluster);
-        cur.for_each_run(self.io, |io, start, len| {
-            if written >= out.len() {
-                return Ok(());
-            }
-            let off = self.meta.unit_offset(start);
-            let bytes = (len as usize) * cs;
-            let to_copy = core::cmp::min(bytes, out.len() - written);
-            io.read_at(off, &mut out[written..written + to_copy])?;
-            written += to_copy;
-            Ok(())
-        })?;
-
-        if written < out.len() {
-            return Err("short_stream_read".into());
-        }
-        Ok(out)
+        read_chain(self.io, self.meta, first_cluster, size)
     }
 
     fn read_attributes(&mut self, path: &str) -> FsResolverResult<FileAttributes> {
@@ -119,9 +95,81 @@ impl<'a, IO: RimIO + ?Sized> FsResolver for Fat32Resolver<'a, IO> {
         }
         Err(FsResolverError::Invalid("Invalid path"))
     }
+
+    fn block_map(&mut self, path: &str) -> FsResolverResult<Vec<(u64, u64)>> {
+        let (is_dir, first_cluster, size) = self.resolve_path(path)?;
+        crate::ensure!(!is_dir, FsResolverError::Invalid("Not a file"));
+        if size == 0 || first_cluster == 0 {
+            return Ok(Vec::new());
+        }
+        chain_runs(self.io, self.meta, first_cluster, size)
+    }
 }
 
-fn read_dir_entries<IO: RimIO + ?Sized>(
+/// Reads the first `size` bytes of the cluster chain at `first_cluster`.
+pub(crate) fn read_chain<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    meta: &Fat32Meta,
+    first_cluster: u32,
+    size: usize,
+) -> FsResolverResult<Vec<u8>> {
+    if size == 0 {
+        return Ok(Vec::new());
+    }
+
+    let cs = meta.unit_size();
+    let mut out = vec![0u8; size];
+    let mut written = 0usize;
+
+    let mut cur = ClusterCursor::new_safe(meta, first_cluster);
+    cur.for_each_run(io, |io, start, len| {
+        if written >= out.len() {
+            return Ok(());
+        }
+        let off = meta.unit_offset(start);
+        let bytes = (len as usize) * cs;
+        let to_copy = core::cmp::min(bytes, out.len() - written);
+        io.read_at(off, &mut out[written..written + to_copy])?;
+        written += to_copy;
+        Ok(())
+    })?;
+
+    if written < out.len() {
+        return Err("short_stream_read".into());
+    }
+    Ok(out)
+}
+
+/// Collects the runs of the cluster chain at `first_cluster`, limited to the
+/// clusters needed to hold `size` bytes.
+fn chain_runs<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    meta: &Fat32Meta,
+    first_cluster: u32,
+    size: usize,
+) -> FsResolverResult<Vec<(u64, u64)>> {
+    let cs = meta.unit_size() as u64;
+    let mut remaining = (size as u64).div_ceil(cs);
+    let mut runs = Vec::new();
+
+    let mut cur = ClusterCursor::new_safe(meta, first_cluster);
+    cur.for_each_run(io, |_, start, len| {
+        if remaining == 0 {
+            return Ok(());
+        }
+        let take = core::cmp::min(len as u64, remaining);
+        runs.push((meta.unit_offset(start), take * cs));
+        remaining -= take;
+        Ok(())
+    })?;
+
+    if remaining > 0 {
+        return Err("short_chain".into());
+    }
+    Ok(runs)
+}
+
+pub(crate) fn read_dir_entries<IO: RimIO + ?Sized>(
     io: &mut IO,
     meta: &Fat32Meta,
     start_cluster: u32,
@@ -196,6 +244,17 @@ pub fn find_in_dir<IO: RimIO + ?Sized>(
     dir_cluster: u32,
     target: &str,
 ) -> FsResolverResult<Option<Fat32Entries>> {
+    Ok(locate_in_dir(io, meta, dir_cluster, target)?.map(|(entry, _)| entry))
+}
+
+/// Same as [`find_in_dir`], also returning the byte offsets of every slot
+/// the entry uses on disk (its LFN set first, then the short entry).
+pub(crate) fn locate_in_dir<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    meta: &Fat32Meta,
+    dir_cluster: u32,
+    target: &str,
+) -> FsResolverResult<Option<(Fat32Entries, Vec<u64>)>> {
     let cs = meta.unit_size();
 
     // Directories -> allow system clusters (root=2)
@@ -203,7 +262,8 @@ pub fn find_in_dir<IO: RimIO + ?Sized>(
 
     // LFNs persistent across clusters AND across runs
     let mut lfn_stack: Vec<[u8; 32]> = Vec::new();
-    let mut found: Option<Fat32Entries> = None;
+    let mut slots: Vec<u64> = Vec::new();
+    let mut found: Option<(Fat32Entries, Vec<u64>)> = None;
 
     // We read a full run in a single operation, then iterate by 32-byte chunks
     let res = cur.for_each_run(io, |io, run_start, run_len| {
@@ -212,13 +272,15 @@ pub fn find_in_dir<IO: RimIO + ?Sized>(
         let off0 = meta.unit_offset(run_start);
         io.read_block_best_effort(off0, &mut data, total)?;
 
-        for chunk in data.chunks_exact(32) {
+        for (i, chunk) in data.chunks_exact(32).enumerate() {
+            let off = off0 + (i * 32) as u64;
             let first = chunk[0];
             if first == FAT_EOD {
                 return Ok(());
             }
             if first == FAT_ENTRY_DELETED {
                 lfn_stack.clear();
+                slots.clear();
                 continue;
             }
 
@@ -228,12 +290,14 @@ pub fn find_in_dir<IO: RimIO + ?Sized>(
                 // Safe: chunks_exact(32) guarantees length 32
                 if let Ok(arr) = chunk.try_into() {
                     lfn_stack.push(arr);
+                    slots.push(off);
                 }
                 continue;
             }
 
             if attr & Fat32Attributes::VOLUME_ID.bits() != 0 {
                 lfn_stack.clear();
+                slots.clear();
                 continue;
             }
 
@@ -243,18 +307,21 @@ pub fn find_in_dir<IO: RimIO + ?Sized>(
                 && (name11 == FAT_DOT_NAME || name11 == FAT_DOTDOT_NAME)
             {
                 lfn_stack.clear();
+                slots.clear();
                 continue;
             }
 
             // SFN entry
             let e = Fat32Entries::from_raw(&lfn_stack, chunk)?;
             lfn_stack.clear();
+            slots.push(off);
 
             if e.name_bytes_eq(target) {
-                found = Some(e);
+                found = Some((e, core::mem::take(&mut slots)));
                 // Early-exit from the run (and thus from for_each_run) via a "sentinel" error
                 return Err(FsCursorError::Other("found"));
             }
+            slots.clear();
         }
         Ok(())
     });
@@ -265,3 +332,40 @@ pub fn find_in_dir<IO: RimIO + ?Sized>(
         Err(e) => Err(FsResolverError::Cursor(e)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::core::test_support::*;
+    use crate::fs::fat32::prelude::*;
+
+    /// A fresh volume holding [`sample_tree`].
+    fn sample_volume() -> (Vec<u8>, Fat32Meta) {
+        let meta = Fat32Meta::new(SAMPLE_VOLUME_BYTES, Some("MAP")).unwrap();
+        let unit = meta.unit_size();
+
+        let mut buf = vec![0u8; SAMPLE_VOLUME_BYTES as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        Fat32Formatter::new(&mut io, &meta).format(false).unwrap();
+        let mut allocator = Fat32Allocator::new(&meta);
+        let mut injector = Fat32Injector::new(&mut io, &mut allocator, &meta);
+        injector.inject_tree(&sample_tree(unit)).unwrap();
+        injector.flush().unwrap();
+        (buf, meta)
+    }
+
+    #[test]
+    fn exists_checks_paths() {
+        let (mut buf, meta) = sample_volume();
+        let mut io = MemRimIO::new(&mut buf);
+        check_exists(&mut Fat32Resolver::new(&mut io, &meta));
+    }
+
+    #[test]
+    fn block_map_covers_file_data() {
+        let (mut buf, meta) = sample_volume();
+        let unit = meta.unit_size();
+        let mut io = MemRimIO::new(&mut buf);
+        let runs = check_block_map(&mut Fat32Resolver::new(&mut io, &meta), unit);
+        check_runs_hold_data(&buf, &runs, unit);
+    }
+}
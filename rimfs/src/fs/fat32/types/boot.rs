@@ -50,10 +50,10 @@ impl Fat32Vbr {
     pub fn from_meta(meta: &Fat32Meta) -> Self {
         Self {
             jump_boot: FAT_JUMP_BOOT,
-            oem_name: oem_name(),
+            oem_name: meta.oem_name,
             bytes_per_sector: meta.bytes_per_sector,
             sectors_per_cluster: meta.sectors_per_cluster,
-            reserved_sectors: DEFAULT_FAT_RESERVED_SECTORS,
+            reserved_sectors: meta.reserved_sectors(),
             num_fats: meta.num_fats,
             root_entry_count: FAT_ROOT_ENTRY_COUNT,
             total_sectors_16: FAT_TOTAL_SECTORS_16,
@@ -64,7 +64,7 @@ impl Fat32Entries {
         };
         let entry = Fat32Entry::new(
             short_name,
-            Fat32Attributes::DIRECTORY.bits(),
+            attr.as_fat_attr() | Fat32Attributes::DIRECTORY.bits(),
             cluster,
             0,
             date,
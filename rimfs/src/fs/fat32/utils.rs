@@ -6,11 +6,19 @@ use alloc::{format, string::String, vec::Vec};
 use crate::{
     core::{
         errors::*,
+        fat,
+        meta::FsMeta,
         resolver::*,
-        utils::{checksum_utils::checksum, time_utils},
+        utils::{checksum_utils::checksum, path_utils::split_path, time_utils},
+    },
+    fs::fat32::{
+        constant::*,
+        meta::Fat32Meta,
+        resolver::{find_in_dir, locate_in_dir, read_dir_entries},
+        types::{Fat32FsInfo, Fat32LFNEntry},
     },
-    fs::fat32::types::Fat32LFNEntry,
 };
+use rimio::{RimIO, RimIOStructExt};
 use time::OffsetDateTime;
 
 pub const MAX_LFN_CHARS: usize = 255;
@@ -198,3 +206,85 @@ pub fn lfn_entries(name: &str, short: &[u8; 11]) -> Vec<Fat32LFNEntry> {
     out.reverse();
     out
 }
+
+/// Removes the file or empty directory at `path`.
+///
+/// The short entry and its LFN set are marked deleted, the cluster chain is
+/// freed in every FAT copy and FSINFO's free count (when known) is raised
+/// accordingly.
+pub fn delete_file<IO: RimIO + ?Sized>(io: &mut IO, meta: &Fat32Meta, path: &str) -> FsResult {
+    let components = split_path(path);
+    let Some((name, parents)) = components.split_last() else {
+        return Err(FsError::Invalid("Cannot delete the root directory"));
+    };
+
+    let mut dir = meta.root_unit();
+    for comp in parents {
+        let entry = find_in_dir(io, meta, dir, comp)?.ok_or(FsResolverError::NotFound)?;
+        crate::ensure!(
+            entry.is_dir(),
+            FsResolverError::Invalid("Expected a directory")
+        );
+        dir = entry.first_cluster();
+    }
+
+    let (entry, slots) = locate_in_dir(io, meta, dir, name)?.ok_or(FsResolverError::NotFound)?;
+    let first = entry.first_cluster();
+    if entry.is_dir() {
+        crate::ensure!(
+            read_dir_entries(io, meta, first)?.is_empty(),
+            FsError::Invalid("Directory is not empty")
+        );
+    }
+
+    let chain = if first >= FAT_FIRST_CLUSTER {
+        fat::chain::read_chain(io, meta, first)?
+    } else {
+        Vec::new()
+    };
+    crate::ensure!(
+        chain
+            .iter()
+            .all(|&c| (FAT_FIRST_CLUSTER..=meta.last_data_unit()).contains(&c)),
+        FsError::Invalid("Cluster chain out of range")
+    );
+
+    for off in slots {
+        io.write_at(off, &[FAT_ENTRY_DELETED])?;
+    }
+    fat::chain::free_chain::<IO, Fat32Meta>(io, meta, &chain)?;
+
+    let bps = meta.bytes_per_sector as u64;
+    for sector in [FAT_FSINFO_SECTOR, FAT_FSINFO_BACKUP_SECTOR] {
+        let mut fsi: Fat32FsInfo = io.read_struct(sector * bps)?;
+        if fsi.free_cluster_count != FAT_FSINFO_UNKNOWN {
+            fsi.free_cluster_count = fsi.free_cluster_count.saturating_add(chain.len() as u32);
+            io.write_struct(sector * bps, &fsi)?;
+        }
+    }
+
+    io.flush()?;
+    Ok(())
+}
+
+/// Free space on the volume, in bytes.
+///
+/// Trusts FSINFO's free count when it is set and plausible, otherwise counts
+/// the free entries of the first FAT.
+pub fn free_bytes<IO: RimIO + ?Sized>(io: &mut IO, meta: &Fat32Meta) -> FsResult<u64> {
+    let fsi: Fat32FsInfo = io.read_struct(FAT_FSINFO_SECTOR * meta.bytes_per_sector as u64)?;
+    let advertised = fsi.free_cluster_count;
+
+    let free = if advertised != FAT_FSINFO_UNKNOWN && advertised <= meta.cluster_count {
+        advertised
+    } else {
+        let mut free = 0u32;
+        fat::chain::for_each_entry(io, meta, 0, |c, e| {
+            if c >= FAT_FIRST_CLUSTER && e == 0 {
+                free += 1;
+            }
+        })?;
+        free
+    };
+    Ok(free as u64 * meta.unit_size() as u64)
+}
@@ -11,11 +11,17 @@ pub mod fs;
 // Reusable types and traits
 pub use core::traits::*;
 
+// Unified error type for APIs spanning several components
+pub use core::errors::{FsError, FsResult};
+
 // Utilities
 #[cfg(feature = "std")]
 pub use core::StdResolver;
 pub use core::utils::{path_utils::*, volume::*};
 
+#[cfg(all(feature = "fat32", feature = "std"))]
+pub use fs::fat32::esp::populate_esp;
+
 // Filesystem APIs
 #[cfg(feature = "fat32")]
 /// FAT32 filesystem implementation.
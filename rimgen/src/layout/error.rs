@@ -8,6 +8,17 @@ pub enum LayoutError {
     SizeTooLarge(&'static str, u64),
     SizeTooSmall(&'static str, u64),
     InvalidConfig(&'static str),
+    ZeroSize(String),
+    NotMountable(String),
+    FsKindMismatch {
+        name: String,
+        fs: String,
+        kind: String,
+    },
+    ImageTooLarge {
+        size_mb: u64,
+        max_mb: u64,
+    },
 }
 
 impl fmt::Display for LayoutError {
@@ -20,6 +31,27 @@ impl fmt::Display for LayoutError {
                 write!(f, "{fs} needs at least {size} MiB")
             }
             LayoutError::InvalidConfig(msg) => write!(f, "Invalid config: {msg}"),
+            LayoutError::ZeroSize(name) => {
+                write!(f, "Partition '{name}' has a zero size")
+            }
+            LayoutError::NotMountable(name) => {
+                write!(
+                    f,
+                    "Partition '{name}' has no filesystem to inject files into"
+                )
+            }
+            LayoutError::FsKindMismatch { name, fs, kind } => {
+                write!(f, "Partition '{name}' of type '{kind}' cannot hold fs={fs}")
+            }
+            LayoutError::ImageTooLarge { size_mb, max_mb } => {
+                write!(
+                    f,
+                    "Image would be {size_mb} MiB, above the {max_mb} MiB limit \
+                     (raise it with --max-size or disk.max_size)"
+                )
+            }
         }
     }
 }
+
+impl std::error::Error for LayoutError {}
@@ -1,14 +1,16 @@
 // SPDX-License-Identifier: MIT
 
+use anyhow::Context;
 use serde::Deserialize;
-use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::{layout::partition::*, layout::size::*};
 
 pub const DEFAULT_AUTO_SIZE_MB: u64 = 64;
+/// Largest image built unless `disk.max_size` / `--max-size` says otherwise.
+pub const DEFAULT_MAX_IMAGE_SIZE_MB: u64 = 64 * 1024;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct Layout {
     #[serde(skip)]
     pub base_dir: PathBuf,
@@ -16,21 +18,79 @@ pub struct Layout {
     pub disk: Option<DiskConfig>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
 pub struct DiskConfig {
     pub alignment: Option<String>,
     pub guid: Option<uuid::Uuid>,
+    /// Unallocated space inserted between consecutive partitions
+    #[serde(default)]
+    pub gap: Option<Size>,
+    /// Pad each partition so its end also falls on an alignment boundary
+    #[serde(default)]
+    pub align_end: bool,
+    /// Refuse to build an image larger than this (default 64G)
+    #[serde(default)]
+    pub max_size: Option<Size>,
 }
 
 impl Layout {
-    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
-        let content = fs::read_to_string(path)?;
-        let mut layout: Layout = toml::from_str(&content)?;
-        layout.base_dir = path
-            .parent()
-            .unwrap_or_else(|| Path::new("."))
-            .canonicalize()?
-            .to_path_buf();
+    /// Loads a layout file, parsed as JSON for `.json` and TOML otherwise.
+    ///
+    /// `include` directives are merged and `${VAR}` references expanded
+    /// first, see [`source`](crate::layout::source).
+    ///
+    /// Unknown keys (e.g. a misspelled `lable`) are rejected, or only
+    /// reported as warnings when `allow_unknown` is set.
+    pub fn from_file(path: &Path, allow_unknown: bool) -> anyhow::Result<Self> {
+        let doc = crate::layout::source::load(path)?;
+        let origin = format!("layout '{}'", path.display());
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        Self::from_document(doc, &origin, base_dir, allow_unknown, || {
+            crate::layout::source::spanned_error::<Layout>(path)
+        })
+    }
+
+    /// Parses a TOML layout like [`Self::from_file`] would, with includes and
+    /// partition paths relative to `base_dir`.
+    #[allow(dead_code)]
+    pub fn from_toml_str(s: &str, base_dir: &Path, allow_unknown: bool) -> anyhow::Result<Self> {
+        let doc = crate::layout::source::load_str(s, false, base_dir)?;
+        Self::from_document(doc, "layout", base_dir, allow_unknown, || {
+            crate::layout::source::spanned_error_str::<Layout>(s)
+        })
+    }
+
+    /// JSON counterpart of [`Self::from_toml_str`].
+    #[allow(dead_code)]
+    pub fn from_json_str(s: &str, base_dir: &Path, allow_unknown: bool) -> anyhow::Result<Self> {
+        let doc = crate::layout::source::load_str(s, true, base_dir)?;
+        Self::from_document(doc, "layout", base_dir, allow_unknown, || None)
+    }
+
+    /// Deserializes a loaded document, checking for unknown keys. `origin`
+    /// names it in errors, and `spanned` may supply a better error (with line
+    /// and column) when deserializing fails.
+    fn from_document(
+        doc: serde_json::Value,
+        origin: &str,
+        base_dir: &Path,
+        allow_unknown: bool,
+        spanned: impl FnOnce() -> Option<anyhow::Error>,
+    ) -> anyhow::Result<Self> {
+        let mut unknown = Vec::new();
+        let mut layout: Layout =
+            serde_ignored::deserialize(doc, |key| unknown.push(key.to_string()))
+                .map_err(|e| spanned().unwrap_or_else(|| e.into()))
+                .with_context(|| format!("Invalid {origin}"))?;
+        if let Some(first) = unknown.first() {
+            if !allow_unknown {
+                anyhow::bail!("Unknown key '{first}' in {origin} (use --allow-unknown to ignore)");
+            }
+            for key in &unknown {
+                crate::log_normal!("Warning: ignoring unknown layout key '{key}'");
+            }
+        }
+        layout.base_dir = base_dir.canonicalize()?;
         layout.resolve_partition()?;
         layout.assign_guids();
         Ok(layout)
@@ -62,7 +122,50 @@ impl Layout {
         }
     }
 
+    /// Gap (in sectors) to leave after partition `index`, before the next one.
+    ///
+    /// The partition's own `gap` wins over `disk.gap`; the last partition has none.
+    pub fn gap_after_sectors(&self, index: usize) -> u64 {
+        if index + 1 >= self.partitions.len() {
+            return 0;
+        }
+        let gap = self.partitions[index]
+            .gap
+            .as_ref()
+            .or_else(|| self.disk.as_ref().and_then(|d| d.gap.as_ref()));
+        match gap {
+            Some(Size::Fixed(mib)) => mib * 1024 * 1024 / crate::layout::constants::SECTOR_SIZE,
+            _ => 0,
+        }
+    }
+
+    pub fn align_end(&self) -> bool {
+        self.disk.as_ref().is_some_and(|d| d.align_end)
+    }
+
+    /// Maximum total image size in MiB.
+    pub fn max_size_mb(&self) -> u64 {
+        match self.disk.as_ref().and_then(|d| d.max_size.as_ref()) {
+            Some(Size::Fixed(mb)) => *mb,
+            _ => DEFAULT_MAX_IMAGE_SIZE_MB,
+        }
+    }
+
     pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(DiskConfig {
+            gap: Some(Size::Auto),
+            ..
+        }) = &self.disk
+        {
+            anyhow::bail!("disk.gap = 'auto' is not supported, gaps must be fixed sizes.");
+        }
+        if let Some(DiskConfig {
+            max_size: Some(Size::Auto),
+            ..
+        }) = &self.disk
+        {
+            anyhow::bail!("disk.max_size = 'auto' is not supported, use a fixed size.");
+        }
         self.partitions.iter().try_for_each(|p| p.validate())?;
 
         Ok(())
@@ -79,6 +182,15 @@ impl core::fmt::Display for Layout {
             if let Some(guid) = &disk.guid {
                 writeln!(f, "  Disk GUID: {guid}")?;
             }
+            if let Some(gap) = &disk.gap {
+                writeln!(f, "  Gap: {gap}")?;
+            }
+            if disk.align_end {
+                writeln!(f, "  Align end: yes")?;
+            }
+            if let Some(max) = &disk.max_size {
+                writeln!(f, "  Max size: {max}")?;
+            }
             writeln!(f)?;
         }
 
@@ -120,3 +232,178 @@ impl core::fmt::Display for Layout {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn json_and_toml_layouts_match() {
+        let toml = r#"
+            [disk]
+            alignment = "1M"
+            guid = "12345678-1234-1234-1234-1234567890ab"
+            gap = "8M"
+
+            [[partitions]]
+            name = "EFI"
+            size = "64M"
+            fs = "fat32"
+            bootable = true
+
+            [[partitions]]
+            name = "rootfs"
+            type = "linux"
+            size = "auto"
+            fs = "ext4"
+            mountpoint = "rootfs"
+            label = "root"
+        "#;
+        let json = r#"{
+            "disk": {
+                "alignment": "1M",
+                "guid": "12345678-1234-1234-1234-1234567890ab",
+                "gap": "8M"
+            },
+            "partitions": [
+                { "name": "EFI", "size": "64M", "fs": "fat32", "bootable": true },
+                {
+                    "name": "rootfs",
+                    "type": "linux",
+                    "size": "auto",
+                    "fs": "ext4",
+                    "mountpoint": "rootfs",
+                    "label": "root"
+                }
+            ]
+        }"#;
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("rootfs")).unwrap();
+        fs::write(dir.path().join("layout.toml"), toml).unwrap();
+        fs::write(dir.path().join("layout.json"), json).unwrap();
+
+        let load = |name: &str| {
+            let mut layout = Layout::from_file(&dir.path().join(name), false).unwrap();
+            // Partitions without a GUID get a random one.
+            layout.partitions.iter_mut().for_each(|p| p.guid = None);
+            layout
+        };
+        let (from_toml, from_json) = (load("layout.toml"), load("layout.json"));
+        assert_eq!(from_toml, from_json);
+        assert_eq!(
+            from_json.partitions[1].size,
+            Size::Fixed(DEFAULT_AUTO_SIZE_MB)
+        );
+        assert_eq!(from_json.partitions[1].kind, Some(PartitionKind::Linux));
+    }
+
+    #[test]
+    fn misspelled_key_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("layout.toml");
+        fs::write(
+            &path,
+            r#"
+            [[partitions]]
+            name = "data"
+            size = "32M"
+            fs = "fat32"
+            lable = "DATA"
+            "#,
+        )
+        .unwrap();
+
+        let err = Layout::from_file(&path, false).unwrap_err().to_string();
+        assert!(err.contains("partitions.0.lable"), "{err}");
+
+        let layout = Layout::from_file(&path, true).unwrap();
+        assert_eq!(layout.partitions[0].label, None);
+    }
+
+    #[test]
+    fn toml_errors_point_at_the_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("layout.toml");
+        fs::write(
+            &path,
+            "[[partitions]]\nname = \"data\"\nsize = \"32M\"\nfs = \"fat32\"\nbootable = \"yes\"\n",
+        )
+        .unwrap();
+
+        let err = format!("{:#}", Layout::from_file(&path, false).unwrap_err());
+        assert!(err.contains("line 5"), "{err}");
+    }
+
+    #[test]
+    fn toml_string_layouts_go_through_the_same_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.toml"),
+            "[[partitions]]\nname = \"data\"\nsize = \"32M\"\nfs = \"fat32\"\n",
+        )
+        .unwrap();
+
+        let layout = Layout::from_toml_str(
+            "include = \"base.toml\"\n[disk]\nalignment = \"1M\"\n",
+            dir.path(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(layout.partitions[0].name, "data");
+        assert_eq!(layout.base_dir, dir.path().canonicalize().unwrap());
+
+        let toml =
+            "[[partitions]]\nname = \"data\"\nsize = \"32M\"\nfs = \"fat32\"\nlable = \"x\"\n";
+        let err = Layout::from_toml_str(toml, dir.path(), false).unwrap_err();
+        assert!(err.to_string().contains("partitions.0.lable"), "{err}");
+        assert!(Layout::from_toml_str(toml, dir.path(), true).is_ok());
+
+        let err = Layout::from_toml_str("[[partitions]]\nname = 1\n", dir.path(), false);
+        assert!(format!("{:#}", err.unwrap_err()).contains("line 2"));
+    }
+
+    #[test]
+    fn json_string_layouts_go_through_the_same_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let layout = Layout::from_json_str(
+            r#"{ "partitions": [{ "name": "data", "size": "32M", "fs": "fat32" }] }"#,
+            dir.path(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(layout.partitions[0].name, "data");
+
+        let json =
+            r#"{ "partitions": [{ "name": "data", "size": "32M", "fs": "fat32" }], "dsik": {} }"#;
+        let err = Layout::from_json_str(json, dir.path(), false).unwrap_err();
+        assert!(err.to_string().contains("dsik"), "{err}");
+        assert!(Layout::from_json_str(json, dir.path(), true).is_ok());
+    }
+
+    #[test]
+    fn from_file_detects_format_by_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("layout.json");
+        fs::write(
+            &json,
+            r#"{ "partitions": [{ "name": "data", "size": "32M", "fs": "fat32" }] }"#,
+        )
+        .unwrap();
+        let toml = dir.path().join("layout.toml");
+        fs::write(
+            &toml,
+            "[[partitions]]\nname = \"data\"\nsize = \"32M\"\nfs = \"fat32\"\n",
+        )
+        .unwrap();
+
+        let (a, b) = (
+            Layout::from_file(&json, false).unwrap(),
+            Layout::from_file(&toml, false).unwrap(),
+        );
+        assert_eq!(a.partitions[0].name, "data");
+        assert_eq!(a.partitions[0].size, b.partitions[0].size);
+        assert_eq!(a.partitions[0].kind, b.partitions[0].kind);
+    }
+}
@@ -5,6 +5,7 @@ pub mod filesystem;
 pub mod layout;
 pub mod partition;
 pub mod size;
+pub mod source;
 
 pub use filesystem::*;
 pub use layout::*;
@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: MIT
 
+use crate::layout::error::LayoutError;
 use crate::layout::filesystem::Filesystem;
-use crate::layout::size::Size;
+use crate::layout::size::{Percent, Size};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, PartialEq, Clone)]
@@ -22,6 +23,16 @@ pub struct Partition {
     pub payload: Option<std::path::PathBuf>,
     pub label: Option<String>,
     pub uuid: Option<String>,
+    /// Unallocated space left after this partition (overrides `disk.gap`)
+    #[serde(default)]
+    pub gap: Option<Size>,
+    /// Create a Windows-style `System Volume Information` directory (FAT32/exFAT)
+    #[serde(default)]
+    pub system_volume_info: bool,
+    /// Fail the build when injected files would fill the filesystem past
+    /// this percentage (`"90%"`), keeping headroom
+    #[serde(default)]
+    pub max_fill: Option<Percent>,
 }
 
 impl Partition {
@@ -35,6 +46,15 @@ impl Partition {
         !matches!(self.fs, Filesystem::Raw | Filesystem::None)
     }
 
+    /// Fails with [`LayoutError::NotMountable`] when files are requested for a
+    /// partition without a filesystem (raw/none).
+    pub fn ensure_injectable(&self) -> Result<(), LayoutError> {
+        if self.mountpoint.is_some() && !self.is_mountable() {
+            return Err(LayoutError::NotMountable(self.name.clone()));
+        }
+        Ok(())
+    }
+
     pub fn validate(&self) -> anyhow::Result<()> {
         if matches!(self.fs, Filesystem::Raw | Filesystem::None) {
             if self.mountpoint.is_some() {
@@ -60,9 +80,40 @@ impl Partition {
         }
 
         if let Size::Fixed(mb) = self.size {
+            if mb == 0 {
+                return Err(LayoutError::ZeroSize(self.name.clone()).into());
+            }
             self.fs.check_size_limit(mb)?;
         }
 
+        if self.system_volume_info && !matches!(self.fs, Filesystem::Fat32 | Filesystem::ExFat) {
+            anyhow::bail!(
+                "Partition '{}' sets 'system_volume_info' but fs={} (only fat32/exfat).",
+                self.name,
+                self.fs
+            );
+        }
+
+        if self.max_fill.is_some()
+            && !matches!(
+                self.fs,
+                Filesystem::Fat32 | Filesystem::ExFat | Filesystem::Ext4
+            )
+        {
+            anyhow::bail!(
+                "Partition '{}' sets 'max_fill' but fs={} (only fat32/exfat/ext4).",
+                self.name,
+                self.fs
+            );
+        }
+
+        if let Some(Size::Auto) = self.gap {
+            anyhow::bail!(
+                "Partition '{}' has gap = 'auto', gaps must be fixed sizes.",
+                self.name
+            );
+        }
+
         if let Size::Auto = self.size {
             anyhow::bail!(
                 "Partition '{}' still has size = 'auto' at validation step.",
@@ -104,6 +155,15 @@ impl Partition {
             );
         }
 
+        if !effective_kind.accepts(&self.fs) {
+            return Err(LayoutError::FsKindMismatch {
+                name: self.name.clone(),
+                fs: self.fs.to_string(),
+                kind: format!("{effective_kind:?}").to_lowercase(),
+            }
+            .into());
+        }
+
         if self.kind.is_some() && effective_kind != expected_kind {
             crate::log_verbose!(
                 "Warning: Partition '{}' uses fs '{}' but type '{:?}' (expected {:?})",
@@ -138,6 +198,21 @@ impl PartitionKind {
         )
     }
 
+    /// Whether a `fs` filesystem makes sense in a partition of this type
+    /// (e.g. an ESP must be FAT). Raw/none partitions are always accepted
+    /// since their content comes from a payload.
+    pub fn accepts(&self, fs: &Filesystem) -> bool {
+        use Filesystem::*;
+        match self {
+            _ if matches!(fs, Raw | None) => true,
+            PartitionKind::Esp => matches!(fs, Fat32),
+            PartitionKind::Data | PartitionKind::Recovery => matches!(fs, Fat32 | ExFat | Ntfs),
+            PartitionKind::Linux => matches!(fs, Ext4 | Btrfs | Xfs),
+            PartitionKind::Boot => matches!(fs, Fat32 | Ext4 | Btrfs | Xfs),
+            PartitionKind::Biosboot | PartitionKind::Swap => false,
+        }
+    }
+
     pub fn default_for_fs(fs: &Filesystem, bootable: bool) -> Self {
         match fs {
             Filesystem::Fat32 => {
@@ -153,3 +228,67 @@ impl PartitionKind {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(size: Size) -> Partition {
+        Partition {
+            name: "data".into(),
+            kind: None,
+            mountpoint: None,
+            size,
+            fs: Filesystem::Fat32,
+            bootable: false,
+            guid: Some(uuid::Uuid::nil()),
+            index: None,
+            payload: None,
+            label: None,
+            uuid: None,
+            gap: None,
+            system_volume_info: false,
+            max_fill: None,
+        }
+    }
+
+    #[test]
+    fn zero_size_partition_is_rejected() {
+        let err = part(Size::Fixed(0)).validate().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<LayoutError>(),
+            Some(LayoutError::ZeroSize(name)) if name == "data"
+        ));
+        assert_eq!(err.to_string(), "Partition 'data' has a zero size");
+
+        part(Size::Fixed(64)).validate().unwrap();
+    }
+
+    #[test]
+    fn fs_must_match_partition_type() {
+        let mut esp = part(Size::Fixed(64));
+        esp.kind = Some(PartitionKind::Esp);
+        esp.fs = Filesystem::Ext4;
+        let err = esp.validate().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<LayoutError>(),
+            Some(LayoutError::FsKindMismatch { name, .. }) if name == "data"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Partition 'data' of type 'esp' cannot hold fs=ext4"
+        );
+
+        esp.fs = Filesystem::Fat32;
+        esp.validate().unwrap();
+
+        // Basic data holding ext4 is rejected, a raw payload is fine anywhere.
+        let mut data = part(Size::Fixed(64));
+        data.kind = Some(PartitionKind::Data);
+        data.fs = Filesystem::Ext4;
+        assert!(data.validate().is_err());
+        data.fs = Filesystem::Raw;
+        data.guid = None;
+        data.validate().unwrap();
+    }
+}
@@ -26,15 +26,7 @@ impl<'de> Deserialize<'de> for Size {
             where
                 E: serde::de::Error,
             {
-                parse_size_mb(value).map(Size::Fixed).or_else(|_| {
-                    if value.trim().eq_ignore_ascii_case("auto") {
-                        Ok(Size::Auto)
-                    } else {
-                        Err(E::custom(format!(
-                            "Invalid size format '{value}'. Use K, M or G suffix."
-                        )))
-                    }
-                })
+                value.parse().map_err(E::custom)
             }
         }
 
@@ -42,6 +34,22 @@ impl<'de> Deserialize<'de> for Size {
     }
 }
 
+impl std::str::FromStr for Size {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_size_mb(value).map(Size::Fixed).or_else(|_| {
+            if value.trim().eq_ignore_ascii_case("auto") {
+                Ok(Size::Auto)
+            } else {
+                Err(format!(
+                    "Invalid size format '{value}'. Use K, M or G suffix."
+                ))
+            }
+        })
+    }
+}
+
 impl std::fmt::Display for Size {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -51,6 +59,66 @@ impl std::fmt::Display for Size {
     }
 }
 
+/// A percentage in 1..=100, written `"90%"` (or a bare `90`).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Percent(pub u8);
+
+impl<'de> Deserialize<'de> for Percent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PercentVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for PercentVisitor {
+            type Value = Percent;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a percentage like '90%'")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                let num = value.trim().trim_end_matches('%').trim();
+                num.parse::<u64>()
+                    .map_err(|_| {
+                        E::custom(format!("Invalid percentage '{value}'. Use e.g. '90%'."))
+                    })
+                    .and_then(|v| self.visit_u64(v))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    1..=100 => Ok(Percent(value as u8)),
+                    _ => Err(E::custom(format!(
+                        "Percentage {value}% out of range (1-100%)"
+                    ))),
+                }
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                self.visit_u64(u64::try_from(value).unwrap_or(0))
+            }
+        }
+
+        deserializer.deserialize_any(PercentVisitor)
+    }
+}
+
+impl std::fmt::Display for Percent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
 fn parse_size_mb(size: &str) -> anyhow::Result<u64> {
     let lower = size.trim().to_lowercase();
 
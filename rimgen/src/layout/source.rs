@@ -0,0 +1,318 @@
+// SPDX-License-Identifier: MIT
+
+//! Layout pre-processing: `include` directives and `${VAR}` expansion.
+//!
+//! Layout files are first loaded as a generic document so that includes
+//! can be merged and variables expanded before deserializing into
+//! [`Layout`](crate::layout::Layout).
+
+use anyhow::{Context, bail};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const INCLUDE_KEY: &str = "include";
+
+/// Loads `path` and everything it includes into a single document.
+///
+/// `include` may be a path or a list of paths, relative to the including
+/// file. Included files are merged in order and the including file wins.
+pub fn load(path: &Path) -> anyhow::Result<Value> {
+    load_with(path, &mut Vec::new(), &|name| std::env::var(name).ok())
+}
+
+/// [`load`] for an in-memory layout, JSON when `json` is set and TOML
+/// otherwise. Includes are resolved relative to `base_dir`.
+#[allow(dead_code)]
+pub fn load_str(content: &str, json: bool, base_dir: &Path) -> anyhow::Result<Value> {
+    let doc = if json {
+        serde_json::from_str(content)?
+    } else {
+        toml::from_str(content)?
+    };
+    resolve(doc, "Layout", base_dir, &mut Vec::new(), &|name| {
+        std::env::var(name).ok()
+    })
+}
+
+fn load_with(
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> anyhow::Result<Value> {
+    let canonical = path
+        .canonicalize()
+        .with_context(|| format!("Cannot open layout '{}'", path.display()))?;
+    if stack.contains(&canonical) {
+        bail!("Layout include cycle through '{}'", path.display());
+    }
+
+    let content = fs::read_to_string(&canonical)?;
+    let doc = parse(&canonical, &content)
+        .with_context(|| format!("Cannot parse layout '{}'", path.display()))?;
+
+    stack.push(canonical.clone());
+    let dir = canonical.parent().unwrap_or_else(|| Path::new("."));
+    let merged = resolve(
+        doc,
+        &format!("Layout '{}'", path.display()),
+        dir,
+        stack,
+        env,
+    )?;
+    stack.pop();
+    Ok(merged)
+}
+
+/// Expands `doc` and merges its includes, looked up relative to `dir`.
+/// `what` names the document in errors.
+fn resolve(
+    mut doc: Value,
+    what: &str,
+    dir: &Path,
+    stack: &mut Vec<PathBuf>,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> anyhow::Result<Value> {
+    expand_env(&mut doc, env)?;
+
+    let Value::Object(map) = &mut doc else {
+        bail!("{what} is not a table");
+    };
+    let includes = match map.remove(INCLUDE_KEY) {
+        None => Vec::new(),
+        Some(Value::String(one)) => vec![one],
+        Some(Value::Array(many)) => many
+            .into_iter()
+            .map(|v| match v {
+                Value::String(s) => Ok(s),
+                _ => bail!("'include' entries must be paths"),
+            })
+            .collect::<anyhow::Result<_>>()?,
+        Some(_) => bail!("'include' must be a path or a list of paths"),
+    };
+
+    let mut merged = Value::Object(Map::new());
+    for include in includes {
+        let base = load_with(&dir.join(include), stack, env)?;
+        merge(&mut merged, base);
+    }
+
+    merge(&mut merged, doc);
+    Ok(merged)
+}
+
+/// Parses a layout document, as JSON for `.json` files and TOML otherwise.
+fn parse(path: &Path, content: &str) -> anyhow::Result<Value> {
+    if is_json(path) {
+        Ok(serde_json::from_str(content)?)
+    } else {
+        Ok(toml::from_str(content)?)
+    }
+}
+
+/// Deserializes `path` straight from its TOML source, for an error that
+/// carries line and column.
+///
+/// Only done when [`load`] passes the file through unchanged (TOML without
+/// `include` or `$`): the merged document then is the file itself, so this
+/// fails exactly where deserializing the document did. `None` otherwise, or
+/// if the direct parse succeeds.
+pub fn spanned_error<T: serde::de::DeserializeOwned>(path: &Path) -> Option<anyhow::Error> {
+    if is_json(path) {
+        return None;
+    }
+    spanned_error_str::<T>(&fs::read_to_string(path).ok()?)
+}
+
+/// [`spanned_error`] for an in-memory TOML layout.
+pub fn spanned_error_str<T: serde::de::DeserializeOwned>(content: &str) -> Option<anyhow::Error> {
+    let table: toml::Table = toml::from_str(content).ok()?;
+    if content.contains('$') || table.contains_key(INCLUDE_KEY) {
+        return None;
+    }
+    toml::from_str::<T>(content).err().map(Into::into)
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// Expands `${VAR}` in every string of `value`; `$$` is a literal `$`.
+fn expand_env(value: &mut Value, env: &dyn Fn(&str) -> Option<String>) -> anyhow::Result<()> {
+    match value {
+        Value::String(s) if s.contains('$') => *s = expand_str(s, env)?,
+        Value::Array(items) => items.iter_mut().try_for_each(|v| expand_env(v, env))?,
+        Value::Object(map) => map.values_mut().try_for_each(|v| expand_env(v, env))?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn expand_str(s: &str, env: &dyn Fn(&str) -> Option<String>) -> anyhow::Result<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            out.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('{') {
+            let Some(end) = after.find('}') else {
+                bail!("Unterminated '${{' in '{s}'");
+            };
+            let name = &after[..end];
+            match env(name) {
+                Some(val) => out.push_str(&val),
+                None => bail!("Environment variable '{name}' is not set (in '{s}')"),
+            }
+            rest = &after[end + 1..];
+        } else {
+            out.push('$');
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Merges `over` into `base`: tables merge key by key, lists of named
+/// tables (like `partitions`) merge by `name`, anything else is replaced.
+fn merge(base: &mut Value, over: Value) {
+    match (base, over) {
+        (Value::Object(base), Value::Object(over)) => {
+            for (key, value) in over {
+                match base.get_mut(&key) {
+                    Some(slot) => merge(slot, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(base), Value::Array(over)) if is_named_list(base) && is_named_list(&over) => {
+            for item in over {
+                match base.iter_mut().find(|b| b.get("name") == item.get("name")) {
+                    Some(slot) => merge(slot, item),
+                    None => base.push(item),
+                }
+            }
+        }
+        (base, over) => *base = over,
+    }
+}
+
+fn is_named_list(items: &[Value]) -> bool {
+    items
+        .iter()
+        .all(|v| v.get("name").is_some_and(Value::is_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{Layout, Size};
+
+    fn vars(name: &str) -> Option<String> {
+        match name {
+            "VERSION" => Some("1.2".into()),
+            "ROOT_SIZE" => Some("256M".into()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn env_variables_are_expanded() {
+        assert_eq!(
+            expand_str("rootfs-${VERSION}", &vars).unwrap(),
+            "rootfs-1.2"
+        );
+        assert_eq!(expand_str("${ROOT_SIZE}", &vars).unwrap(), "256M");
+        assert_eq!(
+            expand_str("cost $$5 or $x", &vars).unwrap(),
+            "cost $5 or $x"
+        );
+        assert!(expand_str("${MISSING}", &vars).is_err());
+        assert!(expand_str("${VERSION", &vars).is_err());
+
+        let mut doc: Value = toml::from_str(
+            r#"
+            [[partitions]]
+            name = "root-${VERSION}"
+            size = "${ROOT_SIZE}"
+            fs = "ext4"
+            "#,
+        )
+        .unwrap();
+        expand_env(&mut doc, &vars).unwrap();
+        let layout: Layout = serde_json::from_value(doc).unwrap();
+        assert_eq!(layout.partitions[0].name, "root-1.2");
+        assert_eq!(layout.partitions[0].size, Size::Fixed(256));
+    }
+
+    #[test]
+    fn include_merges_with_override() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.toml"),
+            r#"
+            [disk]
+            alignment = "1M"
+            gap = "4M"
+
+            [[partitions]]
+            name = "EFI"
+            size = "64M"
+            fs = "fat32"
+            bootable = true
+
+            [[partitions]]
+            name = "rootfs"
+            size = "128M"
+            fs = "ext4"
+            "#,
+        )
+        .unwrap();
+        let top = dir.path().join("board.toml");
+        fs::write(
+            &top,
+            r#"
+            include = "base.toml"
+
+            [disk]
+            gap = "8M"
+
+            [[partitions]]
+            name = "rootfs"
+            size = "${ROOT_SIZE}"
+
+            [[partitions]]
+            name = "data"
+            size = "32M"
+            fs = "exfat"
+            "#,
+        )
+        .unwrap();
+
+        let doc = load_with(&top, &mut Vec::new(), &vars).unwrap();
+        let layout: Layout = serde_json::from_value(doc).unwrap();
+        let disk = layout.disk.unwrap();
+        assert_eq!(disk.alignment.as_deref(), Some("1M"));
+        assert_eq!(disk.gap, Some(Size::Fixed(8)));
+
+        let names: Vec<_> = layout.partitions.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["EFI", "rootfs", "data"]);
+        assert_eq!(layout.partitions[1].size, Size::Fixed(256));
+        assert!(layout.partitions[0].bootable);
+    }
+
+    #[test]
+    fn include_cycles_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.toml"), "include = \"b.json\"\n").unwrap();
+        fs::write(dir.path().join("b.json"), r#"{ "include": ["a.toml"] }"#).unwrap();
+
+        let err = load(&dir.path().join("a.toml")).unwrap_err();
+        assert!(format!("{err:#}").contains("cycle"), "{err:#}");
+    }
+}
@@ -9,8 +9,8 @@ mod utils;
 mod host;
 
 use crate::{
-    layout::Layout,
-    out::{target::DryRunMode, *},
+    layout::{Layout, Size},
+    out::{report::ReportOptions, target::DryRunMode, *},
 };
 use clap::{Parser, Subcommand};
 use std::{path::PathBuf, time::Instant};
@@ -34,14 +34,15 @@ struct Cli {
 enum Commands {
     /// Build a disk image from a declarative layout file.
     ///
-    /// Supported input: TOML layout files.
+    /// Supported input: TOML or JSON (`.json`) layout files.
     /// Supported output formats: Raw (.img), VHD (.vhd), VMDK (.vmdk), QCOW2 (.qcow2), VDI (.vdi).
     Build {
-        /// Layout configuration file path (TOML)
+        /// Layout configuration file path (TOML, or JSON with a `.json` extension)
         #[arg(short, long, default_value = "layout/layout.toml")]
         layout: PathBuf,
 
-        /// Output image structure. Extension determines format: .img, .vhd, .vmdk, .qcow2, .vdi
+        /// Output image structure. Extension determines format: .img, .vhd, .vmdk, .qcow2, .vdi.
+        /// A disk (`/dev/sdb`, `\\.\PhysicalDrive1`) gets the raw image written onto it
         #[arg(short, long, default_value = "output.img")]
         output: PathBuf,
 
@@ -49,10 +50,38 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
 
+        /// Pad partitions so their end LBA is aligned as well (same as `disk.align_end`)
+        #[arg(long)]
+        align_end: bool,
+
+        /// Abort if the computed image is larger than this, e.g. `256G` (same as `disk.max_size`, default 64G)
+        #[arg(long)]
+        max_size: Option<Size>,
+
+        /// Only warn about unknown layout keys instead of failing
+        #[arg(long)]
+        allow_unknown: bool,
+
+        /// Re-read and validate the GPT (and protective MBR) after the build
+        #[arg(long)]
+        verify_gpt: bool,
+
+        /// Write a JSON report of the built partitions (offsets, GUIDs, CRC32) to this path
+        #[arg(long)]
+        report: Option<PathBuf>,
+
+        /// Also include a SHA-256 per partition in the report
+        #[arg(long, requires = "report")]
+        report_sha256: bool,
+
         /// Overwrite existing output file if it exists
         #[arg(long)]
         truncate: bool,
 
+        /// Allow writing to a disk that isn't known to be removable (internal drives; any disk on macOS)
+        #[arg(long)]
+        force: bool,
+
         /// Increase logging verbosity (-v, -vv)
         #[arg(long, short, action = clap::ArgAction::Count)]
         verbose: u8,
@@ -71,7 +100,14 @@ fn main() -> anyhow::Result<()> {
             layout,
             output,
             dry_run,
+            align_end,
+            max_size,
+            allow_unknown,
+            verify_gpt,
+            report,
+            report_sha256,
             truncate,
+            force,
             verbose,
             quiet,
         } => {
@@ -93,68 +129,93 @@ fn main() -> anyhow::Result<()> {
             }
 
             let layout_path = layout;
-            let layout = Layout::from_file(&layout_path)?;
+            let mut layout = Layout::from_file(&layout_path, allow_unknown)?;
+            if align_end {
+                layout.disk.get_or_insert_with(Default::default).align_end = true;
+            }
+            if let Some(max_size) = max_size {
+                layout.disk.get_or_insert_with(Default::default).max_size = Some(max_size);
+            }
             layout.validate()?;
             crate::log_verbose!("Parsed layout {layout}");
 
             let out_kind = Output::from_path(&output)?;
+            if !dry_run && target::is_device(&output) {
+                target::ensure_removable(&output, force)?;
+            }
+            let report = report.map(|path| ReportOptions {
+                path,
+                sha256: report_sha256,
+            });
 
             let res = match out_kind {
                 Output::Img => img::create(
                     &layout,
                     &output,
                     &truncate,
+                    &verify_gpt,
                     if dry_run {
                         DryRunMode::Tempfile
                     } else {
                         DryRunMode::Off
                     },
+                    report.as_ref(),
                 ),
                 Output::Qcow2 => qcow2::create(
                     &layout,
                     &output,
                     &truncate,
+                    &verify_gpt,
                     if dry_run {
                         DryRunMode::Tempfile
                     } else {
                         DryRunMode::Off
                     },
+                    report.as_ref(),
                 ),
                 Output::Vdi => vdi::create(
                     &layout,
                     &output,
                     &truncate,
+                    &verify_gpt,
                     if dry_run {
                         DryRunMode::Tempfile
                     } else {
                         DryRunMode::Off
                     },
+                    report.as_ref(),
                 ),
                 Output::Vhd => vhd::create(
                     &layout,
                     &output,
                     &truncate,
+                    &verify_gpt,
                     if dry_run {
                         DryRunMode::Tempfile
                     } else {
                         DryRunMode::Off
                     },
+                    report.as_ref(),
                 ),
                 Output::Vmdk => vmdk::create(
                     &layout,
                     &output,
                     &truncate,
+                    &verify_gpt,
                     if dry_run {
                         DryRunMode::Tempfile
                     } else {
                         DryRunMode::Off
                     },
+                    report.as_ref(),
                 ),
             };
 
             let dt = t0.elapsed().as_secs_f32();
             if let Err(e) = res {
-                let _ = std::fs::remove_file(&output);
+                if !target::is_device(&output) {
+                    let _ = std::fs::remove_file(&output);
+                }
 
                 crate::log_normal!(
                     "❌ Failed to write {} in {:.2}s\n  ↳ {}",
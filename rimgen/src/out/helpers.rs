@@ -12,13 +12,22 @@ pub fn partition_to_gpt_partition_entry(
     start: u64,
     end: u64,
 ) -> Result<GptEntry> {
+    if end < start {
+        anyhow::bail!(
+            "Partition '{}' has an empty LBA range ({}..={})",
+            partition.name,
+            start,
+            end
+        );
+    }
+
     let type_guid = gpt_type_guid_for_kind(&partition.effective_kind());
 
+    // GPT stores GUIDs mixed-endian: the first three fields are little-endian.
     let unique_guid = partition
         .guid
         .ok_or_else(|| anyhow::anyhow!("Missing GUID for '{}'", partition.name))?
-        .as_u128()
-        .to_le_bytes();
+        .to_bytes_le();
 
     Ok(GptEntry::new(
         type_guid,
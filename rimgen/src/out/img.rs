@@ -1,6 +1,8 @@
 use crate::layout::constants::*;
+use crate::layout::error::LayoutError;
 use crate::layout::*;
 use crate::out::helpers::{partition_to_gpt_partition_entry, size_to_sectors};
+use crate::out::report::{self, ReportOptions};
 use crate::out::target::{DryRunMode, TargetImage};
 use crate::utils;
 use colored::Colorize;
@@ -21,15 +23,16 @@ pub fn create(
     layout: &Layout,
     output: &Path,
     truncate: &bool,
+    verify_gpt: &bool,
     dry_mode: DryRunMode,
+    report: Option<&ReportOptions>,
 ) -> anyhow::Result<()> {
     let t0 = Instant::now();
     let total_sectors = calculate_total_disk_sectors(layout);
     let total_bytes = total_sectors * SECTOR_SIZE;
+    check_max_size(layout, total_bytes)?;
 
     // PLAN: pure calculations (always performed)
-    let mut partition_entries = vec![];
-
     // Determine alignment (default 1MB = 2048 sectors)
     let align_sectors = if let Some(disk) = &layout.disk {
         if let Some(align_str) = &disk.alignment {
@@ -41,22 +44,7 @@ pub fn create(
         rimpart::gpt::align_lba_1m(SECTOR_SIZE)
     };
 
-    let mut start = align_sectors;
-
-    for part in &layout.partitions {
-        let sectors = size_to_sectors(&part.size);
-        let end = start + sectors - 1;
-        if end >= total_sectors {
-            anyhow::bail!(
-                "Partition '{}' does not fit ({} > {})",
-                part.name,
-                end,
-                total_sectors
-            );
-        }
-        partition_entries.push(partition_to_gpt_partition_entry(part, start, end)?);
-        start = rimpart::gpt::align_up(end, align_sectors);
-    }
+    let partition_entries = plan_partition_entries(layout, total_sectors, align_sectors)?;
 
     // Plan mode => stop after the plan and logical checks
     if matches!(dry_mode, DryRunMode::Plan) {
@@ -85,16 +73,14 @@ pub fn create(
 
     // Real GPT writes
     {
-        let disk_guid = if let Some(disk) = &layout.disk {
-            if let Some(guid) = disk.guid {
-                *guid.as_bytes()
-            } else {
-                *Uuid::new_v4().as_bytes()
-            }
-        } else {
-            *Uuid::new_v4().as_bytes()
-        };
-
+        let disk_guid = layout
+            .disk
+            .as_ref()
+            .and_then(|disk| disk.guid)
+            .unwrap_or_else(Uuid::new_v4)
+            .to_bytes_le();
+
+        let device = target.device;
         let mut io = target.as_io()?;
 
         rimpart::mbr::write_mbr_protective(&mut io, total_sectors)
@@ -102,7 +88,9 @@ pub fn create(
         rimpart::gpt::write_gpt_from_entries(&mut io, &partition_entries, total_sectors, disk_guid)
             .map_err(|e| anyhow::anyhow!("{}", e))?;
 
-        if *truncate
+        if *truncate && device {
+            crate::log_info!("Output is a disk: --truncate ignored.");
+        } else if *truncate
             && let Some(rep) = rimpart::truncate_image_custom_sector(
                 &mut io,
                 &partition_entries,
@@ -119,7 +107,11 @@ pub fn create(
             );
         }
 
-        rimpart::validate_full_disk(&mut io).map_err(|e| anyhow::anyhow!("{}", e))?;
+        let alignment =
+            rimpart::validate_full_disk(&mut io).map_err(|e| anyhow::anyhow!("{}", e))?;
+        for finding in alignment.iter().filter(|f| f.is_warning()) {
+            crate::log_info!("{}", finding.to_string().yellow());
+        }
         let info = rimpart::scan_disk_with_sector(&mut io, SECTOR_SIZE)
             .map_err(|e| anyhow::anyhow!("{}", e))?;
         crate::log_verbose!("{info}");
@@ -132,6 +124,48 @@ pub fn create(
 
     format_inject(layout, &mut target)?;
 
+    if *verify_gpt {
+        verify_gpt_image(&mut target.as_io()?, total_sectors)?;
+        crate::log_info!("GPT verified");
+    }
+
+    if let Some(opts) = report {
+        if matches!(dry_mode, DryRunMode::Off) {
+            report::write_report(&mut target.as_io()?, layout, opts)?;
+            crate::log_info!("Report written to {}", opts.path.display());
+        } else {
+            crate::log_info!("Dry-run: report not written.");
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-reads the partition tables and checks them: protective MBR, entry
+/// bounds, overlaps, unique GUIDs and the entry array CRC.
+pub fn verify_gpt_image<IO: rimio::RimIO + ?Sized>(
+    io: &mut IO,
+    total_sectors: u64,
+) -> anyhow::Result<()> {
+    let fail = |what: &str, e: rimpart::errors::PartError| {
+        anyhow::anyhow!("GPT verification failed ({what}): {e}")
+    };
+
+    rimpart::mbr::read_mbr(io)
+        .and_then(|mbr| mbr.validate_protective(total_sectors))
+        .map_err(|e| fail("protective MBR", e))?;
+
+    let mut reader =
+        rimpart::gpt_stream::GptStreamReader::<_, { SECTOR_SIZE as usize }>::new(io, SECTOR_SIZE)
+            .map_err(|e| fail("header", e))?;
+    reader.validate_bounds().map_err(|e| fail("bounds", e))?;
+    reader
+        .validate_overlaps()
+        .map_err(|e| fail("overlaps", e))?;
+    reader
+        .validate_unique_guids()
+        .map_err(|e| fail("unique GUIDs", e))?;
+    reader.validate_crc().map_err(|e| fail("entries CRC", e))?;
     Ok(())
 }
 
@@ -163,6 +197,7 @@ fn format_inject(layout: &Layout, target: &mut TargetImage) -> anyhow::Result<()
     let mut parser = rimfs::core::StdResolver::new();
 
     for (i, part) in layout.partitions.iter().enumerate() {
+        part.ensure_injectable()?;
         let mountpoint = &part.mountpoint.as_deref().unwrap_or("");
         pb.set_message(format!("Partition {}/{}", i + 1, layout.partitions.len()));
 
@@ -290,8 +325,12 @@ fn format_inject_fat32(
     formatter.format(false)?;
 
     let mut allocator = Fat32Allocator::new(&meta);
+    let opts = FsInjectOptions {
+        system_volume_info: part.system_volume_info,
+        max_fill: part.max_fill.map(|p| p.0),
+    };
     let mut injector = Fat32Injector::new(io, &mut allocator, &meta);
-    injector.inject_tree(node)?;
+    injector.inject_tree_with(node, &opts)?;
 
     let mut checker = Fat32Checker::new(io, &meta);
     let report = checker.check_all()?;
@@ -359,8 +398,12 @@ fn format_inject_exfat(
     formatter.format(false)?;
 
     let mut allocator = ExFatAllocator::new(&meta);
+    let opts = FsInjectOptions {
+        system_volume_info: part.system_volume_info,
+        max_fill: part.max_fill.map(|p| p.0),
+    };
     let mut injector = ExFatInjector::new(io, &mut allocator, &meta)?;
-    injector.inject_tree(node)?;
+    injector.inject_tree_with(node, &opts)?;
 
     let mut checker = ExFatChecker::new(io, &meta);
     let report = checker.check_all()?;
@@ -420,8 +463,12 @@ fn format_inject_ext4(
     formatter.format(false)?;
 
     let mut allocator = Ext4Allocator::new(&meta);
+    let opts = FsInjectOptions {
+        max_fill: part.max_fill.map(|p| p.0),
+        ..Default::default()
+    };
     let mut injector = Ext4Injector::new(io, &mut allocator, &meta);
-    injector.inject_tree(node)?;
+    injector.inject_tree_with(node, &opts)?;
 
     let mut checker = Ext4Checker::new(io, &meta);
     let report = checker.check_all()?;
@@ -534,16 +581,60 @@ fn format_raw(
     Ok(())
 }
 
+/// Lays out the GPT entries of `layout` on a disk of `total_sectors`.
+///
+/// Places partitions sequentially on `align_sectors` boundaries, honoring gaps.
+/// With `disk.align_end`, lengths are padded up so ends are aligned too.
+fn plan_partition_entries(
+    layout: &Layout,
+    total_sectors: u64,
+    align_sectors: u64,
+) -> anyhow::Result<Vec<GptEntry>> {
+    let mut entries = Vec::with_capacity(layout.partitions.len());
+    let mut start = align_sectors;
+
+    for (i, part) in layout.partitions.iter().enumerate() {
+        let mut sectors = size_to_sectors(&part.size);
+        if layout.align_end() {
+            sectors = rimpart::gpt::align_up(sectors, align_sectors);
+        }
+        let end = start + sectors - 1;
+        if end >= total_sectors {
+            anyhow::bail!(
+                "Partition '{}' does not fit ({} > {})",
+                part.name,
+                end,
+                total_sectors
+            );
+        }
+        entries.push(partition_to_gpt_partition_entry(part, start, end)?);
+        start = rimpart::gpt::align_up(end + 1 + layout.gap_after_sectors(i), align_sectors);
+    }
+
+    Ok(entries)
+}
+
 /// Calculate total disk sectors needed
 fn calculate_total_disk_sectors(layout: &Layout) -> u64 {
     layout
         .partitions
         .iter()
-        .map(|p| size_to_sectors(&p.size) + ALIGNMENT)
+        .enumerate()
+        .map(|(i, p)| size_to_sectors(&p.size) + layout.gap_after_sectors(i) + ALIGNMENT)
         .sum::<u64>()
         + ALIGNMENT
 }
 
+/// Guards against a layout mistake turning into a giant (sparse) image.
+fn check_max_size(layout: &Layout, total_bytes: u64) -> Result<(), LayoutError> {
+    let size_mb = total_bytes.div_ceil(1024 * 1024);
+    let max_mb = layout.max_size_mb();
+    if size_mb > max_mb {
+        return Err(LayoutError::ImageTooLarge { size_mb, max_mb });
+    }
+    Ok(())
+}
+
 fn parse_alignment_sectors(s: &str) -> anyhow::Result<u64> {
     let lower = s.trim().to_lowercase();
     let bytes = if let Some(stripped) = lower.strip_suffix("k") {
@@ -567,3 +658,252 @@ fn parse_alignment_sectors(s: &str) -> anyhow::Result<u64> {
 
     Ok(bytes / SECTOR_SIZE)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gap_between_partitions() {
+        let toml = r#"
+            [disk]
+            gap = "16M"
+
+            [[partitions]]
+            name = "first"
+            size = "32M"
+            fs = "fat32"
+
+            [[partitions]]
+            name = "second"
+            size = "32M"
+            fs = "fat32"
+        "#;
+        let mut layout: Layout = toml::from_str(toml).unwrap();
+        layout.assign_guids();
+        layout.validate().unwrap();
+
+        let total = calculate_total_disk_sectors(&layout);
+        let align = rimpart::gpt::align_lba_1m(SECTOR_SIZE);
+        let entries = plan_partition_entries(&layout, total, align).unwrap();
+
+        let gap = 16 * 1024 * 1024 / SECTOR_SIZE;
+        let (first_end, second_start) = (entries[0].end_lba, entries[1].start_lba);
+        assert_eq!(second_start, first_end + 1 + gap);
+        assert!(second_start.is_multiple_of(align));
+        assert!(entries[1].end_lba < total);
+    }
+
+    #[test]
+    fn align_end_pads_partitions() {
+        let toml = r#"
+            [disk]
+            align_end = true
+
+            [[partitions]]
+            name = "first"
+            size = "33000K"
+            fs = "fat32"
+
+            [[partitions]]
+            name = "second"
+            size = "40M"
+            fs = "fat32"
+        "#;
+        let mut layout: Layout = toml::from_str(toml).unwrap();
+        layout.assign_guids();
+        layout.validate().unwrap();
+
+        let total = calculate_total_disk_sectors(&layout);
+        let align = rimpart::gpt::align_lba_1m(SECTOR_SIZE);
+        let entries = plan_partition_entries(&layout, total, align).unwrap();
+
+        for e in &entries {
+            assert!(e.start_lba.is_multiple_of(align));
+            assert!((e.end_lba + 1).is_multiple_of(align));
+            assert!(e.end_lba < total);
+        }
+        assert_eq!(entries[1].start_lba, entries[0].end_lba + 1);
+    }
+
+    #[test]
+    fn verify_gpt_after_build() {
+        use std::io::{Seek, SeekFrom, Write};
+
+        let toml = r#"
+            [[partitions]]
+            name = "esp"
+            size = "32M"
+            fs = "fat32"
+
+            [[partitions]]
+            name = "data"
+            size = "16M"
+            fs = "ext4"
+        "#;
+        let mut layout: Layout = toml::from_str(toml).unwrap();
+        layout.assign_guids();
+        layout.validate().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.img");
+        create(&layout, &path, &false, &true, DryRunMode::Off, None).unwrap();
+
+        let total = calculate_total_disk_sectors(&layout);
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+        verify_gpt_image(&mut rimio::prelude::StdRimIO::new(&mut file), total).unwrap();
+
+        // Corrupt the first entry's name: the stored entries CRC no longer matches.
+        file.seek(SeekFrom::Start(2 * SECTOR_SIZE + 60)).unwrap();
+        file.write_all(b"X").unwrap();
+        let err =
+            verify_gpt_image(&mut rimio::prelude::StdRimIO::new(&mut file), total).unwrap_err();
+        assert!(err.to_string().contains("entries CRC"), "{err}");
+    }
+
+    #[test]
+    fn oversized_layout_needs_max_size_override() {
+        let toml = r#"
+            [[partitions]]
+            name = "huge"
+            size = "100G"
+            fs = "raw"
+            type = "linux"
+        "#;
+        let mut layout: Layout = toml::from_str(toml).unwrap();
+        layout.assign_guids();
+        layout.validate().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.img");
+        let err = create(&layout, &path, &false, &false, DryRunMode::Plan, None).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<LayoutError>(),
+            Some(LayoutError::ImageTooLarge { max_mb, .. }) if *max_mb == DEFAULT_MAX_IMAGE_SIZE_MB
+        ));
+        assert!(!path.exists());
+
+        layout.disk.get_or_insert_with(Default::default).max_size = Some("128G".parse().unwrap());
+        layout.validate().unwrap();
+        create(&layout, &path, &false, &false, DryRunMode::Plan, None).unwrap();
+    }
+
+    #[test]
+    fn inject_into_raw_partition_is_not_mountable() {
+        let toml = r#"
+            [[partitions]]
+            name = "blob"
+            size = "4M"
+            fs = "raw"
+            mountpoint = "files"
+        "#;
+        let mut layout: Layout = toml::from_str(toml).unwrap();
+        layout.assign_guids();
+        // validate() would catch this too; the injection path must not rely on it.
+        assert!(layout.validate().is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let err = create(
+            &layout,
+            &dir.path().join("out.img"),
+            &false,
+            &false,
+            DryRunMode::Tempfile,
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<LayoutError>(),
+            Some(LayoutError::NotMountable(name)) if name == "blob"
+        ));
+    }
+
+    #[test]
+    fn pinned_partition_guid_is_written_mixed_endian() {
+        let toml = r#"
+            [disk]
+            guid = "01234567-89AB-CDEF-0123-456789ABCDEF"
+
+            [[partitions]]
+            name = "esp"
+            size = "32M"
+            fs = "fat32"
+            guid = "C12A7328-F81F-11D2-BA4B-00A0C93EC93B"
+
+            [[partitions]]
+            name = "data"
+            size = "16M"
+            fs = "fat32"
+        "#;
+        let mut layout: Layout = toml::from_str(toml).unwrap();
+        layout.assign_guids();
+        layout.validate().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.img");
+        create(&layout, &path, &false, &true, DryRunMode::Off, None).unwrap();
+
+        let mut file = std::fs::File::open(&path).unwrap();
+        let mut io = rimio::prelude::StdRimIO::new(&mut file);
+        let (hdr, entries) = rimpart::gpt::read_gpt(&mut io).unwrap();
+
+        // Same string as the ESP type GUID, so the on-disk bytes must match it.
+        assert_eq!(
+            entries[0].unique_guid,
+            rimpart::guids::GPT_PARTITION_TYPE_ESP
+        );
+        assert_eq!(
+            hdr.disk_guid,
+            [
+                0x67, 0x45, 0x23, 0x01, 0xAB, 0x89, 0xEF, 0xCD, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB,
+                0xCD, 0xEF
+            ]
+        );
+        assert_eq!(
+            rimpart::guids::format_guid(&entries[0].unique_guid),
+            "C12A7328-F81F-11D2-BA4B-00A0C93EC93B"
+        );
+        // Unpinned partitions still get a fresh GUID.
+        assert_ne!(entries[1].unique_guid, [0; 16]);
+    }
+
+    #[test]
+    fn max_fill_limits_injected_data() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("files")).unwrap();
+        std::fs::write(dir.path().join("files/blob.bin"), vec![0xA5; 4 << 20]).unwrap();
+
+        let build = |max_fill: &str| {
+            let toml = format!(
+                r#"
+                [[partitions]]
+                name = "data"
+                size = "32M"
+                fs = "fat32"
+                mountpoint = "files"
+                max_fill = "{max_fill}"
+                "#
+            );
+            let mut layout: Layout = toml::from_str(&toml).unwrap();
+            layout.base_dir = dir.path().to_path_buf();
+            layout.assign_guids();
+            layout.validate().unwrap();
+            create(
+                &layout,
+                &dir.path().join("out.img"),
+                &true,
+                &false,
+                DryRunMode::Tempfile,
+                None,
+            )
+        };
+
+        build("50%").unwrap();
+        let err = build("10%").unwrap_err();
+        assert!(err.to_string().contains("max_fill"), "{err}");
+    }
+}
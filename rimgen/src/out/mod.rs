@@ -1,6 +1,7 @@
 mod helpers;
 pub mod img;
 pub mod qcow2;
+pub mod report;
 pub mod target;
 pub mod vdi;
 pub mod vhd;
@@ -18,6 +19,11 @@ pub enum Output {
 
 impl Output {
     pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        // Disks get the raw image written straight onto them.
+        if target::is_device(path) {
+            return Ok(Output::Img);
+        }
+
         let ext = path
             .extension()
             .and_then(|s| s.to_str())
@@ -11,6 +11,7 @@ use std::path::Path;
 
 use crate::layout::Layout;
 use crate::out::img;
+use crate::out::report::ReportOptions;
 use crate::out::target::DryRunMode;
 
 use zerocopy::byteorder::{BigEndian, U32, U64};
@@ -75,12 +76,14 @@ pub fn create(
     layout: &Layout,
     output: &Path,
     truncate: &bool,
+    verify_gpt: &bool,
     dry_mode: DryRunMode,
+    report: Option<&ReportOptions>,
 ) -> anyhow::Result<()> {
     crate::log_verbose!("Create temp img.");
     let temp_root = tempfile::tempdir()?;
     let temp_path = temp_root.path().join("rim_temp.img");
-    img::create(layout, &temp_path, truncate, dry_mode)?;
+    img::create(layout, &temp_path, truncate, verify_gpt, dry_mode, report)?;
     if matches!(dry_mode, DryRunMode::Off) {
         crate::log_verbose!("Wrapping img to qcow2.");
         wrap_raw_as_qcow2_to(&temp_path, output)?;
@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: MIT
+
+//! Build report: a JSON sidecar documenting the produced partitions and
+//! their checksums.
+
+use rimio::prelude::*;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use crate::layout::Layout;
+use crate::layout::constants::SECTOR_SIZE;
+
+/// Where to write the report and which digests to include.
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    pub path: PathBuf,
+    /// Also hash each partition with SHA-256 (slower than CRC32).
+    pub sha256: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BuildReport {
+    pub disk_guid: String,
+    pub total_bytes: u64,
+    pub sector_size: u64,
+    pub partitions: Vec<PartitionReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartitionReport {
+    pub index: usize,
+    pub name: String,
+    pub fs: String,
+    pub guid: String,
+    pub start_lba: u64,
+    pub end_lba: u64,
+    pub size_bytes: u64,
+    /// CRC32 (IEEE) of the whole partition, as `0x%08X`
+    pub crc32: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// Builds the report from the partition table as written on `io`.
+pub fn build_report(
+    io: &mut dyn RimIO,
+    layout: &Layout,
+    opts: &ReportOptions,
+) -> anyhow::Result<BuildReport> {
+    let (header, entries) = rimpart::gpt::read_gpt_with_sector(io, SECTOR_SIZE)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let partitions = entries
+        .iter()
+        .enumerate()
+        .map(|(index, e)| {
+            let offset = e.start_lba * SECTOR_SIZE;
+            let size_bytes = (e.end_lba - e.start_lba + 1) * SECTOR_SIZE;
+            let crc = io.crc32_region(offset, size_bytes)?;
+            let sha256 = if opts.sha256 {
+                Some(sha256_region(io, offset, size_bytes)?)
+            } else {
+                None
+            };
+            Ok(PartitionReport {
+                index,
+                name: e.name_string(),
+                fs: layout
+                    .partitions
+                    .get(index)
+                    .map_or_else(|| "unknown".into(), |p| p.fs.to_string()),
+                guid: rimpart::guids::format_guid(&e.unique_guid),
+                start_lba: e.start_lba,
+                end_lba: e.end_lba,
+                size_bytes,
+                crc32: format!("0x{crc:08X}"),
+                sha256,
+            })
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(BuildReport {
+        disk_guid: rimpart::guids::format_guid(&header.disk_guid),
+        total_bytes: (header.backup_lba + 1) * SECTOR_SIZE,
+        sector_size: SECTOR_SIZE,
+        partitions,
+    })
+}
+
+/// Builds the report and writes it as pretty JSON to `opts.path`.
+pub fn write_report(
+    io: &mut dyn RimIO,
+    layout: &Layout,
+    opts: &ReportOptions,
+) -> anyhow::Result<()> {
+    let report = build_report(io, layout, opts)?;
+    write_json(&opts.path, &report)
+}
+
+fn write_json(path: &Path, report: &BuildReport) -> anyhow::Result<()> {
+    let mut json = serde_json::to_string_pretty(report)?;
+    json.push('\n');
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+fn sha256_region(io: &mut dyn RimIO, offset: u64, len: u64) -> RimIOResult<String> {
+    let mut buf = vec![0u8; rimio::BLOCK_BUF_SIZE];
+    let mut hasher = Sha256::new();
+    let mut done = 0;
+    while done < len {
+        let n = (len - done).min(buf.len() as u64) as usize;
+        io.read_at(offset + done, &mut buf[..n])?;
+        hasher.update(&buf[..n]);
+        done += n as u64;
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::out::{img, target::DryRunMode};
+
+    #[test]
+    fn reported_crc_matches_image_bytes() {
+        let toml = r#"
+            [[partitions]]
+            name = "esp"
+            size = "32M"
+            fs = "fat32"
+
+            [[partitions]]
+            name = "data"
+            size = "16M"
+            fs = "ext4"
+        "#;
+        let mut layout: Layout = toml::from_str(toml).unwrap();
+        layout.assign_guids();
+        layout.validate().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("out.img");
+        let opts = ReportOptions {
+            path: dir.path().join("out.json"),
+            sha256: true,
+        };
+        img::create(
+            &layout,
+            &image,
+            &false,
+            &false,
+            DryRunMode::Off,
+            Some(&opts),
+        )
+        .unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&opts.path).unwrap()).unwrap();
+        let bytes = std::fs::read(&image).unwrap();
+        let parts = json["partitions"].as_array().unwrap();
+        assert_eq!(parts.len(), 2);
+        for (p, name) in parts.iter().zip(["esp", "data"]) {
+            assert_eq!(p["name"], name);
+            let start = p["start_lba"].as_u64().unwrap() * SECTOR_SIZE;
+            let end = (p["end_lba"].as_u64().unwrap() + 1) * SECTOR_SIZE;
+            let range = &bytes[start as usize..end as usize];
+
+            let crc = crc32fast::hash(range);
+            assert_eq!(p["crc32"], format!("0x{crc:08X}"));
+            let sha: String = Sha256::digest(range)
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect();
+            assert_eq!(p["sha256"], sha);
+        }
+        assert_eq!(json["partitions"][1]["fs"], "ext4");
+    }
+}
@@ -20,6 +20,8 @@ pub struct TargetImage {
     _tmp: Option<tempfile::NamedTempFile>,
     /// Real path if "Off", otherwise tempfile path (useful for reopening).
     pub path: std::path::PathBuf,
+    /// Writing straight to a disk rather than an image file.
+    pub device: bool,
 
     pub mode: DryRunMode,
 }
@@ -33,6 +35,7 @@ impl TargetImage {
                     file: None,
                     _tmp: None,
                     path: PathBuf::new(),
+                    device: false,
                     mode,
                 })
             }
@@ -46,6 +49,26 @@ impl TargetImage {
                     file: Some(f),
                     _tmp: Some(tmp),
                     path,
+                    device: false,
+                    mode,
+                })
+            }
+            DryRunMode::Off if is_device(output) => {
+                let mut f = StdRimIO::open_device(output)?;
+                let len = StdRimIO::new(&mut f).with_device_length(true).len()?;
+                if len < total_bytes {
+                    anyhow::bail!(
+                        "{} is too small: {} bytes, image needs {}",
+                        output.display(),
+                        len,
+                        total_bytes
+                    );
+                }
+                Ok(Self {
+                    file: Some(f),
+                    _tmp: None,
+                    path: output.to_path_buf(),
+                    device: true,
                     mode,
                 })
             }
@@ -61,6 +84,7 @@ impl TargetImage {
                     file: Some(f),
                     _tmp: None,
                     path: output.to_path_buf(),
+                    device: false,
                     mode,
                 })
             }
@@ -71,6 +95,85 @@ impl TargetImage {
         let file = self.file.as_mut().ok_or_else(|| {
             anyhow::anyhow!("No file backing in this mode (Plan). Use Tempfile or Off.")
         })?;
-        Ok(StdRimIO::new(file))
+        // Disks can't grow or punch holes; their size comes from the OS.
+        Ok(StdRimIO::new(file)
+            .with_hole_punch(!self.device)
+            .with_device_length(self.device))
+    }
+}
+
+/// Whether `path` names a disk (`/dev/sdb`, `\\.\PhysicalDrive1`) rather
+/// than an image file.
+pub fn is_device(path: &Path) -> bool {
+    if path.to_str().is_some_and(is_windows_device_path) {
+        return true;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+
+        std::fs::metadata(path).is_ok_and(|m| {
+            let kind = m.file_type();
+            kind.is_block_device() || kind.is_char_device()
+        })
+    }
+    #[cfg(not(unix))]
+    false
+}
+
+/// Refuses to write to a disk that isn't known to be removable, unless `force`.
+///
+/// Guards against wiping an internal drive by mistake. macOS has no
+/// removable-media query (see [`StdRimIO::is_removable`]), so disks there
+/// always need `--force`.
+pub fn ensure_removable(path: &Path, force: bool) -> anyhow::Result<()> {
+    let mut file = std::fs::File::open(path)?;
+    check_removable(path, StdRimIO::new(&mut file).is_removable(), force)
+}
+
+fn check_removable(path: &Path, removable: Option<bool>, force: bool) -> anyhow::Result<()> {
+    match removable {
+        Some(true) => Ok(()),
+        _ if force => {
+            crate::log_info!("--force: writing to {} anyway", path.display());
+            Ok(())
+        }
+        Some(false) => anyhow::bail!(
+            "{} is a fixed disk; pass --force to overwrite it",
+            path.display()
+        ),
+        None => anyhow::bail!(
+            "can't tell whether {} is removable; pass --force to overwrite it",
+            path.display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_paths_are_detected() {
+        assert!(is_device(Path::new(r"\\.\PhysicalDrive1")));
+        assert!(!is_device(Path::new("output.img")));
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        assert!(!is_device(tmp.path()));
+        #[cfg(unix)]
+        assert!(is_device(Path::new("/dev/null")));
+    }
+
+    #[test]
+    fn fixed_disks_need_force() {
+        let disk = Path::new("/dev/nvme0n1");
+        assert!(check_removable(disk, Some(true), false).is_ok());
+
+        let err = check_removable(disk, Some(false), false).unwrap_err();
+        assert!(err.to_string().contains("fixed disk"), "{err}");
+        let err = check_removable(disk, None, false).unwrap_err();
+        assert!(err.to_string().contains("--force"), "{err}");
+
+        assert!(check_removable(disk, Some(false), true).is_ok());
+        assert!(check_removable(disk, None, true).is_ok());
     }
 }
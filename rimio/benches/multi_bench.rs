@@ -0,0 +1,46 @@
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use rimio::prelude::*;
+
+const CLUSTER: usize = 4096;
+const CLUSTERS: usize = 1024;
+
+/// Offsets made of 16-cluster contiguous runs separated by one-cluster holes.
+fn fragmented_offsets() -> Vec<u64> {
+    (0..CLUSTERS)
+        .map(|i| ((i + i / 16) * CLUSTER) as u64)
+        .collect()
+}
+
+fn bench_std_multi_read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("std_multi_read");
+    group.throughput(Throughput::Bytes((CLUSTERS * CLUSTER) as u64));
+
+    let mut file = tempfile::tempfile().unwrap();
+    file.set_len((2 * CLUSTERS * CLUSTER) as u64).unwrap();
+    let mut io = StdRimIO::new(&mut file);
+    let mut buf = vec![0u8; CLUSTERS * CLUSTER];
+
+    for (label, offsets) in [
+        (
+            "contiguous",
+            (0..CLUSTERS).map(|i| (i * CLUSTER) as u64).collect(),
+        ),
+        ("fragmented", fragmented_offsets()),
+    ] {
+        group.bench_function(format!("read_multi_at_{label}"), |b| {
+            b.iter(|| io.read_multi_at(&offsets, CLUSTER, &mut buf).unwrap());
+        });
+        group.bench_function(format!("read_at_loop_{label}"), |b| {
+            b.iter(|| {
+                for (off, chunk) in offsets.iter().zip(buf.chunks_mut(CLUSTER)) {
+                    io.read_at(*off, chunk).unwrap();
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_std_multi_read);
+criterion_main!(benches);
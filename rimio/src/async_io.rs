@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: MIT
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use crate::{RimIO, RimIOError, RimIOResult};
+
+/// Non-blocking counterpart of [`RimIO`], for async runtimes.
+///
+/// Mirrors the sync trait method for method; offsets have the same meaning.
+pub trait AsyncRimIO {
+    /// Writes `data` at `offset` (absolute).
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> impl Future<Output = RimIOResult> + Send;
+
+    /// Reads `buf.len()` bytes into `buf` from `offset` (absolute).
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> impl Future<Output = RimIOResult> + Send;
+
+    /// Flushes any buffered data (may be a no-op).
+    fn flush(&mut self) -> impl Future<Output = RimIOResult> + Send;
+}
+
+/// Bridges a sync `RimIO` to [`AsyncRimIO`] by running each call on tokio's
+/// blocking pool (`spawn_blocking`), so file-backed IO never stalls the runtime.
+///
+/// Data is copied through an owned buffer, since blocking tasks must be `'static`.
+pub struct BlockingRimIO<IO: RimIO + Send + 'static> {
+    inner: Arc<Mutex<IO>>,
+}
+
+impl<IO: RimIO + Send + 'static> BlockingRimIO<IO> {
+    #[inline]
+    pub fn new(inner: IO) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    /// Returns the inner IO, or `None` while a blocking call still holds it.
+    pub fn into_inner(self) -> Option<IO> {
+        Arc::try_unwrap(self.inner)
+            .ok()
+            .and_then(|m| m.into_inner().ok())
+    }
+
+    async fn run<T, F>(&self, f: F) -> RimIOResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut IO) -> RimIOResult<T> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || {
+            let mut io = inner
+                .lock()
+                .map_err(|_| RimIOError::Other("BlockingRimIO: poisoned lock"))?;
+            f(&mut io)
+        })
+        .await
+        .map_err(|_| RimIOError::Other("BlockingRimIO: blocking task failed"))?
+    }
+}
+
+impl<IO: RimIO + Send + 'static> AsyncRimIO for BlockingRimIO<IO> {
+    async fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
+        let data = data.to_vec();
+        self.run(move |io| io.write_at(offset, &data)).await
+    }
+
+    async fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
+        let len = buf.len();
+        let data = self
+            .run(move |io| {
+                let mut data = vec![0u8; len];
+                io.read_at(offset, &mut data)?;
+                Ok(data)
+            })
+            .await?;
+        buf.copy_from_slice(&data);
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> RimIOResult {
+        self.run(|io| io.flush()).await
+    }
+}
+
+#[cfg(all(test, feature = "mem"))]
+mod test {
+    use crate::prelude::*;
+
+    #[tokio::test]
+    async fn test_blocking_adapter_rw() {
+        let mut io = BlockingRimIO::new(MemRimIO::new_growable());
+
+        io.write_at(100, b"async bytes").await.unwrap();
+        io.flush().await.unwrap();
+
+        let mut out = [0u8; 11];
+        io.read_at(100, &mut out).await.unwrap();
+        assert_eq!(&out, b"async bytes");
+        // Growable memory reads zeros past its end.
+        io.read_at(1 << 20, &mut out).await.unwrap();
+        assert_eq!(out, [0u8; 11]);
+
+        let mut mem = io.into_inner().unwrap();
+        let mut first = [0u8; 5];
+        mem.read_at(100, &mut first).unwrap();
+        assert_eq!(&first, b"async");
+    }
+}
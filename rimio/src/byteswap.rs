@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT
+
+//! Test-only adapter storing data byte-reversed inside each `W`-byte word.
+//!
+//! Anything that round-trips through it must go through the explicit-endian
+//! helpers; code that peeks at raw host-endian bytes behind the adapter's back
+//! (or relies on the backing layout) will see mirrored words and fail.
+
+use crate::{RimIO, RimIOResult};
+
+pub(crate) struct ByteSwapRimIO<'a, IO: RimIO + ?Sized, const W: usize = 4> {
+    inner: &'a mut IO,
+}
+
+impl<'a, IO: RimIO + ?Sized, const W: usize> ByteSwapRimIO<'a, IO, W> {
+    pub(crate) fn new(inner: &'a mut IO) -> Self {
+        Self { inner }
+    }
+
+    /// Mirrors `pos` inside its `W`-byte word.
+    #[inline]
+    pub(crate) fn mirror(pos: u64) -> u64 {
+        let w = W as u64;
+        (pos / w) * w + (w - 1 - pos % w)
+    }
+}
+
+impl<'a, IO: RimIO + ?Sized, const W: usize> RimIO for ByteSwapRimIO<'a, IO, W> {
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
+        let mut word = [0u8; W];
+        for (i, &b) in data.iter().enumerate() {
+            let pos = offset + i as u64;
+            let base = pos - pos % W as u64;
+            self.inner.read_at(base, &mut word)?;
+            word[(Self::mirror(pos) - base) as usize] = b;
+            self.inner.write_at(base, &word)?;
+        }
+        Ok(())
+    }
+
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
+        let mut word = [0u8; W];
+        for (i, b) in buf.iter_mut().enumerate() {
+            let pos = offset + i as u64;
+            let base = pos - pos % W as u64;
+            self.inner.read_at(base, &mut word)?;
+            *b = word[(Self::mirror(pos) - base) as usize];
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> RimIOResult {
+        self.inner.flush()
+    }
+
+    fn set_offset(&mut self, partition_offset: u64) -> u64 {
+        self.inner.set_offset(partition_offset)
+    }
+
+    fn partition_offset(&self) -> u64 {
+        self.inner.partition_offset()
+    }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        self.inner.flush_persists()
+    }
+
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        self.inner.len()
+    }
+}
+
+#[cfg(feature = "mem")]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+    use alloc::{vec, vec::Vec};
+    use zerocopy::byteorder::{LittleEndian, U32};
+
+    /// Undo the per-word mirroring of a raw backing buffer.
+    fn unswap<const W: usize>(raw: &[u8]) -> Vec<u8> {
+        let mut out = vec![0u8; raw.len()];
+        for (pos, b) in out.iter_mut().enumerate() {
+            *b = raw[ByteSwapRimIO::<MemRimIO, W>::mirror(pos as u64) as usize];
+        }
+        out
+    }
+
+    #[test]
+    fn test_backing_store_is_mirrored() {
+        let mut buf = [0u8; 8];
+        {
+            let mut mem = MemRimIO::new(&mut buf);
+            let mut io = ByteSwapRimIO::<_, 4>::new(&mut mem);
+            io.write_at(0, &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        }
+        assert_eq!(buf, [4, 3, 2, 1, 8, 7, 6, 5]);
+    }
+
+    #[test]
+    fn test_le_helpers_produce_le_bytes() {
+        // Tiny header: u16 tag @0, u32 len @2 (unaligned), u64 id @6, LE struct @14.
+        let mut buf = [0u8; 24];
+        {
+            let mut mem = MemRimIO::new(&mut buf);
+            let mut io = ByteSwapRimIO::<_, 4>::new(&mut mem);
+            io.write_u16_at(0, 0xBEEF).unwrap();
+            io.write_u32_at(2, 0x1122_3344).unwrap();
+            io.write_u64_at(6, 0x0102_0304_0506_0708).unwrap();
+            io.write_struct(14, &U32::<LittleEndian>::new(0xCAFE_BABE))
+                .unwrap();
+
+            assert_eq!(io.read_u16_at(0).unwrap(), 0xBEEF);
+            assert_eq!(io.read_u32_at(2).unwrap(), 0x1122_3344);
+            assert_eq!(io.read_u64_at(6).unwrap(), 0x0102_0304_0506_0708);
+            let v: U32<LittleEndian> = io.read_struct(14).unwrap();
+            assert_eq!(v.get(), 0xCAFE_BABE);
+        }
+
+        let disk = unswap::<4>(&buf);
+        assert_eq!(&disk[0..2], &[0xEF, 0xBE]);
+        assert_eq!(&disk[2..6], &[0x44, 0x33, 0x22, 0x11]);
+        assert_eq!(&disk[6..14], &[8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(&disk[14..18], &[0xBE, 0xBA, 0xFE, 0xCA]);
+    }
+}
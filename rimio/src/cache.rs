@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{BLOCK_BUF_SIZE, RimIO, RimIOError, RimIOResult};
+
+/// Default number of dirty blocks kept before an eviction pass.
+pub const DEFAULT_CACHE_BLOCKS: usize = 1024;
+
+/// One cached block: its bytes and the dirty span `[lo, hi)` inside it.
+struct DirtyBlock {
+    data: Vec<u8>,
+    lo: usize,
+    hi: usize,
+}
+
+/// Write-back caching wrapper.
+///
+/// Buffers writes in aligned blocks of `block_size` bytes and only pushes dirty
+/// blocks to the inner backend on `flush()` or when more than `capacity` blocks
+/// are dirty. Adjacent dirty blocks are coalesced into a single `write_at`.
+/// Reads are served from the backend and patched with the dirty cache.
+///
+/// Blocks are keyed on absolute offsets, so `set_offset` may be used freely
+/// while data is still pending.
+///
+/// Dropping the wrapper writes dirty blocks back on a best-effort basis; call
+/// `flush()` to observe errors and guarantee durability.
+pub struct CachedRimIO<'a, IO: RimIO + ?Sized> {
+    inner: &'a mut IO,
+    block_size: usize,
+    capacity: usize,
+    blocks: BTreeMap<u64, DirtyBlock>,
+}
+
+impl<'a, IO: RimIO + ?Sized> CachedRimIO<'a, IO> {
+    #[inline]
+    pub fn new(inner: &'a mut IO) -> Self {
+        Self::with_block_size(inner, BLOCK_BUF_SIZE)
+    }
+
+    #[inline]
+    pub fn with_block_size(inner: &'a mut IO, block_size: usize) -> Self {
+        Self::with_capacity(inner, block_size, DEFAULT_CACHE_BLOCKS)
+    }
+
+    #[inline]
+    pub fn with_capacity(inner: &'a mut IO, block_size: usize, capacity: usize) -> Self {
+        Self {
+            inner,
+            block_size: block_size.max(1),
+            capacity: capacity.max(1),
+            blocks: BTreeMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Number of blocks currently holding unwritten data.
+    #[inline]
+    pub fn dirty_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Pushes every dirty block to the backend without flushing it.
+    ///
+    /// Contiguous dirty spans are merged into a single write.
+    pub fn write_back(&mut self) -> RimIOResult {
+        if self.blocks.is_empty() {
+            return Ok(());
+        }
+
+        let base = self.inner.partition_offset();
+        self.inner.set_offset(0);
+        let res = self.write_back_abs();
+        self.inner.set_offset(base);
+        res?;
+
+        self.blocks.clear();
+        Ok(())
+    }
+
+    fn write_back_abs(&mut self) -> RimIOResult {
+        let bs = self.block_size as u64;
+        let mut run: Vec<u8> = Vec::new();
+        let mut run_start = 0u64;
+
+        for (&idx, blk) in &self.blocks {
+            let start = idx * bs + blk.lo as u64;
+            if !run.is_empty() && run_start + run.len() as u64 != start {
+                self.inner.write_at(run_start, &run)?;
+                run.clear();
+            }
+            if run.is_empty() {
+                run_start = start;
+            }
+            run.extend_from_slice(&blk.data[blk.lo..blk.hi]);
+        }
+
+        if !run.is_empty() {
+            self.inner.write_at(run_start, &run)?;
+        }
+        Ok(())
+    }
+
+    /// Reads `buf.len()` bytes at absolute offset `abs` from the backend.
+    fn read_inner_abs(&mut self, abs: u64, buf: &mut [u8]) -> RimIOResult {
+        let base = self.inner.partition_offset();
+        let rel = abs
+            .checked_sub(base)
+            .ok_or(RimIOError::Invalid("Cache block below partition offset"))?;
+        self.inner.read_at(rel, buf)
+    }
+
+    fn cache_write(&mut self, abs: u64, data: &[u8]) -> RimIOResult {
+        let bs = self.block_size as u64;
+        let mut pos = 0usize;
+
+        while pos < data.len() {
+            let off = abs + pos as u64;
+            let idx = off / bs;
+            let in_blk = (off % bs) as usize;
+            let n = (self.block_size - in_blk).min(data.len() - pos);
+            let (a, b) = (in_blk, in_blk + n);
+
+            if !self.blocks.contains_key(&idx) {
+                let blk = DirtyBlock {
+                    data: alloc::vec![0u8; self.block_size],
+                    lo: a,
+                    hi: b,
+                };
+                self.blocks.insert(idx, blk);
+            } else {
+                // Extending a disjoint span: fill the gap with backend bytes first.
+                let (lo, hi) = {
+                    let blk = &self.blocks[&idx];
+                    (blk.lo, blk.hi)
+                };
+                let blk_abs = idx * bs;
+                if a > hi {
+                    let mut gap = alloc::vec![0u8; a - hi];
+                    self.read_inner_abs(blk_abs + hi as u64, &mut gap)?;
+                    self.blocks.get_mut(&idx).unwrap().data[hi..a].copy_from_slice(&gap);
+                }
+                if b < lo {
+                    let mut gap = alloc::vec![0u8; lo - b];
+                    self.read_inner_abs(blk_abs + b as u64, &mut gap)?;
+                    self.blocks.get_mut(&idx).unwrap().data[b..lo].copy_from_slice(&gap);
+                }
+                let blk = self.blocks.get_mut(&idx).unwrap();
+                blk.lo = blk.lo.min(a);
+                blk.hi = blk.hi.max(b);
+            }
+
+            let blk = self.blocks.get_mut(&idx).unwrap();
+            blk.data[a..b].copy_from_slice(&data[pos..pos + n]);
+            pos += n;
+        }
+
+        if self.blocks.len() > self.capacity {
+            self.write_back()?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, IO: RimIO + ?Sized> RimIO for CachedRimIO<'a, IO> {
+    #[inline]
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let abs = self
+            .inner
+            .partition_offset()
+            .checked_add(offset)
+            .ok_or(RimIOError::Invalid("Offset overflow"))?;
+        self.cache_write(abs, data)
+    }
+
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
+        self.inner.read_at(offset, buf)?;
+        if self.blocks.is_empty() || buf.is_empty() {
+            return Ok(());
+        }
+
+        // Overlay dirty spans intersecting [abs, abs + len).
+        let bs = self.block_size as u64;
+        let abs = self.inner.partition_offset() + offset;
+        let end = abs + buf.len() as u64;
+        for (&idx, blk) in self.blocks.range(abs / bs..=(end - 1) / bs) {
+            let d_start = (idx * bs + blk.lo as u64).max(abs);
+            let d_end = (idx * bs + blk.hi as u64).min(end);
+            if d_start >= d_end {
+                continue;
+            }
+            let src = (d_start - idx * bs) as usize;
+            let dst = (d_start - abs) as usize;
+            let n = (d_end - d_start) as usize;
+            buf[dst..dst + n].copy_from_slice(&blk.data[src..src + n]);
+        }
+        Ok(())
+    }
+
+    /// Writes back every dirty block, then flushes the backend.
+    #[inline]
+    fn flush(&mut self) -> RimIOResult {
+        self.write_back()?;
+        self.inner.flush()
+    }
+
+    #[inline]
+    fn set_offset(&mut self, partition_offset: u64) -> u64 {
+        self.inner.set_offset(partition_offset)
+    }
+
+    #[inline]
+    fn partition_offset(&self) -> u64 {
+        self.inner.partition_offset()
+    }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        self.inner.flush_persists()
+    }
+
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        self.inner.len()
+    }
+}
+
+impl<'a, IO: RimIO + ?Sized> Drop for CachedRimIO<'a, IO> {
+    fn drop(&mut self) {
+        let _ = self.write_back();
+    }
+}
+
+#[cfg(all(test, feature = "mem"))]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_write_back_on_flush() {
+        let mut buf = [0u8; 64];
+        {
+            let mut mem = MemRimIO::new(&mut buf);
+            let mut io = CachedRimIO::with_block_size(&mut mem, 16);
+            io.write_at(4, &[1, 2, 3, 4]).unwrap();
+            assert_eq!(io.dirty_blocks(), 1);
+            io.flush().unwrap();
+            assert_eq!(io.dirty_blocks(), 0);
+        }
+        assert_eq!(&buf[4..8], &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_reads_see_dirty_data() {
+        let mut buf = [0xAAu8; 64];
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut io = CachedRimIO::with_block_size(&mut mem, 16);
+
+        io.write_at(14, &[1, 2, 3, 4]).unwrap();
+        io.write_at(20, &[9]).unwrap();
+
+        let mut out = [0u8; 10];
+        io.read_at(12, &mut out).unwrap();
+        assert_eq!(out, [0xAA, 0xAA, 1, 2, 3, 4, 0xAA, 0xAA, 9, 0xAA]);
+    }
+
+    #[test]
+    fn test_coalesces_adjacent_writes() {
+        let mut buf = [0u8; 256];
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut counter = IOCounter::new(&mut mem);
+        {
+            let mut io = CachedRimIO::with_block_size(&mut counter, 16);
+            for i in 0..64u64 {
+                io.write_at(i * 4, &(i as u32).to_le_bytes()).unwrap();
+            }
+            io.flush().unwrap();
+        }
+        assert_eq!(counter.stats.writes, 1);
+        assert_eq!(counter.stats.write_bytes, 256);
+
+        let mut out = [0u8; 4];
+        counter.read_at(63 * 4, &mut out).unwrap();
+        assert_eq!(u32::from_le_bytes(out), 63);
+    }
+
+    #[test]
+    fn test_disjoint_spans_keep_backend_bytes() {
+        let mut buf = [0xEEu8; 32];
+        {
+            let mut mem = MemRimIO::new(&mut buf);
+            let mut io = CachedRimIO::with_block_size(&mut mem, 16);
+            io.write_at(2, &[1]).unwrap();
+            io.write_at(10, &[2]).unwrap();
+            io.flush().unwrap();
+        }
+        assert_eq!(buf[2], 1);
+        assert_eq!(buf[10], 2);
+        assert!(buf[3..10].iter().all(|&b| b == 0xEE));
+    }
+
+    #[test]
+    fn test_eviction_and_partition_offset() {
+        let mut buf = [0u8; 128];
+        {
+            let mut mem = MemRimIO::new_with_offset(&mut buf, 64);
+            let mut io = CachedRimIO::with_capacity(&mut mem, 8, 2);
+            io.write_at(0, &[1; 8]).unwrap();
+            io.write_at(16, &[2; 8]).unwrap();
+            io.write_at(32, &[3; 8]).unwrap();
+            // Third dirty block triggers an eviction pass.
+            assert_eq!(io.dirty_blocks(), 0);
+            io.set_offset(0);
+            io.write_at(0, &[4; 8]).unwrap();
+        }
+        assert_eq!(&buf[64..72], &[1; 8]);
+        assert_eq!(&buf[80..88], &[2; 8]);
+        assert_eq!(&buf[96..104], &[3; 8]);
+        assert_eq!(&buf[0..8], &[4; 8]);
+    }
+}
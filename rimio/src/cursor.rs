@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: MIT
+
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom, Write};
+
+use crate::{RimIO, RimIOError};
+
+/// `std::io::{Read, Write, Seek}` adapter over a `RimIO`.
+///
+/// Tracks a position and delegates to `read_at`/`write_at`, so a partition
+/// can be handed to libraries expecting `std::io` (tar/zip readers, hashers…).
+/// `len` bounds reads and anchors `SeekFrom::End`; writes past it extend it.
+pub struct RimIOCursor<'a, IO: RimIO + ?Sized> {
+    inner: &'a mut IO,
+    pos: u64,
+    len: u64,
+}
+
+impl<'a, IO: RimIO + ?Sized> RimIOCursor<'a, IO> {
+    #[inline]
+    pub fn new(inner: &'a mut IO, len: u64) -> Self {
+        Self { inner, pos: 0, len }
+    }
+
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> &'a mut IO {
+        self.inner
+    }
+}
+
+fn to_io_error(e: RimIOError) -> io::Error {
+    let kind = match e {
+        RimIOError::UnexpectedEof { .. } => ErrorKind::UnexpectedEof,
+        RimIOError::Invalid(_) => ErrorKind::InvalidInput,
+        RimIOError::Unsupported => ErrorKind::Unsupported,
+        RimIOError::ReadOnly => ErrorKind::ReadOnlyFilesystem,
+        _ => ErrorKind::Other,
+    };
+    io::Error::new(kind, e)
+}
+
+impl<'a, IO: RimIO + ?Sized> Read for RimIOCursor<'a, IO> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let avail = self.len.saturating_sub(self.pos);
+        let n = (buf.len() as u64).min(avail) as usize;
+        if n == 0 {
+            return Ok(0);
+        }
+        self.inner
+            .read_at(self.pos, &mut buf[..n])
+            .map_err(to_io_error)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<'a, IO: RimIO + ?Sized> Write for RimIOCursor<'a, IO> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write_at(self.pos, buf).map_err(to_io_error)?;
+        self.pos += buf.len() as u64;
+        self.len = self.len.max(self.pos);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().map_err(to_io_error)
+    }
+}
+
+impl<'a, IO: RimIO + ?Sized> Seek for RimIOCursor<'a, IO> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, delta) = match pos {
+            SeekFrom::Start(p) => {
+                self.pos = p;
+                return Ok(p);
+            }
+            SeekFrom::Current(d) => (self.pos, d),
+            SeekFrom::End(d) => (self.len, d),
+        };
+        self.pos = base.checked_add_signed(delta).ok_or_else(|| {
+            io::Error::new(ErrorKind::InvalidInput, "seek to a negative position")
+        })?;
+        Ok(self.pos)
+    }
+}
+
+#[cfg(all(test, feature = "mem"))]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_read_to_end() {
+        let mut buf: Vec<u8> = (0..200u8).collect();
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut view = PartitionRimIO::new(&mut mem, 50, 100);
+        let mut cur = RimIOCursor::new(&mut view, 100);
+
+        let mut out = Vec::new();
+        cur.read_to_end(&mut out).unwrap();
+        assert_eq!(out, (50..150u8).collect::<Vec<_>>());
+        assert_eq!(cur.read(&mut [0u8; 4]).unwrap(), 0);
+
+        cur.seek(SeekFrom::End(-10)).unwrap();
+        let mut out = Vec::new();
+        cur.read_to_end(&mut out).unwrap();
+        assert_eq!(out, (140..150u8).collect::<Vec<_>>());
+
+        assert!(cur.seek(SeekFrom::Current(-200)).is_err());
+    }
+
+    #[test]
+    fn test_write_seek() {
+        let mut buf = [0u8; 64];
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut cur = RimIOCursor::new(&mut mem, 0);
+
+        cur.write_all(b"hello").unwrap();
+        cur.seek(SeekFrom::Start(1)).unwrap();
+        cur.write_all(b"E").unwrap();
+        assert_eq!(cur.len(), 5);
+
+        cur.seek(SeekFrom::Start(0)).unwrap();
+        let mut s = String::new();
+        cur.read_to_string(&mut s).unwrap();
+        assert_eq!(s, "hEllo");
+    }
+}
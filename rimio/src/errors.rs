@@ -10,8 +10,26 @@ pub type RimIOResult<T = ()> = core::result::Result<T, RimIOError>;
 pub enum RimIOError {
     Other(&'static str),
     Invalid(&'static str),
-    OutOfBounds,
+    /// Access to `[offset, offset + len)` reaches past the `size` bytes the backend exposes.
+    OutOfBounds {
+        offset: u64,
+        len: usize,
+        size: u64,
+    },
     Unsupported,
+    /// Write attempted through a read-only IO.
+    ReadOnly,
+    /// A streamed chunk of `bytes` does not fit the `max`-byte internal buffer.
+    ChunkTooLarge {
+        bytes: usize,
+        max: usize,
+    },
+    /// The backend ended before `requested` bytes could be transferred at `offset`.
+    UnexpectedEof {
+        offset: u64,
+        requested: usize,
+        got: usize,
+    },
 }
 
 impl RimIOError {
@@ -19,8 +37,11 @@ impl RimIOError {
         match self {
             RimIOError::Other(msg) => msg,
             RimIOError::Invalid(msg) => msg,
-            RimIOError::OutOfBounds => "Out of bounds",
+            RimIOError::OutOfBounds { .. } => "Out of bounds",
             RimIOError::Unsupported => "Unsupported operation",
+            RimIOError::ReadOnly => "Write to read-only IO",
+            RimIOError::ChunkTooLarge { .. } => "Chunk too large for the internal buffer",
+            RimIOError::UnexpectedEof { .. } => "Unexpected end of data",
         }
     }
 }
@@ -35,7 +56,21 @@ impl From<&'static str> for RimIOError {
 impl fmt::Display for RimIOError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.msg())?;
-        Ok(())
+        match self {
+            RimIOError::UnexpectedEof {
+                offset,
+                requested,
+                got,
+            } => write!(
+                f,
+                " at offset {offset} (requested {requested} bytes, got {got})"
+            ),
+            RimIOError::OutOfBounds { offset, len, size } => {
+                write!(f, " at offset {offset} (len {len}, size {size})")
+            }
+            RimIOError::ChunkTooLarge { bytes, max } => write!(f, " ({bytes} > {max} bytes)"),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MIT
+
+use core::ops::Range;
+
+use crate::{RimIO, RimIOError, RimIOResult};
+
+/// Wrapper that injects I/O failures over chosen byte ranges.
+///
+/// Any read (or write) whose span overlaps a faulty range fails with
+/// `RimIOError::Other`, everything else is forwarded to the inner IO.
+/// Writes can also be dropped silently, to mimic a device that loses them.
+/// Ranges are expressed in the same offsets the caller uses. Meant to
+/// exercise bad-sector handling in checkers and tools.
+pub struct FaultyRimIO<'a, IO: RimIO + ?Sized> {
+    inner: &'a mut IO,
+    bad_reads: Option<Range<u64>>,
+    bad_writes: Option<Range<u64>>,
+    dropped_writes: Option<Range<u64>>,
+}
+
+impl<'a, IO: RimIO + ?Sized> FaultyRimIO<'a, IO> {
+    #[inline]
+    pub fn new(inner: &'a mut IO) -> Self {
+        Self {
+            inner,
+            bad_reads: None,
+            bad_writes: None,
+            dropped_writes: None,
+        }
+    }
+
+    /// Makes reads overlapping `range` fail.
+    #[inline]
+    pub fn fail_reads(mut self, range: Range<u64>) -> Self {
+        self.bad_reads = Some(range);
+        self
+    }
+
+    /// Makes writes overlapping `range` fail.
+    #[inline]
+    pub fn fail_writes(mut self, range: Range<u64>) -> Self {
+        self.bad_writes = Some(range);
+        self
+    }
+
+    /// Makes writes overlapping `range` report success without reaching the inner IO.
+    #[inline]
+    pub fn drop_writes(mut self, range: Range<u64>) -> Self {
+        self.dropped_writes = Some(range);
+        self
+    }
+
+    #[inline]
+    pub fn inner(&mut self) -> &mut IO {
+        self.inner
+    }
+
+    #[inline]
+    fn hits(range: &Option<Range<u64>>, offset: u64, len: usize) -> bool {
+        match range {
+            Some(r) => len > 0 && offset < r.end && offset.saturating_add(len as u64) > r.start,
+            None => false,
+        }
+    }
+}
+
+impl<'a, IO: RimIO + ?Sized> RimIO for FaultyRimIO<'a, IO> {
+    #[inline]
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
+        if Self::hits(&self.bad_writes, offset, data.len()) {
+            return Err(RimIOError::Other("Injected write fault"));
+        }
+        if Self::hits(&self.dropped_writes, offset, data.len()) {
+            return Ok(());
+        }
+        self.inner.write_at(offset, data)
+    }
+
+    #[inline]
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
+        if Self::hits(&self.bad_reads, offset, buf.len()) {
+            return Err(RimIOError::Other("Injected read fault"));
+        }
+        self.inner.read_at(offset, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> RimIOResult {
+        self.inner.flush()
+    }
+
+    #[inline]
+    fn set_offset(&mut self, partition_offset: u64) -> u64 {
+        self.inner.set_offset(partition_offset)
+    }
+
+    #[inline]
+    fn partition_offset(&self) -> u64 {
+        self.inner.partition_offset()
+    }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        self.inner.flush_persists()
+    }
+
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        self.inner.len()
+    }
+}
+
+#[cfg(all(test, feature = "mem"))]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_faulty_ranges() {
+        let mut buf = [0u8; 64];
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut io = FaultyRimIO::new(&mut mem).fail_reads(16..32);
+
+        let mut out = [0u8; 8];
+        io.read_at(0, &mut out).unwrap();
+        io.read_at(32, &mut out).unwrap();
+        assert!(io.read_at(12, &mut out).is_err());
+        assert!(io.read_at(31, &mut out[..1]).is_err());
+        io.write_at(16, &[1u8; 16]).unwrap();
+    }
+
+    #[test]
+    fn test_dropped_writes() {
+        let mut buf = [0u8; 64];
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut io = FaultyRimIO::new(&mut mem).drop_writes(16..32);
+
+        io.write_at(0, &[1u8; 8]).unwrap();
+        io.write_at(24, &[2u8; 16]).unwrap();
+        let mut out = [0u8; 64];
+        io.read_at(0, &mut out).unwrap();
+        assert_eq!(&out[..8], &[1u8; 8]);
+        assert!(out[8..].iter().all(|&b| b == 0));
+    }
+}
@@ -8,9 +8,23 @@ extern crate alloc;
 use alloc::vec;
 
 // Core modules
+#[cfg(feature = "async")]
+mod async_io;
+#[cfg(all(test, feature = "mem", feature = "alloc"))]
+mod byteswap;
+#[cfg(feature = "alloc")]
+mod cache;
+#[cfg(feature = "std")]
+mod cursor;
 pub mod errors;
+mod faulty;
 mod macros;
+mod partition;
+mod readonly;
 pub mod stats;
+mod tee;
+#[cfg(feature = "std")]
+mod throttled;
 pub mod utils;
 
 // Backend modules
@@ -31,13 +45,29 @@ pub mod prelude {
     pub use super::RimIOStreamExt;
     pub use super::RimIOStructExt;
     pub use super::errors::*;
+    pub use super::faulty::FaultyRimIO;
+    pub use super::partition::PartitionRimIO;
+    pub use super::readonly::ReadOnlyRimIO;
     pub use super::stats::*;
+    pub use super::tee::TeeRimIO;
+
+    #[cfg(feature = "async")]
+    pub use super::async_io::{AsyncRimIO, BlockingRimIO};
+
+    #[cfg(feature = "alloc")]
+    pub use super::cache::CachedRimIO;
+
+    #[cfg(feature = "std")]
+    pub use super::cursor::RimIOCursor;
 
     #[cfg(feature = "mem")]
     pub use super::mem::MemRimIO;
 
     #[cfg(feature = "std")]
-    pub use super::std::StdRimIO;
+    pub use super::std::{StdRimIO, is_windows_device_path, physical_drive_number};
+
+    #[cfg(feature = "std")]
+    pub use super::throttled::ThrottledRimIO;
 
     #[cfg(feature = "uefi")]
     pub use super::uefi::UefiRimIO;
@@ -53,12 +83,16 @@ use errors::*;
 /// Safe for no_std/UEFI stack usage, overridable in high-level code.
 pub const BLOCK_BUF_SIZE: usize = 4096;
 
+/// Smallest `zero_fill` length offered to [`RimIO::try_zero_range`] (64 KiB).
+pub const ZERO_RANGE_MIN: usize = 64 * 1024;
+
 // Traits
 
 /// Block IO abstraction trait.
 ///
 /// Allows read/write/flush at arbitrary offsets.
 /// Implementations may target RAM, files, block devices, UEFI, BIOS, etc.
+#[allow(clippy::len_without_is_empty)]
 pub trait RimIO {
     /// Writes `data` at `offset` (absolute).
     fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult;
@@ -70,6 +104,31 @@ pub trait RimIO {
     fn set_offset(&mut self, partition_offset: u64) -> u64;
     fn partition_offset(&self) -> u64;
 
+    /// Whether a successful `flush` means the data reached persistent storage.
+    ///
+    /// Backends that only keep data in memory return `false` (the default).
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        false
+    }
+
+    /// Tries to make `len` bytes at `offset` read back as zeroes without writing them
+    /// (hole punching, sparse extension…).
+    ///
+    /// Returns `Ok(false)` when the backend can't, in which case `zero_fill` writes zeroes.
+    #[inline]
+    fn try_zero_range(&mut self, _offset: u64, _len: u64) -> RimIOResult<bool> {
+        Ok(false)
+    }
+
+    /// Total length of the underlying device in bytes, regardless of the partition offset.
+    ///
+    /// Backends that can't tell return `RimIOError::Unsupported` (the default).
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        Err(RimIOError::Unsupported)
+    }
+
     /// Copies data from a source `RimIO` into this one.
     ///
     /// The default implementation uses an intermediate buffer (double-copy).
@@ -197,6 +256,47 @@ pub trait RimIOExt: RimIO {
         }
         Ok(())
     }
+    /// Copies `len` bytes from `src` to `dst` within this device.
+    ///
+    /// Streams through a `BLOCK_BUF_SIZE` scratch buffer. Overlapping ranges are
+    /// handled like `memmove`: when `dst > src` the copy runs backwards.
+    fn copy_region(&mut self, src: u64, dst: u64, len: u64) -> RimIOResult {
+        self.copy_region_with_progress(src, dst, len, &mut |_| {})
+    }
+
+    /// [`copy_region`](RimIOExt::copy_region) reporting progress.
+    ///
+    /// `progress` receives the total number of bytes copied so far after each chunk.
+    fn copy_region_with_progress(
+        &mut self,
+        src: u64,
+        dst: u64,
+        len: u64,
+        progress: &mut dyn FnMut(u64),
+    ) -> RimIOResult {
+        if len == 0 || src == dst {
+            return Ok(());
+        }
+        src.checked_add(len)
+            .and(dst.checked_add(len))
+            .ok_or(RimIOError::Invalid("copy_region: range overflow"))?;
+
+        let mut buf = [0u8; BLOCK_BUF_SIZE];
+        let backwards = dst > src && dst < src + len;
+
+        let mut done = 0u64;
+        while done < len {
+            let n = (len - done).min(BLOCK_BUF_SIZE as u64);
+            let rel = if backwards { len - done - n } else { done };
+            let chunk = &mut buf[..n as usize];
+            self.read_at(src + rel, chunk)?;
+            self.write_at(dst + rel, chunk)?;
+            done += n;
+            progress(done);
+        }
+        Ok(())
+    }
+
     /// Reads a block or range of blocks of `block_size` starting at `offset`.
     ///
     /// If offset and length are aligned to `block_size`, performs a single fast read.
@@ -242,6 +342,9 @@ pub trait RimIOExt: RimIO {
     /// This default implementation attempts to coalesce adjacent reads into larger transactions
     /// to reduce overhead. It typically performs better than individual `read_at` calls.
     ///
+    /// Each contiguous run already maps to a single `read_at`, so on a file backend it costs one
+    /// read syscall, the same as a vectored `preadv` would. Non-contiguous runs cannot be merged.
+    ///
     /// # Errors
     /// Returns `RimIOError::Invalid` if `buf.len()` does not match `offsets.len() * cluster_size`.
     #[inline(always)]
@@ -343,29 +446,225 @@ pub trait RimIOExt: RimIO {
         Ok(())
     }
 
-    /// Fills a region with zeroes.
+    /// Computes the CRC32 (IEEE) of `len` bytes starting at `offset`.
     ///
-    /// Used for quick cluster clearing, FS formatting, VBR/FSInfo clears, etc.
+    /// Reads in `BLOCK_BUF_SIZE` chunks, so the region is never held in memory at once.
+    #[cfg(feature = "crc")]
+    fn crc32_region(&mut self, offset: u64, len: u64) -> RimIOResult<u32> {
+        let mut buf = [0u8; BLOCK_BUF_SIZE];
+        let mut hasher = crc32fast::Hasher::new();
+        let mut off = offset;
+        let mut remaining = len;
+
+        while remaining > 0 {
+            let n = remaining.min(BLOCK_BUF_SIZE as u64) as usize;
+            self.read_at(off, &mut buf[..n])?;
+            hasher.update(&buf[..n]);
+            off += n as u64;
+            remaining -= n as u64;
+        }
+
+        Ok(hasher.finalize())
+    }
+
+    /// Finds the first occurrence of `needle` in `[start, end)` whose offset is a
+    /// multiple of `align` (0 or 1 = any offset), e.g. to locate a superblock magic.
+    ///
+    /// Streams through a `BLOCK_BUF_SIZE` buffer, keeping `needle.len() - 1` bytes
+    /// between refills so matches straddling two reads are still found.
+    ///
+    /// # Errors
+    /// Returns `RimIOError::Invalid` if `needle` is empty or longer than half the buffer.
+    fn find_pattern(
+        &mut self,
+        needle: &[u8],
+        start: u64,
+        end: u64,
+        align: u64,
+    ) -> RimIOResult<Option<u64>> {
+        let n = needle.len();
+        if n == 0 || n > BLOCK_BUF_SIZE / 2 {
+            return Err(RimIOError::Invalid(
+                "find_pattern: unsupported needle length",
+            ));
+        }
+        let align = align.max(1);
+        let mut buf = [0u8; BLOCK_BUF_SIZE];
+        // `buf[..carry]` holds the tail of the previous read, starting at `pos`.
+        let mut pos = start;
+        let mut carry = 0usize;
+
+        while pos + (carry as u64) < end {
+            let want = (BLOCK_BUF_SIZE - carry).min((end - pos - carry as u64) as usize);
+            self.read_at(pos + carry as u64, &mut buf[carry..carry + want])?;
+            let filled = carry + want;
+
+            let mut p = match pos.checked_next_multiple_of(align) {
+                Some(p) => p,
+                None => return Ok(None),
+            };
+            while p + n as u64 <= pos + filled as u64 {
+                let at = (p - pos) as usize;
+                if &buf[at..at + n] == needle {
+                    return Ok(Some(p));
+                }
+                p += align;
+            }
+
+            let keep = (n - 1).min(filled);
+            buf.copy_within(filled - keep..filled, 0);
+            pos += (filled - keep) as u64;
+            carry = keep;
+        }
+
+        Ok(None)
+    }
+
+    /// Reads exactly `buf.len()` bytes at `offset`, or reports how many were available.
+    ///
+    /// # Errors
+    /// Returns `RimIOError::UnexpectedEof` when the backend ends before the
+    /// whole range could be read (`got` is the length of the readable prefix).
+    fn read_exact_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
+        let mut done = 0usize;
+        while done < buf.len() {
+            let n = (buf.len() - done).min(BLOCK_BUF_SIZE);
+            let off = offset + done as u64;
+            if let Err(e) = self.read_at(off, &mut buf[done..done + n]) {
+                let got = done
+                    + readable_prefix(e, n, |len| self.read_at(off, &mut buf[done..done + len]))?;
+                return Err(RimIOError::UnexpectedEof {
+                    offset,
+                    requested: buf.len(),
+                    got,
+                });
+            }
+            done += n;
+        }
+        Ok(())
+    }
+
+    /// Writes all of `data` at `offset`, or reports how many bytes made it.
+    ///
+    /// # Errors
+    /// Returns `RimIOError::UnexpectedEof` when the backend ends before the
+    /// whole range could be written (`got` is the length of the written prefix).
+    fn write_all_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
+        let mut done = 0usize;
+        while done < data.len() {
+            let n = (data.len() - done).min(BLOCK_BUF_SIZE);
+            let off = offset + done as u64;
+            if let Err(e) = self.write_at(off, &data[done..done + n]) {
+                let got = done
+                    + readable_prefix(e, n, |len| self.write_at(off, &data[done..done + len]))?;
+                return Err(RimIOError::UnexpectedEof {
+                    offset,
+                    requested: data.len(),
+                    got,
+                });
+            }
+            done += n;
+        }
+        Ok(())
+    }
+
+    /// Fills a region with `byte`.
+    ///
+    /// Useful for format fillers (0xF6) or 0xFF padding; writes go through a
+    /// `BLOCK_BUF_SIZE` stack buffer.
     #[inline(always)]
-    fn zero_fill(&mut self, offset: u64, len: usize) -> RimIOResult {
-        const ZERO_BUF: [u8; BLOCK_BUF_SIZE] = [0u8; BLOCK_BUF_SIZE];
+    fn fill_region(&mut self, offset: u64, len: usize, byte: u8) -> RimIOResult {
+        self.fill_region_with_progress(offset, len, byte, &mut |_| {})
+    }
+
+    /// [`fill_region`](RimIOExt::fill_region) reporting progress.
+    ///
+    /// `progress` receives the total number of bytes written so far after each chunk.
+    fn fill_region_with_progress(
+        &mut self,
+        offset: u64,
+        len: usize,
+        byte: u8,
+        progress: &mut dyn FnMut(u64),
+    ) -> RimIOResult {
+        let buf = [byte; BLOCK_BUF_SIZE];
         let mut remaining = len;
         let mut off = offset;
         while remaining > 0 {
-            let chunk = remaining.min(ZERO_BUF.len());
-            self.write_at(off, &ZERO_BUF[..chunk])?;
+            let chunk = remaining.min(buf.len());
+            self.write_at(off, &buf[..chunk])?;
             off += chunk as u64;
             remaining -= chunk;
+            progress((len - remaining) as u64);
         }
         Ok(())
     }
 
+    /// Fills a region with zeroes.
+    ///
+    /// Used for quick cluster clearing, FS formatting, VBR/FSInfo clears, etc.
+    /// Large regions are first offered to [`RimIO::try_zero_range`].
+    #[inline(always)]
+    fn zero_fill(&mut self, offset: u64, len: usize) -> RimIOResult {
+        self.zero_fill_with_progress(offset, len, &mut |_| {})
+    }
+
+    /// [`zero_fill`](RimIOExt::zero_fill) reporting progress.
+    ///
+    /// `progress` receives the total number of bytes cleared so far; a range
+    /// handled by [`RimIO::try_zero_range`] reports once with `len`.
+    fn zero_fill_with_progress(
+        &mut self,
+        offset: u64,
+        len: usize,
+        progress: &mut dyn FnMut(u64),
+    ) -> RimIOResult {
+        if len >= ZERO_RANGE_MIN && self.try_zero_range(offset, len as u64)? {
+            progress(len as u64);
+            return Ok(());
+        }
+        self.fill_region_with_progress(offset, len, 0, progress)
+    }
+
     // Implements read/write helpers for primitive types (u16, u32, u64, u128)
     RimIO_impl_primitive_rw!(u16, u32, u64, u128);
+
+    // Big-endian variants (`read_u32_be_at`, …) for foreign on-disk formats
+    RimIO_impl_primitive_rw!(be: u16, u32, u64, u128);
+
+    // Signed variants (`read_i32_at`, …), reinterpreting the unsigned helpers
+    RimIO_impl_primitive_rw!(signed: i16 => u16, i32 => u32, i64 => u64);
 }
 
 impl<T: RimIO + ?Sized> RimIOExt for T {}
 
+/// Length of the prefix of a failed `n`-byte transfer that the backend accepts.
+///
+/// Backends reporting `UnexpectedEof` already know it; for `OutOfBounds` the
+/// prefix is found by bisection through `probe(len)`. Other errors are passed through.
+fn readable_prefix(
+    err: RimIOError,
+    n: usize,
+    mut probe: impl FnMut(usize) -> RimIOResult,
+) -> RimIOResult<usize> {
+    match err {
+        RimIOError::UnexpectedEof { got, .. } => Ok(got),
+        RimIOError::OutOfBounds { .. } => {
+            let (mut lo, mut hi) = (0usize, n);
+            while lo + 1 < hi {
+                let mid = lo + (hi - lo) / 2;
+                if probe(mid).is_ok() {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            Ok(lo)
+        }
+        e => Err(e),
+    }
+}
+
 pub trait RimIOStreamExt: RimIO {
     /// Stream-read N-byte fixed-size elements using a callback function (e.g. for u16, u32, custom entries).
     fn read_chunks_streamed<const N: usize, F>(
@@ -389,6 +688,20 @@ pub trait RimIOStreamExt: RimIO {
     where
         F: FnMut(usize) -> [u8; N];
 
+    /// Same as [`Self::write_chunks_streamed`], calling `progress(written, count)`
+    /// after each chunk reaches the backend (e.g. for progress bars on FAT/bitmap writes).
+    fn write_chunks_streamed_with_progress<const N: usize, F, P>(
+        &mut self,
+        offset: u64,
+        count: usize,
+        chunk: usize,
+        f: F,
+        progress: P,
+    ) -> RimIOResult
+    where
+        F: FnMut(usize) -> [u8; N],
+        P: FnMut(usize, usize);
+
     /// Stream-read fixed-size elements at multiple arbitrary offsets using a callback.
     fn read_multi_streamed<const N: usize, F>(
         &mut self,
@@ -450,14 +763,30 @@ impl<T: RimIO + ?Sized> RimIOStreamExt for T {
 
     #[inline]
     fn write_chunks_streamed<const N: usize, F>(
+        &mut self,
+        offset: u64,
+        count: usize,
+        chunk: usize,
+        f: F,
+    ) -> RimIOResult
+    where
+        F: FnMut(usize) -> [u8; N],
+    {
+        self.write_chunks_streamed_with_progress(offset, count, chunk, f, |_, _| {})
+    }
+
+    #[inline]
+    fn write_chunks_streamed_with_progress<const N: usize, F, P>(
         &mut self,
         offset: u64,
         count: usize,
         chunk: usize,
         mut f: F,
+        mut progress: P,
     ) -> RimIOResult
     where
         F: FnMut(usize) -> [u8; N],
+        P: FnMut(usize, usize),
     {
         let mut buf = vec![0u8; chunk * N];
 
@@ -475,6 +804,7 @@ impl<T: RimIO + ?Sized> RimIOStreamExt for T {
             }
 
             self.write_in_chunks(current_offset, &buf[..bytes_to_write], BLOCK_BUF_SIZE)?;
+            progress(index, count);
 
             current_offset += bytes_to_write as u64;
             remaining -= to_write;
@@ -560,11 +890,7 @@ impl<T: RimIO + ?Sized> RimIOStreamExt for T {
     {
         const BUF_SIZE: usize = BLOCK_BUF_SIZE;
         let mut buf = [0u8; BUF_SIZE];
-        let entries_per_chunk = BUF_SIZE / N;
-        assert!(
-            chunk <= entries_per_chunk,
-            "Chunk too large for the internal buffer."
-        );
+        check_chunk_fits(chunk.saturating_mul(N), BUF_SIZE)?;
 
         let mut remaining = count;
         let mut current_offset = offset;
@@ -591,22 +917,34 @@ impl<T: RimIO + ?Sized> RimIOStreamExt for T {
 
     #[inline]
     fn write_chunks_streamed<const N: usize, F>(
+        &mut self,
+        offset: u64,
+        count: usize,
+        chunk: usize,
+        f: F,
+    ) -> RimIOResult
+    where
+        F: FnMut(usize) -> [u8; N],
+    {
+        self.write_chunks_streamed_with_progress(offset, count, chunk, f, |_, _| {})
+    }
+
+    #[inline]
+    fn write_chunks_streamed_with_progress<const N: usize, F, P>(
         &mut self,
         offset: u64,
         count: usize,
         chunk: usize,
         mut f: F,
+        mut progress: P,
     ) -> RimIOResult
     where
         F: FnMut(usize) -> [u8; N],
+        P: FnMut(usize, usize),
     {
         const BUF_SIZE: usize = BLOCK_BUF_SIZE;
         let mut buf = [0u8; BUF_SIZE];
-        let entries_per_chunk = BUF_SIZE / N;
-        assert!(
-            chunk <= entries_per_chunk,
-            "Chunk too large for the internal buffer."
-        );
+        check_chunk_fits(chunk.saturating_mul(N), BUF_SIZE)?;
 
         let mut remaining = count;
         let mut current_offset = offset;
@@ -622,6 +960,7 @@ impl<T: RimIO + ?Sized> RimIOStreamExt for T {
             }
 
             self.write_in_chunks(current_offset, &buf[..bytes_to_write], BLOCK_BUF_SIZE)?;
+            progress(index, count);
 
             current_offset += bytes_to_write as u64;
             remaining -= to_write;
@@ -631,53 +970,100 @@ impl<T: RimIO + ?Sized> RimIOStreamExt for T {
     }
 
     /// Stream-read fixed-size elements at multiple arbitrary offsets (no-alloc).
+    ///
+    /// Elements are gathered `chunk` at a time into a stack buffer; `chunk * N`
+    /// must fit in `BLOCK_BUF_SIZE`.
     #[inline]
     fn read_multi_streamed<const N: usize, F>(
         &mut self,
         offsets: &[u64],
-        _chunk: usize,
+        chunk: usize,
         mut f: F,
     ) -> RimIOResult
     where
         F: FnMut(usize, &[u8; N]),
     {
-        let mut elem = [0u8; N];
-        assert!(N <= BLOCK_BUF_SIZE, "N too large for internal buffer.",);
+        const BUF_SIZE: usize = BLOCK_BUF_SIZE;
+        let mut buf = [0u8; BUF_SIZE];
+        check_chunk_fits(chunk.saturating_mul(N), BUF_SIZE)?;
+        let chunk = chunk.max(1);
+
+        for (chunk_idx, offset_chunk) in offsets.chunks(chunk).enumerate() {
+            for (i, &off) in offset_chunk.iter().enumerate() {
+                self.read_at(off, &mut buf[i * N..(i + 1) * N])?;
+            }
 
-        for (i, &off) in offsets.iter().enumerate() {
-            self.read_at(off, &mut elem)?;
-            f(i, &elem);
+            for i in 0..offset_chunk.len() {
+                let slice = &buf[i * N..(i + 1) * N];
+                f(chunk_idx * chunk + i, slice.try_into().unwrap());
+            }
         }
         Ok(())
     }
 
     /// Stream-write fixed-size elements at multiple arbitrary offsets (no-alloc).
+    ///
+    /// Elements are generated `chunk` at a time into a stack buffer; `chunk * N`
+    /// must fit in `BLOCK_BUF_SIZE`.
     #[inline]
     fn write_multi_streamed<const N: usize, F>(
         &mut self,
         offsets: &[u64],
-        _chunk: usize,
+        chunk: usize,
         mut f: F,
     ) -> RimIOResult
     where
         F: FnMut(usize) -> [u8; N],
     {
-        assert!(N <= BLOCK_BUF_SIZE, "N too large for internal buffer.",);
+        const BUF_SIZE: usize = BLOCK_BUF_SIZE;
+        let mut buf = [0u8; BUF_SIZE];
+        check_chunk_fits(chunk.saturating_mul(N), BUF_SIZE)?;
+        let chunk = chunk.max(1);
+
+        for (chunk_idx, offset_chunk) in offsets.chunks(chunk).enumerate() {
+            for i in 0..offset_chunk.len() {
+                buf[i * N..(i + 1) * N].copy_from_slice(&f(chunk_idx * chunk + i));
+            }
 
-        for (i, &off) in offsets.iter().enumerate() {
-            let bytes = f(i);
-            self.write_at(off, &bytes)?;
+            for (i, &off) in offset_chunk.iter().enumerate() {
+                self.write_at(off, &buf[i * N..(i + 1) * N])?;
+            }
         }
         Ok(())
     }
 }
 
+/// Rejects a streamed chunk of `bytes` that would not fit a `max`-byte buffer.
+#[cfg(not(feature = "alloc"))]
+#[inline]
+fn check_chunk_fits(bytes: usize, max: usize) -> RimIOResult {
+    if bytes > max {
+        return Err(RimIOError::ChunkTooLarge { bytes, max });
+    }
+    Ok(())
+}
+
 /// Trait for setting the length of a RimIO object.
 ///
 /// Allows resizing the underlying storage (if supported by the backend).
 pub trait RimIOSetLen: RimIO {
     /// Sets the length of the storage.
     fn set_len(&mut self, len: u64) -> RimIOResult;
+
+    /// Sets the length to `len` rounded up to `granularity`, zero-filling the padding.
+    ///
+    /// Returns the final length. Handy to finalize images on a sector/MiB boundary.
+    fn set_len_aligned(&mut self, len: u64, granularity: u64) -> RimIOResult<u64> {
+        let rounded = utils::round_up_len(len, granularity);
+        self.set_len(rounded)?;
+        let mut off = len;
+        while off < rounded {
+            let chunk = (rounded - off).min(usize::MAX as u64) as usize;
+            self.zero_fill(off, chunk)?;
+            off += chunk as u64;
+        }
+        Ok(rounded)
+    }
 }
 
 /// Extension trait for reading and writing structs using zerocopy.
@@ -686,17 +1072,62 @@ pub trait RimIOSetLen: RimIO {
 /// Requires the struct to implement zerocopy traits for safe conversion.
 pub trait RimIOStructExt: RimIO {
     /// Reads a struct of type `T` from the given offset.
+    ///
+    /// Types larger than `BLOCK_BUF_SIZE` go through a heap buffer (`alloc`),
+    /// or are rejected with `RimIOError::Invalid` without it.
     fn read_struct<T: zerocopy::FromBytes + zerocopy::KnownLayout + zerocopy::Immutable>(
         &mut self,
         offset: u64,
     ) -> RimIOResult<T> {
         let size = core::mem::size_of::<T>();
-        assert!(size <= BLOCK_BUF_SIZE, "read_struct: type too large");
+        if size > BLOCK_BUF_SIZE {
+            #[cfg(feature = "alloc")]
+            {
+                let mut buf = vec![0u8; size];
+                self.read_at(offset, &mut buf)?;
+                return T::read_from_bytes(&buf)
+                    .map_err(|_| RimIOError::Other("read_struct failed"));
+            }
+            #[cfg(not(feature = "alloc"))]
+            return Err(RimIOError::Invalid("read_struct: type too large"));
+        }
         let mut buf = [0u8; BLOCK_BUF_SIZE];
         self.read_at(offset, &mut buf[..size])?;
         T::read_from_bytes(&buf[..size]).map_err(|_| RimIOError::Other("read_struct failed"))
     }
 
+    /// Fills `out` with `out.len()` structs read back-to-back from `offset`.
+    ///
+    /// Reads go straight into `out`, in `BLOCK_BUF_SIZE` chunks, without extra buffers.
+    fn read_structs_into<T>(&mut self, offset: u64, out: &mut [T]) -> RimIOResult
+    where
+        T: zerocopy::FromBytes + zerocopy::IntoBytes + zerocopy::KnownLayout + zerocopy::Immutable,
+    {
+        core::mem::size_of::<T>()
+            .checked_mul(out.len())
+            .ok_or(RimIOError::Invalid("read_structs_into: length overflow"))?;
+        self.read_in_chunks(
+            offset,
+            zerocopy::IntoBytes::as_mut_bytes(out),
+            BLOCK_BUF_SIZE,
+        )
+    }
+
+    /// Reads `count` structs back-to-back from `offset` (GPT entry tables, ext4 BGDT…).
+    #[cfg(feature = "alloc")]
+    fn read_struct_array<T>(&mut self, offset: u64, count: usize) -> RimIOResult<alloc::vec::Vec<T>>
+    where
+        T: zerocopy::FromBytes + zerocopy::IntoBytes + zerocopy::KnownLayout + zerocopy::Immutable,
+    {
+        core::mem::size_of::<T>()
+            .checked_mul(count)
+            .ok_or(RimIOError::Invalid("read_struct_array: length overflow"))?;
+        let mut out = T::new_vec_zeroed(count)
+            .map_err(|_| RimIOError::Other("read_struct_array: allocation failed"))?;
+        self.read_structs_into(offset, &mut out)?;
+        Ok(out)
+    }
+
     /// Writes a struct of type `T` at the given offset.
     fn write_struct<T: zerocopy::IntoBytes + zerocopy::KnownLayout + zerocopy::Immutable>(
         &mut self,
@@ -709,3 +1140,55 @@ pub trait RimIOStructExt: RimIO {
 }
 
 impl<T: RimIO + ?Sized> RimIOStructExt for T {}
+
+#[cfg(all(test, feature = "mem", not(feature = "alloc")))]
+mod test {
+    use super::BLOCK_BUF_SIZE;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_streamed_chunk_too_large_is_error() {
+        let mut buf = [0u8; 2 * BLOCK_BUF_SIZE];
+        let mut io = MemRimIO::new(&mut buf);
+        let too_many = BLOCK_BUF_SIZE / 4 + 1;
+        let err = RimIOError::ChunkTooLarge {
+            bytes: too_many * 4,
+            max: BLOCK_BUF_SIZE,
+        };
+
+        assert_eq!(
+            io.write_chunks_streamed::<4, _>(0, too_many, too_many, |i| (i as u32).to_le_bytes()),
+            Err(err)
+        );
+        assert_eq!(
+            io.read_chunks_streamed::<4, _>(0, too_many, too_many, |_, _| {}),
+            Err(err)
+        );
+
+        io.write_chunks_streamed::<4, _>(0, 10, BLOCK_BUF_SIZE / 4, |i| (i as u32).to_le_bytes())
+            .unwrap();
+        assert_eq!(io.read_u32_at(36).unwrap(), 9);
+    }
+
+    #[test]
+    fn test_multi_streamed_no_alloc() {
+        let mut buf = [0u8; 4096];
+        let mut io = MemRimIO::new(&mut buf);
+        let offsets = [512u64, 8, 1024, 100, 2000];
+
+        io.write_multi_streamed::<4, _>(&offsets, 2, |i| (i as u32 + 1).to_le_bytes())
+            .unwrap();
+        assert_eq!(io.read_u32_at(100).unwrap(), 4);
+
+        let mut seen = [0u32; 5];
+        io.read_multi_streamed::<4, _>(&offsets, 3, |i, b| seen[i] = u32::from_le_bytes(*b))
+            .unwrap();
+        assert_eq!(seen, [1, 2, 3, 4, 5]);
+
+        let too_many = BLOCK_BUF_SIZE / 4 + 1;
+        assert!(matches!(
+            io.read_multi_streamed::<4, _>(&offsets, too_many, |_, _| {}),
+            Err(RimIOError::ChunkTooLarge { .. })
+        ));
+    }
+}
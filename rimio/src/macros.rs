@@ -1,6 +1,43 @@
 /// Automatically implements read/write functions for primitive types on RimIO
+///
+/// `RimIO_impl_primitive_rw!(u16, u32)` generates little-endian `read_u16_at`/`write_u16_at`…,
+/// `RimIO_impl_primitive_rw!(be: u16, u32)` generates big-endian `read_u16_be_at`/`write_u16_be_at`…,
+/// `RimIO_impl_primitive_rw!(signed: i32 => u32)` generates `read_i32_at`/`write_i32_at` on top of the unsigned helpers.
 #[macro_export]
 macro_rules! RimIO_impl_primitive_rw {
+    (signed: $($ity:ty => $uty:ty),+ $(,)?) => {
+        $(
+            paste::paste! {
+                #[inline(always)]
+                fn [<write_ $ity _at>](&mut self, offset: u64, value: $ity) -> RimIOResult {
+                    self.[<write_ $uty _at>](offset, value as $uty)
+                }
+
+                #[inline(always)]
+                fn [<read_ $ity _at>](&mut self, offset: u64) -> RimIOResult<$ity> {
+                    Ok(self.[<read_ $uty _at>](offset)? as $ity)
+                }
+            }
+        )+
+    };
+    (be: $($ty:ty),+ $(,)?) => {
+        $(
+            paste::paste! {
+                #[inline(always)]
+                fn [<write_ $ty _be_at>](&mut self, offset: u64, value: $ty) -> RimIOResult {
+                    let buf = value.to_be_bytes();
+                    self.write_at(offset, &buf)
+                }
+
+                #[inline(always)]
+                fn [<read_ $ty _be_at>](&mut self, offset: u64) -> RimIOResult<$ty> {
+                    let mut buf = [0u8; core::mem::size_of::<$ty>()];
+                    self.read_at(offset, &mut buf)?;
+                    Ok(<$ty>::from_be_bytes(buf))
+                }
+            }
+        )+
+    };
     ($($ty:ty),+ $(,)?) => {
         $(
             paste::paste! {
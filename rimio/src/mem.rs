@@ -1,13 +1,24 @@
 // SPDX-License-Identifier: MIT
 
-use crate::{RimIO, RimIOError, RimIOResult, RimIOSetLen};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{RimIO, RimIOError, RimIOResult, RimIOSetLen, prelude::PartitionRimIO};
+
+/// Backing storage of a [`MemRimIO`].
+#[derive(Debug)]
+enum MemStorage<'a> {
+    Borrowed(&'a mut [u8]),
+    #[cfg(feature = "alloc")]
+    Owned(Vec<u8>),
+}
 
 /// In-memory implementation of `RimIO`.
 ///
 /// Useful for tests, RAM-backed FS, virtual disks.
 #[derive(Debug)]
 pub struct MemRimIO<'a> {
-    buffer: &'a mut [u8],
+    buffer: MemStorage<'a>,
     partition_offset: u64,
     logical_len: usize,
 }
@@ -18,7 +29,7 @@ impl<'a> MemRimIO<'a> {
         let logical_len = buffer.len();
 
         Self {
-            buffer,
+            buffer: MemStorage::Borrowed(buffer),
             logical_len,
             partition_offset: 0,
         }
@@ -29,43 +40,152 @@ impl<'a> MemRimIO<'a> {
         let logical_len = buffer.len();
 
         Self {
-            buffer,
+            buffer: MemStorage::Borrowed(buffer),
             logical_len,
             partition_offset,
         }
     }
 
+    /// View restricted to `len` bytes starting at `start`.
+    ///
+    /// Handy for exercising a single partition: offset 0 of the view is
+    /// `start`, and accesses past `len` fail instead of touching neighbours.
+    #[inline]
+    pub fn subregion(&mut self, start: u64, len: u64) -> PartitionRimIO<'_, Self> {
+        PartitionRimIO::new(self, start, len)
+    }
+
+    /// Current contents, up to the logical length.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes()[..self.logical_len]
+    }
+
+    /// Consumes the backend and returns its contents.
+    ///
+    /// Borrowed buffers are copied; growable ones hand back their vector.
+    #[cfg(feature = "alloc")]
+    pub fn into_inner(self) -> Vec<u8> {
+        match self.buffer {
+            MemStorage::Borrowed(buf) => buf[..self.logical_len].to_vec(),
+            MemStorage::Owned(mut vec) => {
+                vec.truncate(self.logical_len);
+                vec
+            }
+        }
+    }
+
+    #[inline]
+    fn bytes(&self) -> &[u8] {
+        match &self.buffer {
+            MemStorage::Borrowed(buf) => buf,
+            #[cfg(feature = "alloc")]
+            MemStorage::Owned(vec) => vec,
+        }
+    }
+
+    #[inline]
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        match &mut self.buffer {
+            MemStorage::Borrowed(buf) => buf,
+            #[cfg(feature = "alloc")]
+            MemStorage::Owned(vec) => vec,
+        }
+    }
+
+    #[inline]
+    fn is_growable(&self) -> bool {
+        match self.buffer {
+            MemStorage::Borrowed(_) => false,
+            #[cfg(feature = "alloc")]
+            MemStorage::Owned(_) => true,
+        }
+    }
+
+    /// Makes room for `[abs_off, abs_off + len)`, growing owned storage if needed.
+    #[inline]
+    fn reserve(&mut self, abs_off: u64, len: usize) -> RimIOResult {
+        #[cfg(feature = "alloc")]
+        if let MemStorage::Owned(vec) = &mut self.buffer {
+            let end = abs_off
+                .checked_add(len as u64)
+                .ok_or(RimIOError::Invalid("Offset overflow"))? as usize;
+            if end > vec.len() {
+                vec.resize(end, 0);
+            }
+            self.logical_len = vec.len();
+            return Ok(());
+        }
+        self.check_bounds(abs_off, len)
+    }
+
+    /// Out-of-range error for a caller-relative access; `size` is what the
+    /// caller can see past the partition offset.
+    #[inline]
+    fn out_of_bounds(&self, offset: u64, len: usize) -> RimIOError {
+        RimIOError::OutOfBounds {
+            offset,
+            len,
+            size: (self.logical_len as u64).saturating_sub(self.partition_offset),
+        }
+    }
+
+    /// Absolute offset of a caller-relative `offset`.
+    #[inline]
+    fn abs_offset(&self, offset: u64, len: usize) -> RimIOResult<u64> {
+        self.partition_offset
+            .checked_add(offset)
+            .ok_or_else(|| self.out_of_bounds(offset, len))
+    }
+
     #[inline]
     fn check_bounds(&self, abs_off: u64, len: usize) -> RimIOResult {
-        let end = abs_off
-            .checked_add(len as u64)
-            .ok_or(RimIOError::OutOfBounds)?;
-        let max = self
-            .partition_offset
-            .checked_add(self.logical_len as u64)
-            .ok_or(RimIOError::OutOfBounds)?;
-        if end > max {
-            return Err(RimIOError::OutOfBounds);
+        match abs_off.checked_add(len as u64) {
+            Some(end) if end <= self.logical_len as u64 => Ok(()),
+            _ => Err(self.out_of_bounds(abs_off - self.partition_offset, len)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl MemRimIO<'static> {
+    /// Creates an empty, owned backend that grows on demand.
+    ///
+    /// Writes past the current end zero-extend the buffer; reads past the end
+    /// return zeros instead of failing.
+    #[inline]
+    pub fn new_growable() -> Self {
+        Self {
+            buffer: MemStorage::Owned(Vec::new()),
+            logical_len: 0,
+            partition_offset: 0,
         }
-        Ok(())
     }
 }
 
 impl<'a> RimIO for MemRimIO<'a> {
     #[inline(always)]
     fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
-        let abs_offset = self.partition_offset + offset;
-        self.check_bounds(abs_offset, data.len())?;
-        let dst = &mut self.buffer[abs_offset as usize..abs_offset as usize + data.len()];
+        let abs_offset = self.abs_offset(offset, data.len())?;
+        self.reserve(abs_offset, data.len())?;
+        let dst = &mut self.bytes_mut()[abs_offset as usize..abs_offset as usize + data.len()];
         dst.copy_from_slice(data);
         Ok(())
     }
 
     #[inline(always)]
     fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
-        let abs_offset = self.partition_offset + offset;
+        let abs_offset = self.abs_offset(offset, buf.len())?;
+        if self.is_growable() {
+            let data = self.as_slice();
+            let start = (abs_offset as usize).min(data.len());
+            let avail = (data.len() - start).min(buf.len());
+            buf[..avail].copy_from_slice(&data[start..start + avail]);
+            buf[avail..].fill(0);
+            return Ok(());
+        }
         self.check_bounds(abs_offset, buf.len())?;
-        let src = &self.buffer[abs_offset as usize..abs_offset as usize + buf.len()];
+        let src = &self.bytes()[abs_offset as usize..abs_offset as usize + buf.len()];
         buf.copy_from_slice(src);
         Ok(())
     }
@@ -86,6 +206,11 @@ impl<'a> RimIO for MemRimIO<'a> {
         self.partition_offset
     }
 
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        Ok(self.logical_len as u64)
+    }
+
     /// Optimized single-copy implementation.
     /// Reads directly from `src` into the internal buffer segment.
     fn copy_from(
@@ -95,26 +220,40 @@ impl<'a> RimIO for MemRimIO<'a> {
         dest_offset: u64,
         len: u64,
     ) -> RimIOResult {
-        let abs_offset = self.partition_offset + dest_offset;
         let len_usize = len as usize;
-        self.check_bounds(abs_offset, len_usize)?;
+        let abs_offset = self.abs_offset(dest_offset, len_usize)?;
+        self.reserve(abs_offset, len_usize)?;
 
-        let dst = &mut self.buffer[abs_offset as usize..abs_offset as usize + len_usize];
+        let dst = &mut self.bytes_mut()[abs_offset as usize..abs_offset as usize + len_usize];
         src.read_at(src_offset, dst)?;
         Ok(())
     }
 }
 
 impl<'a> RimIOSetLen for MemRimIO<'a> {
+    /// Sets the logical length.
+    ///
+    /// Borrowed buffers cannot grow past their slice. Growable backends are
+    /// truncated or zero-extended so that the data ends at `new_len`.
     fn set_len(&mut self, new_len: u64) -> RimIOResult {
         let end = self
             .partition_offset
             .checked_add(new_len)
-            .ok_or(RimIOError::OutOfBounds)? as usize;
-        if end > self.buffer.len() {
-            return Err(RimIOError::OutOfBounds);
+            .ok_or(RimIOError::Invalid("Offset overflow"))? as usize;
+        #[cfg(feature = "alloc")]
+        if let MemStorage::Owned(vec) = &mut self.buffer {
+            vec.resize(end, 0);
+            self.logical_len = end;
+            return Ok(());
+        }
+        if end > self.bytes().len() {
+            return Err(RimIOError::OutOfBounds {
+                offset: new_len,
+                len: 0,
+                size: (self.bytes().len() as u64).saturating_sub(self.partition_offset),
+            });
         }
-        self.logical_len = new_len as usize;
+        self.logical_len = end;
         Ok(())
     }
 }
@@ -195,6 +334,360 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_chunks_streamed_reports_progress() {
+        let mut buf = [0u8; 1024];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let mut calls = [(0usize, 0usize); 4];
+        let mut n = 0;
+        io.write_chunks_streamed_with_progress::<4, _, _>(
+            0,
+            10,
+            3,
+            |i| (i as u32).to_le_bytes(),
+            |written, total| {
+                calls[n] = (written, total);
+                n += 1;
+            },
+        )
+        .unwrap();
+
+        assert_eq!(n, 4);
+        assert_eq!(calls, [(3, 10), (6, 10), (9, 10), (10, 10)]);
+        assert_eq!(io.read_u32_at(36).unwrap(), 9);
+    }
+
+    #[test]
+    fn test_out_of_bounds_is_error() {
+        let mut buf = [0u8; 100];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let mut out = [0u8; 16];
+        assert_eq!(
+            io.read_at(90, &mut out),
+            Err(RimIOError::OutOfBounds {
+                offset: 90,
+                len: 16,
+                size: 100,
+            })
+        );
+        assert!(matches!(
+            io.write_at(u64::MAX, &[1]),
+            Err(RimIOError::OutOfBounds { .. })
+        ));
+
+        io.set_offset(40);
+        assert_eq!(
+            io.write_at(50, &[0u8; 16]),
+            Err(RimIOError::OutOfBounds {
+                offset: 50,
+                len: 16,
+                size: 60,
+            })
+        );
+        io.read_at(44, &mut out).unwrap();
+    }
+
+    #[test]
+    fn test_read_exact_past_end() {
+        let mut buf = [0x5Au8; 100];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let mut out = [0u8; 64];
+        assert_eq!(
+            io.read_exact_at(60, &mut out),
+            Err(RimIOError::UnexpectedEof {
+                offset: 60,
+                requested: 64,
+                got: 40,
+            })
+        );
+        assert_eq!(
+            io.write_all_at(90, &[1u8; 16]),
+            Err(RimIOError::UnexpectedEof {
+                offset: 90,
+                requested: 16,
+                got: 10,
+            })
+        );
+        io.read_exact_at(36, &mut out).unwrap();
+    }
+
+    #[test]
+    fn test_read_struct_larger_than_scratch() {
+        #[derive(zerocopy::FromBytes, zerocopy::KnownLayout, zerocopy::Immutable)]
+        #[repr(C)]
+        struct Big {
+            head: u32,
+            body: [u8; 9000],
+            tail: u32,
+        }
+
+        let mut buf = vec![0u8; 10_000];
+        let mut io = MemRimIO::new(&mut buf);
+        io.write_u32_at(16, 0xDEAD_BEEF).unwrap();
+        io.fill_region(20, 9000, 0x5A).unwrap();
+        io.write_u32_at(9020, 0x1234_5678).unwrap();
+
+        let big: Big = io.read_struct(16).unwrap();
+        assert_eq!(big.head, 0xDEAD_BEEF);
+        assert!(big.body.iter().all(|&b| b == 0x5A));
+        assert_eq!(big.tail, 0x1234_5678);
+
+        assert!(matches!(
+            io.read_struct::<Big>(2000),
+            Err(RimIOError::OutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_struct_array() {
+        #[derive(
+            Debug,
+            PartialEq,
+            zerocopy::FromBytes,
+            zerocopy::IntoBytes,
+            zerocopy::KnownLayout,
+            zerocopy::Immutable,
+        )]
+        #[repr(C)]
+        struct Entry {
+            type_guid: [u8; 16],
+            start_lba: u64,
+            end_lba: u64,
+            name: [u8; 32],
+        }
+
+        let entries: Vec<Entry> = (0..200u64)
+            .map(|i| Entry {
+                type_guid: [i as u8; 16],
+                start_lba: 2048 * (i + 1),
+                end_lba: 2048 * (i + 2) - 1,
+                name: [b'a' + (i % 26) as u8; 32],
+            })
+            .collect();
+
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut io = MemRimIO::new(&mut buf);
+        for (i, e) in entries.iter().enumerate() {
+            io.write_struct(1024 + (i * size_of::<Entry>()) as u64, e)
+                .unwrap();
+        }
+
+        let read: Vec<Entry> = io.read_struct_array(1024, entries.len()).unwrap();
+        assert_eq!(read, entries);
+
+        let mut some: [Entry; 3] = zerocopy::FromZeros::new_zeroed();
+        io.read_structs_into(1024 + 5 * size_of::<Entry>() as u64, &mut some)
+            .unwrap();
+        assert_eq!(some[..], entries[5..8]);
+
+        assert!(matches!(
+            io.read_struct_array::<Entry>(0, usize::MAX / 8),
+            Err(RimIOError::Invalid(_))
+        ));
+        assert!(io.read_struct_array::<Entry>(0, 2000).is_err());
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        const MAGIC: &[u8] = b"EXFAT   ";
+        let bs = crate::BLOCK_BUF_SIZE;
+        let mut buf = vec![0u8; 4 * bs];
+        // Unaligned copy straddling the first buffer boundary, aligned one later.
+        buf[bs - 3..bs - 3 + MAGIC.len()].copy_from_slice(MAGIC);
+        buf[2 * bs + 512..2 * bs + 512 + MAGIC.len()].copy_from_slice(MAGIC);
+        let mut io = MemRimIO::new(&mut buf);
+        let len = (4 * bs) as u64;
+
+        assert_eq!(
+            io.find_pattern(MAGIC, 0, len, 1).unwrap(),
+            Some((bs - 3) as u64)
+        );
+        assert_eq!(
+            io.find_pattern(MAGIC, 0, len, 512).unwrap(),
+            Some((2 * bs + 512) as u64)
+        );
+        // Match must end before `end`, and `start` skips earlier hits.
+        assert_eq!(io.find_pattern(MAGIC, 0, (bs + 4) as u64, 1).unwrap(), None);
+        assert_eq!(
+            io.find_pattern(MAGIC, (bs - 2) as u64, len, 1).unwrap(),
+            Some((2 * bs + 512) as u64)
+        );
+        assert!(io.find_pattern(&[], 0, len, 1).is_err());
+    }
+
+    #[cfg(feature = "crc")]
+    #[test]
+    fn test_crc32_region() {
+        let mut buf = vec![0u8; 3 * crate::BLOCK_BUF_SIZE];
+        buf[100..109].copy_from_slice(b"123456789");
+        for (i, b) in buf[200..].iter_mut().enumerate() {
+            *b = (i * 7) as u8;
+        }
+        let expected = crc32fast::hash(&buf[150..]);
+
+        let mut io = MemRimIO::new(&mut buf);
+        // Standard check value for CRC-32/ISO-HDLC.
+        assert_eq!(io.crc32_region(100, 9).unwrap(), 0xCBF4_3926);
+        assert_eq!(
+            io.crc32_region(150, (3 * crate::BLOCK_BUF_SIZE - 150) as u64)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(io.crc32_region(0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_copy_region() {
+        let pattern: Vec<u8> = (0..10_000u32).map(|i| (i % 253) as u8).collect();
+
+        // Disjoint
+        let mut buf = vec![0u8; 30_000];
+        buf[..10_000].copy_from_slice(&pattern);
+        let mut io = MemRimIO::new(&mut buf);
+        io.copy_region(0, 20_000, 10_000).unwrap();
+        assert_eq!(&buf[20_000..], &pattern[..]);
+
+        // Overlap, dst > src (must copy backwards)
+        let mut buf = vec![0u8; 20_000];
+        buf[..10_000].copy_from_slice(&pattern);
+        let mut io = MemRimIO::new(&mut buf);
+        io.copy_region(0, 3_000, 10_000).unwrap();
+        assert_eq!(&buf[3_000..13_000], &pattern[..]);
+
+        // Overlap, dst < src (forward copy)
+        let mut buf = vec![0u8; 20_000];
+        buf[5_000..15_000].copy_from_slice(&pattern);
+        let mut io = MemRimIO::new(&mut buf);
+        io.copy_region(5_000, 1_000, 10_000).unwrap();
+        assert_eq!(&buf[1_000..11_000], &pattern[..]);
+    }
+
+    #[test]
+    fn test_growable() {
+        let mut io = MemRimIO::new_growable();
+        assert!(io.as_slice().is_empty());
+
+        io.write_at(100, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(io.as_slice().len(), 104);
+
+        let mut out = [0xFFu8; 8];
+        io.read_at(100, &mut out).unwrap();
+        assert_eq!(out, [1, 2, 3, 4, 0, 0, 0, 0]);
+        io.read_at(4096, &mut out).unwrap();
+        assert_eq!(out, [0u8; 8]);
+
+        let data = io.into_inner();
+        assert_eq!(data.len(), 104);
+        assert_eq!(&data[100..], &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_growable_set_len() {
+        let mut io = MemRimIO::new_growable();
+        io.write_at(0, &[0xAA; 16]).unwrap();
+
+        // Grow: new tail reads back as zeros
+        io.set_len(64).unwrap();
+        assert_eq!(io.as_slice().len(), 64);
+        let mut out = [0xFFu8; 4];
+        io.read_at(14, &mut out).unwrap();
+        assert_eq!(out, [0xAA, 0xAA, 0, 0]);
+
+        // Shrink below existing data: tail is dropped
+        io.set_len(8).unwrap();
+        assert_eq!(io.as_slice(), &[0xAA; 8]);
+        io.read_at(6, &mut out).unwrap();
+        assert_eq!(out, [0xAA, 0xAA, 0, 0]);
+
+        // Growing again does not resurrect dropped bytes
+        io.set_len(16).unwrap();
+        io.read_at(6, &mut out).unwrap();
+        assert_eq!(out, [0xAA, 0xAA, 0, 0]);
+        assert_eq!(io.into_inner().len(), 16);
+    }
+
+    #[test]
+    fn test_flush_persists() {
+        let mut buf = [0u8; 64];
+        let mut io = MemRimIO::new(&mut buf);
+        assert!(!io.flush_persists());
+        assert!(!PartitionRimIO::new(&mut io, 0, 32).flush_persists());
+        assert!(!MemRimIO::new_growable().flush_persists());
+    }
+
+    #[test]
+    fn test_big_endian_rw() {
+        let mut buf = [0u8; 64];
+        let mut io = MemRimIO::new(&mut buf);
+
+        io.write_u16_be_at(0, 0x1234).unwrap();
+        io.write_u32_be_at(2, 0xDEAD_BEEF).unwrap();
+        io.write_u64_be_at(6, 0x0102_0304_0506_0708).unwrap();
+        io.write_u128_be_at(14, u128::MAX - 1).unwrap();
+
+        assert_eq!(io.read_u16_be_at(0).unwrap(), 0x1234);
+        assert_eq!(io.read_u32_be_at(2).unwrap(), 0xDEAD_BEEF);
+        assert_eq!(io.read_u64_be_at(6).unwrap(), 0x0102_0304_0506_0708);
+        assert_eq!(io.read_u128_be_at(14).unwrap(), u128::MAX - 1);
+
+        // Same bytes through the LE helpers come back swapped
+        assert_eq!(io.read_u32_at(2).unwrap(), 0xEFBE_ADDE);
+        assert_eq!(
+            u32::from_be_bytes(buf[2..6].try_into().unwrap()),
+            0xDEAD_BEEF
+        );
+    }
+
+    #[test]
+    fn test_signed_rw() {
+        let mut buf = [0u8; 32];
+        let mut io = MemRimIO::new(&mut buf);
+
+        io.write_i16_at(0, -2).unwrap();
+        io.write_i32_at(2, i32::MIN).unwrap();
+        io.write_i64_at(6, -1_234_567_890_123).unwrap();
+
+        assert_eq!(io.read_i16_at(0).unwrap(), -2);
+        assert_eq!(io.read_i32_at(2).unwrap(), i32::MIN);
+        assert_eq!(io.read_i64_at(6).unwrap(), -1_234_567_890_123);
+        assert_eq!(io.read_u16_at(0).unwrap(), 0xFFFE);
+    }
+
+    #[test]
+    fn test_subregion_stays_inside() {
+        let mut buf = [0u8; 64];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let mut part = io.subregion(16, 32);
+        assert_eq!(part.len(), 32);
+        part.fill_region(0, 32, 0xCC).unwrap();
+        assert!(matches!(
+            part.write_at(30, &[1, 2, 3]),
+            Err(RimIOError::OutOfBounds { .. })
+        ));
+        assert_eq!(part.read_u16_at(0).unwrap(), 0xCCCC);
+
+        assert_eq!(&buf[..16], &[0u8; 16]);
+        assert!(buf[16..48].iter().all(|&b| b == 0xCC));
+        assert_eq!(&buf[48..], &[0u8; 16]);
+    }
+
+    #[test]
+    fn test_fill_region() {
+        let len = crate::BLOCK_BUF_SIZE * 2 + 123;
+        let mut buf = vec![0u8; len + 20];
+        let mut io = MemRimIO::new(&mut buf);
+
+        io.fill_region(10, len, 0xAB).unwrap();
+
+        assert_eq!(&buf[..10], &[0u8; 10]);
+        assert!(buf[10..10 + len].iter().all(|&b| b == 0xAB));
+        assert_eq!(&buf[10 + len..], &[0u8; 10]);
+    }
+
     #[test]
     fn test_zero_fill() {
         let mut buf = [0xFF; 64];
@@ -206,4 +699,43 @@ mod test {
         io.read_at(10, &mut output).unwrap();
         assert_eq!(output, [0u8; 8]);
     }
+
+    #[test]
+    fn test_progress_callbacks() {
+        let len = crate::BLOCK_BUF_SIZE * 3 + 77;
+        let mut buf = vec![0xFFu8; len * 2];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let mut seen = Vec::new();
+        io.zero_fill_with_progress(0, len, &mut |done| seen.push(done))
+            .unwrap();
+        assert_eq!(seen.len(), 4);
+        assert!(seen.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*seen.last().unwrap(), len as u64);
+
+        let mut seen = Vec::new();
+        io.copy_region_with_progress(0, len as u64, len as u64, &mut |done| seen.push(done))
+            .unwrap();
+        assert!(seen.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*seen.last().unwrap(), len as u64);
+        assert!(buf.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_len() {
+        let mut buf = [0u8; 4096];
+        let mut io = MemRimIO::new_with_offset(&mut buf, 1024);
+        assert_eq!(io.len().unwrap(), 4096);
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut grow = MemRimIO::new_growable();
+            assert_eq!(grow.len().unwrap(), 0);
+            grow.write_at(100, &[1; 28]).unwrap();
+            assert_eq!(grow.len().unwrap(), 128);
+        }
+
+        let mut view = PartitionRimIO::new(&mut io, 512, 1024);
+        assert_eq!(RimIO::len(&mut view).unwrap(), view.len());
+    }
 }
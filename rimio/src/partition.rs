@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: MIT
+
+use crate::{RimIO, RimIOError, RimIOResult};
+
+/// Windowed view over a region of another `RimIO`.
+///
+/// Offset 0 of the view maps to `base` on the inner IO, and any access that
+/// would reach past `len` bytes fails with `RimIOError::OutOfBounds`. Handing a
+/// view to a formatter or checker guarantees it cannot touch a neighbouring
+/// partition.
+pub struct PartitionRimIO<'a, IO: RimIO + ?Sized> {
+    inner: &'a mut IO,
+    base: u64,
+    len: u64,
+}
+
+impl<'a, IO: RimIO + ?Sized> PartitionRimIO<'a, IO> {
+    #[inline]
+    pub fn new(inner: &'a mut IO, base: u64, len: u64) -> Self {
+        Self { inner, base, len }
+    }
+
+    #[inline]
+    pub fn inner(&mut self) -> &mut IO {
+        self.inner
+    }
+
+    /// Start of the view, relative to the inner IO.
+    #[inline]
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Length of the view in bytes.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> &'a mut IO {
+        self.inner
+    }
+
+    #[inline]
+    fn translate(&self, offset: u64, len: usize) -> RimIOResult<u64> {
+        let oob = RimIOError::OutOfBounds {
+            offset,
+            len,
+            size: self.len,
+        };
+        match offset.checked_add(len as u64) {
+            Some(end) if end <= self.len => self.base.checked_add(offset).ok_or(oob),
+            _ => Err(oob),
+        }
+    }
+}
+
+impl<'a, IO: RimIO + ?Sized> RimIO for PartitionRimIO<'a, IO> {
+    #[inline]
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
+        let off = self.translate(offset, data.len())?;
+        self.inner.write_at(off, data)
+    }
+
+    #[inline]
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
+        let off = self.translate(offset, buf.len())?;
+        self.inner.read_at(off, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> RimIOResult {
+        self.inner.flush()
+    }
+
+    /// Moves the window so it starts at the absolute `partition_offset`,
+    /// keeping its length. Offsets before the inner IO's own start clamp to it;
+    /// returns the resulting absolute start.
+    #[inline]
+    fn set_offset(&mut self, partition_offset: u64) -> u64 {
+        self.base = partition_offset.saturating_sub(self.inner.partition_offset());
+        self.partition_offset()
+    }
+
+    /// Absolute start of the view (inner partition offset + `base`).
+    #[inline]
+    fn partition_offset(&self) -> u64 {
+        self.inner.partition_offset() + self.base
+    }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        self.inner.flush_persists()
+    }
+
+    /// Length of the window.
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        Ok(self.len)
+    }
+
+    #[inline]
+    fn try_zero_range(&mut self, offset: u64, len: u64) -> RimIOResult<bool> {
+        let len = usize::try_from(len).map_err(|_| RimIOError::Invalid("Length overflow"))?;
+        let off = self.translate(offset, len)?;
+        self.inner.try_zero_range(off, len as u64)
+    }
+}
+
+#[cfg(all(test, feature = "mem"))]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_reads_are_rebased() {
+        let mut buf = [0u8; 64];
+        buf[16..20].copy_from_slice(&[1, 2, 3, 4]);
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut view = PartitionRimIO::new(&mut mem, 16, 32);
+
+        let mut out = [0u8; 4];
+        view.read_at(0, &mut out).unwrap();
+        assert_eq!(out, [1, 2, 3, 4]);
+        assert_eq!(view.base(), 16);
+        assert_eq!(view.partition_offset(), 16);
+
+        view.write_u32_at(28, 0xDEAD_BEEF).unwrap();
+        assert_eq!(view.inner().read_u32_at(44).unwrap(), 0xDEAD_BEEF);
+    }
+
+    #[test]
+    fn test_access_past_len_fails() {
+        let mut buf = [0u8; 64];
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut view = PartitionRimIO::new(&mut mem, 16, 32);
+
+        let len = view.len();
+        assert!(matches!(
+            view.write_at(len, &[0xFF]),
+            Err(RimIOError::OutOfBounds { .. })
+        ));
+        assert!(view.write_at(len - 2, &[0xFF; 4]).is_err());
+        let mut out = [0u8; 1];
+        assert!(view.read_at(len, &mut out).is_err());
+
+        // Nothing leaked past the window.
+        assert!(buf[48..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_set_offset_is_absolute_when_nested() {
+        let mut buf = [0u8; 128];
+        buf[40..44].copy_from_slice(&[1, 2, 3, 4]);
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut outer = PartitionRimIO::new(&mut mem, 16, 96);
+        let mut inner = PartitionRimIO::new(&mut outer, 8, 32);
+        assert_eq!(inner.partition_offset(), 24);
+
+        assert_eq!(inner.set_offset(40), 40);
+        assert_eq!(inner.partition_offset(), 40);
+        assert_eq!(inner.base(), 24);
+        let mut out = [0u8; 4];
+        inner.read_at(0, &mut out).unwrap();
+        assert_eq!(out, [1, 2, 3, 4]);
+
+        // Can't move before the outer view
+        assert_eq!(inner.set_offset(4), 16);
+    }
+}
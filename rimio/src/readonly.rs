@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: MIT
+
+use crate::{RimIO, RimIOError, RimIOResult};
+
+/// Wrapper guaranteeing the inner IO is never mutated.
+///
+/// Every `write_at` fails with `RimIOError::ReadOnly` and `flush` is a
+/// no-op, reads and offsets are forwarded. Put it around a real device
+/// before running checkers or resolvers on it.
+pub struct ReadOnlyRimIO<'a, IO: RimIO + ?Sized> {
+    inner: &'a mut IO,
+}
+
+impl<'a, IO: RimIO + ?Sized> ReadOnlyRimIO<'a, IO> {
+    #[inline]
+    pub fn new(inner: &'a mut IO) -> Self {
+        Self { inner }
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> &'a mut IO {
+        self.inner
+    }
+}
+
+impl<'a, IO: RimIO + ?Sized> RimIO for ReadOnlyRimIO<'a, IO> {
+    #[inline]
+    fn write_at(&mut self, _offset: u64, _data: &[u8]) -> RimIOResult {
+        Err(RimIOError::ReadOnly)
+    }
+
+    #[inline]
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
+        self.inner.read_at(offset, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> RimIOResult {
+        Ok(())
+    }
+
+    #[inline]
+    fn set_offset(&mut self, partition_offset: u64) -> u64 {
+        self.inner.set_offset(partition_offset)
+    }
+
+    #[inline]
+    fn partition_offset(&self) -> u64 {
+        self.inner.partition_offset()
+    }
+
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        self.inner.len()
+    }
+}
+
+#[cfg(all(test, feature = "mem"))]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_read_only_rejects_writes() {
+        let mut buf: [u8; 64] = core::array::from_fn(|i| i as u8);
+        let mut mem = MemRimIO::new(&mut buf);
+        let mut io = ReadOnlyRimIO::new(&mut mem);
+
+        let mut out = [0u8; 4];
+        io.read_at(8, &mut out).unwrap();
+        assert_eq!(out, [8, 9, 10, 11]);
+
+        assert_eq!(io.write_at(0, &[0xFF]), Err(RimIOError::ReadOnly));
+        assert_eq!(io.zero_fill(0, 16), Err(RimIOError::ReadOnly));
+        io.flush().unwrap();
+        assert!(buf.iter().enumerate().all(|(i, &b)| b == i as u8));
+    }
+}
@@ -35,19 +35,11 @@ impl IoStats {
     }
     #[inline]
     pub fn avg_read(&self) -> u64 {
-        if self.reads == 0 {
-            0
-        } else {
-            self.read_bytes / self.reads
-        }
+        self.read_bytes.checked_div(self.reads).unwrap_or(0)
     }
     #[inline]
     pub fn avg_write(&self) -> u64 {
-        if self.writes == 0 {
-            0
-        } else {
-            self.write_bytes / self.writes
-        }
+        self.write_bytes.checked_div(self.writes).unwrap_or(0)
     }
     #[inline]
     pub fn read_aligned_ratio(&self) -> (u64, u64) {
@@ -131,7 +123,7 @@ fn fmt_pct(numer: u64, denom: u64, f: &mut core::fmt::Formatter<'_>) -> core::fm
     let pct = (numer as f64) * 100.0 / (denom as f64);
     // avoid pulling in formatting heavy machinery; keep simple
     // show with 0 or 1 decimal depending on size
-    if pct.fract() == 0.0 {
+    if pct == (pct as u64) as f64 {
         write!(f, "{}%", pct as u64)
     } else {
         write!(f, "{pct:.1}%")
@@ -221,6 +213,15 @@ impl<'a, IO: RimIO + ?Sized> IOCounter<'a, IO> {
     pub fn snapshot(&self) -> IoStats {
         self.stats
     }
+    /// Counters accumulated since creation (or the last `reset_stats`).
+    #[inline]
+    pub fn stats(&self) -> &IoStats {
+        &self.stats
+    }
+    #[inline]
+    pub fn reset_stats(&mut self) {
+        self.stats.reset();
+    }
     #[inline]
     pub fn into_inner(self) -> &'a mut IO {
         self.inner
@@ -280,6 +281,16 @@ impl<'a, IO: RimIO + ?Sized> RimIO for IOCounter<'a, IO> {
     fn partition_offset(&self) -> u64 {
         self.inner.partition_offset()
     }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        self.inner.flush_persists()
+    }
+
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        self.inner.len()
+    }
 }
 
 pub trait IOTracer {
@@ -293,6 +304,21 @@ pub struct TracingIO<'a, IO: RimIO + ?Sized, Tr: IOTracer> {
     tracer: Tr,
 }
 
+impl<'a, IO: RimIO + ?Sized, Tr: IOTracer> TracingIO<'a, IO, Tr> {
+    #[inline]
+    pub fn new(inner: &'a mut IO, tracer: Tr) -> Self {
+        Self { inner, tracer }
+    }
+    #[inline]
+    pub fn tracer(&self) -> &Tr {
+        &self.tracer
+    }
+    #[inline]
+    pub fn into_parts(self) -> (&'a mut IO, Tr) {
+        (self.inner, self.tracer)
+    }
+}
+
 impl<'a, IO: RimIO + ?Sized, Tr: IOTracer> RimIO for TracingIO<'a, IO, Tr> {
     fn write_at(&mut self, off: u64, data: &[u8]) -> RimIOResult {
         self.tracer.on_write(off, data.len());
@@ -312,4 +338,14 @@ impl<'a, IO: RimIO + ?Sized, Tr: IOTracer> RimIO for TracingIO<'a, IO, Tr> {
     fn partition_offset(&self) -> u64 {
         self.inner.partition_offset()
     }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        self.inner.flush_persists()
+    }
+
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        self.inner.len()
+    }
 }
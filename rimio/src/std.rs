@@ -1,17 +1,31 @@
 // SPDX-License-Identifier: MIT
 
 #[cfg(feature = "std")]
-use std::io::{Error, Read, Seek, SeekFrom, Write};
+use std::io::{Error, ErrorKind, Read, Seek, SeekFrom, Write};
 
 #[cfg(feature = "std")]
 use crate::RimIOSetLen;
 use crate::{RimIO, RimIOError, RimIOResult};
 
+/// Zeroes an absolute byte range of the backing stream, see [`StdRimIO::with_hole_punch`].
+#[cfg(feature = "std")]
+type ZeroRangeFn<T> = fn(&mut T, u64, u64) -> std::io::Result<bool>;
+
+/// Queries the size of the backing device, see [`StdRimIO::with_device_length`].
+#[cfg(feature = "std")]
+type DeviceLenFn<T> = fn(&mut T) -> std::io::Result<u64>;
+
 #[cfg(feature = "std")]
 #[derive(Debug)]
 pub struct StdRimIO<'a, T: Read + Write + Seek> {
     io: &'a mut T,
     partition_offset: u64,
+    /// Called after the userspace flush to push data to stable storage.
+    sync: Option<fn(&mut T) -> std::io::Result<()>>,
+    /// Deallocates an absolute byte range so it reads back as zeroes.
+    zero_range: Option<ZeroRangeFn<T>>,
+    /// Reports the device size when seeking to the end can't.
+    device_len: Option<DeviceLenFn<T>>,
 }
 
 #[cfg(feature = "std")]
@@ -21,6 +35,9 @@ impl<'a, T: Read + Write + Seek> StdRimIO<'a, T> {
         Self {
             io,
             partition_offset: 0,
+            sync: None,
+            zero_range: None,
+            device_len: None,
         }
     }
 
@@ -29,8 +46,290 @@ impl<'a, T: Read + Write + Seek> StdRimIO<'a, T> {
         Self {
             io,
             partition_offset,
+            sync: None,
+            zero_range: None,
+            device_len: None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> StdRimIO<'a, std::fs::File> {
+    /// Makes `flush` also call `File::sync_all`, so data and metadata reach
+    /// the device instead of lingering in the OS cache.
+    #[inline]
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = if sync {
+            Some(|f: &mut std::fs::File| f.sync_all())
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Lets large `zero_fill`s leave holes instead of writing zeroes, keeping
+    /// sparse images sparse.
+    ///
+    /// Ranges past the end of the file are covered by extending it; on Linux,
+    /// ranges inside it are released with `fallocate(FALLOC_FL_PUNCH_HOLE)`.
+    #[inline]
+    pub fn with_hole_punch(mut self, punch: bool) -> Self {
+        self.zero_range = if punch { Some(zero_range_file) } else { None };
+        self
+    }
+
+    /// Makes `len` ask the OS for the device size.
+    ///
+    /// Needed for Windows raw disks (`\\.\PhysicalDriveN`), which can't be
+    /// seeked to their end: the size comes from `IOCTL_DISK_GET_LENGTH_INFO`.
+    /// Elsewhere seeking already reports the device size and this is a no-op.
+    #[inline]
+    pub fn with_device_length(mut self, device: bool) -> Self {
+        self.device_len = if device { DEVICE_LEN } else { None };
+        self
+    }
+
+    /// Opens a disk or image for raw read/write access.
+    ///
+    /// On Windows, device paths (`\\.\PhysicalDriveN`, `\\.\E:`) are opened
+    /// with read/write sharing, as the OS requires, then locked and dismounted
+    /// (`FSCTL_LOCK_VOLUME`, `FSCTL_DISMOUNT_VOLUME`) so no mounted file system
+    /// writes behind our back; the lock lasts until the file is closed.
+    pub fn open_device(path: &std::path::Path) -> std::io::Result<std::fs::File> {
+        let mut opts = std::fs::OpenOptions::new();
+        opts.read(true).write(true);
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::OpenOptionsExt;
+
+            let device = path.to_str().is_some_and(is_windows_device_path);
+            if device {
+                opts.share_mode(win::FILE_SHARE_READ | win::FILE_SHARE_WRITE);
+            }
+            let file = opts.open(path)?;
+            if device {
+                win::lock_and_dismount(&file)?;
+            }
+            Ok(file)
+        }
+        #[cfg(not(windows))]
+        opts.open(path)
+    }
+
+    /// Whether the backing device is removable (USB stick, SD card, ...)
+    /// rather than a fixed internal drive.
+    ///
+    /// Uses sysfs on Linux and `IOCTL_STORAGE_QUERY_PROPERTY` on Windows.
+    /// `None` when unknown: regular image files and failed queries.
+    ///
+    /// macOS is not supported (it would need IOKit) and always gets `None`,
+    /// as do other platforms.
+    pub fn is_removable(&self) -> Option<bool> {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+            let meta = self.io.metadata().ok()?;
+            if !meta.file_type().is_block_device() {
+                return None;
+            }
+            let rdev = meta.rdev();
+            let dev = std::path::PathBuf::from(format!(
+                "/sys/dev/block/{}:{}",
+                libc::major(rdev),
+                libc::minor(rdev)
+            ));
+            // Partitions carry no `removable` flag: ask their parent disk.
+            let mut disk = std::fs::canonicalize(&dev).ok()?;
+            if disk.join("partition").exists() {
+                disk.pop();
+            }
+            let flag = std::fs::read_to_string(disk.join("removable")).ok()?;
+            parse_sysfs_removable(&flag, disk.to_str()?)
+        }
+        #[cfg(windows)]
+        {
+            win::is_removable(self.io)
+        }
+        #[cfg(not(any(target_os = "linux", windows)))]
+        {
+            None
+        }
+    }
+}
+
+/// Reads a sysfs block device's `removable` attribute.
+///
+/// USB disks often report `0` (they are "fixed" media behind a removable
+/// bus), so anything whose sysfs path goes through a USB controller counts
+/// as removable too.
+#[cfg(feature = "std")]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_sysfs_removable(flag: &str, sysfs_path: &str) -> Option<bool> {
+    let removable = match flag.trim() {
+        "1" => true,
+        "0" => false,
+        _ => return None,
+    };
+    Some(removable || sysfs_path.split('/').any(|c| c.starts_with("usb")))
+}
+
+/// Reads a `STORAGE_DEVICE_DESCRIPTOR`: `RemovableMedia` (byte 10) or a
+/// USB / SD / MMC `BusType` (u32 at byte 28) make the device removable.
+#[cfg(feature = "std")]
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_storage_descriptor(desc: &[u8]) -> Option<bool> {
+    const BUS_TYPE_USB: u32 = 0x07;
+    const BUS_TYPE_SD: u32 = 0x0C;
+    const BUS_TYPE_MMC: u32 = 0x0D;
+
+    let removable_media = *desc.get(10)? != 0;
+    let bus = u32::from_le_bytes(desc.get(28..32)?.try_into().ok()?);
+    Some(removable_media || matches!(bus, BUS_TYPE_USB | BUS_TYPE_SD | BUS_TYPE_MMC))
+}
+
+#[cfg(all(feature = "std", windows))]
+const DEVICE_LEN: Option<DeviceLenFn<std::fs::File>> = Some(win::device_length);
+#[cfg(all(feature = "std", not(windows)))]
+const DEVICE_LEN: Option<DeviceLenFn<std::fs::File>> = None;
+
+/// Whether `path` names a Windows device namespace object (`\\.\...`).
+#[cfg(feature = "std")]
+pub fn is_windows_device_path(path: &str) -> bool {
+    path.starts_with(r"\\.\")
+}
+
+/// Disk number of a `\\.\PhysicalDriveN` path (case-insensitive), as shown
+/// by `Get-Disk` / `diskpart`.
+#[cfg(feature = "std")]
+pub fn physical_drive_number(path: &str) -> Option<u32> {
+    const PREFIX: &str = r"\\.\PhysicalDrive";
+    let head = path.get(..PREFIX.len())?;
+    if !head.eq_ignore_ascii_case(PREFIX) {
+        return None;
+    }
+    let digits = &path[PREFIX.len()..];
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+#[cfg(all(feature = "std", windows))]
+mod win {
+    use std::ffi::c_void;
+    use std::fs::File;
+    use std::io::Error;
+    use std::os::windows::io::AsRawHandle;
+
+    pub const FILE_SHARE_READ: u32 = 0x0000_0001;
+    pub const FILE_SHARE_WRITE: u32 = 0x0000_0002;
+
+    const IOCTL_DISK_GET_LENGTH_INFO: u32 = 0x0007_405C;
+    const FSCTL_LOCK_VOLUME: u32 = 0x0009_0018;
+    const FSCTL_DISMOUNT_VOLUME: u32 = 0x0009_0020;
+    const IOCTL_STORAGE_QUERY_PROPERTY: u32 = 0x002D_1400;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn DeviceIoControl(
+            device: *mut c_void,
+            code: u32,
+            in_buf: *mut c_void,
+            in_size: u32,
+            out_buf: *mut c_void,
+            out_size: u32,
+            returned: *mut u32,
+            overlapped: *mut c_void,
+        ) -> i32;
+    }
+
+    fn ioctl(file: &File, code: u32, input: &[u8], out: &mut [u8]) -> std::io::Result<()> {
+        let mut returned = 0u32;
+        // SAFETY: the handle is owned by `file` for the whole call; `input` is
+        // only read and both buffers are valid for their advertised sizes.
+        let ok = unsafe {
+            DeviceIoControl(
+                file.as_raw_handle(),
+                code,
+                input.as_ptr().cast_mut().cast(),
+                input.len() as u32,
+                out.as_mut_ptr().cast(),
+                out.len() as u32,
+                &mut returned,
+                core::ptr::null_mut(),
+            )
+        };
+        if ok == 0 {
+            return Err(Error::last_os_error());
         }
+        Ok(())
+    }
+
+    /// `GET_LENGTH_INFORMATION.Length` of a disk or volume handle.
+    pub fn device_length(file: &mut File) -> std::io::Result<u64> {
+        let mut len = [0u8; 8];
+        ioctl(file, IOCTL_DISK_GET_LENGTH_INFO, &[], &mut len)?;
+        Ok(u64::from_le_bytes(len))
+    }
+
+    pub fn lock_and_dismount(file: &File) -> std::io::Result<()> {
+        ioctl(file, FSCTL_LOCK_VOLUME, &[], &mut [])?;
+        ioctl(file, FSCTL_DISMOUNT_VOLUME, &[], &mut [])
+    }
+
+    /// `STORAGE_DEVICE_DESCRIPTOR` of a disk handle, see [`super::parse_storage_descriptor`].
+    pub fn is_removable(file: &File) -> Option<bool> {
+        // STORAGE_PROPERTY_QUERY { StorageDeviceProperty, PropertyStandardQuery }
+        let query = [0u8; 12];
+        let mut desc = [0u8; 64];
+        ioctl(file, IOCTL_STORAGE_QUERY_PROPERTY, &query, &mut desc).ok()?;
+        super::parse_storage_descriptor(&desc)
+    }
+}
+
+/// Zeroes `[offset, offset + len)` of `file` without writing data.
+///
+/// Returns `false` (leaving the file untouched) when the range can't be handled.
+#[cfg(feature = "std")]
+fn zero_range_file(file: &mut std::fs::File, offset: u64, len: u64) -> std::io::Result<bool> {
+    let end = offset
+        .checked_add(len)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "zero range overflow"))?;
+    let file_len = file.metadata()?.len();
+
+    if offset < file_len {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::fd::AsRawFd;
+
+            let punch = end.min(file_len) - offset;
+            // SAFETY: plain syscall on a descriptor owned by `file` for the whole call.
+            let ret = unsafe {
+                libc::fallocate(
+                    file.as_raw_fd(),
+                    libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+                    offset as libc::off_t,
+                    punch as libc::off_t,
+                )
+            };
+            if ret != 0 {
+                let err = Error::last_os_error();
+                return match err.raw_os_error() {
+                    Some(libc::EOPNOTSUPP) | Some(libc::ENOSYS) => Ok(false),
+                    _ => Err(err),
+                };
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        return Ok(false);
+    }
+
+    if end > file_len {
+        file.set_len(end)?;
     }
+    Ok(true)
 }
 
 #[cfg(feature = "std")]
@@ -38,19 +337,51 @@ impl<'a, T: Read + Write + Seek> RimIO for StdRimIO<'a, T> {
     fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
         let abs_offset = self.partition_offset + offset;
         self.io.seek(SeekFrom::Start(abs_offset))?;
-        self.io.write_all(data)?;
+        let mut done = 0;
+        while done < data.len() {
+            match self.io.write(&data[done..]) {
+                Ok(0) => {
+                    return Err(RimIOError::UnexpectedEof {
+                        offset,
+                        requested: data.len(),
+                        got: done,
+                    });
+                }
+                Ok(n) => done += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
         Ok(())
     }
 
     fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
         let abs_offset = self.partition_offset + offset;
         self.io.seek(SeekFrom::Start(abs_offset))?;
-        self.io.read_exact(buf)?;
+        // Like `read_exact`, but report how far we got when the stream ends early.
+        let mut done = 0;
+        while done < buf.len() {
+            match self.io.read(&mut buf[done..]) {
+                Ok(0) => {
+                    return Err(RimIOError::UnexpectedEof {
+                        offset,
+                        requested: buf.len(),
+                        got: done,
+                    });
+                }
+                Ok(n) => done += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
         Ok(())
     }
 
     fn flush(&mut self) -> RimIOResult {
         self.io.flush()?;
+        if let Some(sync) = self.sync {
+            sync(self.io)?;
+        }
         Ok(())
     }
 
@@ -64,6 +395,27 @@ impl<'a, T: Read + Write + Seek> RimIO for StdRimIO<'a, T> {
     fn partition_offset(&self) -> u64 {
         self.partition_offset
     }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        self.sync.is_some()
+    }
+
+    fn try_zero_range(&mut self, offset: u64, len: u64) -> RimIOResult<bool> {
+        match self.zero_range {
+            Some(zero_range) => Ok(zero_range(self.io, self.partition_offset + offset, len)?),
+            None => Ok(false),
+        }
+    }
+
+    /// Stream length, found by seeking to its end (also works on block devices,
+    /// whose metadata reports 0).
+    fn len(&mut self) -> RimIOResult<u64> {
+        match self.device_len {
+            Some(device_len) => Ok(device_len(self.io)?),
+            None => Ok(self.io.seek(SeekFrom::End(0))?),
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -146,6 +498,34 @@ mod test {
         assert_eq!(input, output);
     }
 
+    #[test]
+    fn test_multi_matches_per_offset() {
+        let mut file = tempfile().unwrap();
+        let mut io = StdRimIO::new_with_offset(&mut file, 512);
+
+        let cluster_size = 16;
+        // Two contiguous runs, a backwards jump and a lone cluster.
+        let offsets = [64u64, 80, 96, 256, 272, 0, 128];
+        let input: Vec<u8> = (0..offsets.len() * cluster_size).map(|i| i as u8).collect();
+        io.write_multi_at(&offsets, cluster_size, &input).unwrap();
+
+        let mut multi = vec![0u8; input.len()];
+        io.read_multi_at(&offsets, cluster_size, &mut multi)
+            .unwrap();
+
+        let mut single = vec![0u8; input.len()];
+        for (off, chunk) in offsets.iter().zip(single.chunks_mut(cluster_size)) {
+            io.read_at(*off, chunk).unwrap();
+        }
+
+        assert_eq!(multi, input);
+        assert_eq!(single, input);
+        assert!(
+            io.read_multi_at(&offsets, cluster_size, &mut multi[1..])
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_chunks_streamed_rw() {
         let mut file = tempfile().unwrap();
@@ -165,6 +545,89 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_flush_persists() {
+        let mut file = tempfile().unwrap();
+        let io = StdRimIO::new(&mut file);
+        assert!(!io.flush_persists());
+        let mut io = io.with_sync(true);
+        assert!(io.flush_persists());
+        assert!(IOCounter::new(&mut io).flush_persists());
+    }
+
+    #[test]
+    fn test_flush_with_sync() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sync.img");
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+        let mut io = StdRimIO::new(&mut file).with_sync(true);
+        io.write_at(512, &[0x5A; 64]).unwrap();
+        io.flush().unwrap();
+
+        let mut reopened = std::fs::File::open(&path).unwrap();
+        reopened.seek(SeekFrom::Start(512)).unwrap();
+        let mut buf = [0u8; 64];
+        reopened.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [0x5A; 64]);
+    }
+
+    #[test]
+    fn test_short_file_read() {
+        let mut file = tempfile().unwrap();
+        file.write_all(&[0x11; 100]).unwrap();
+        let mut io = StdRimIO::new(&mut file);
+
+        let mut buf = [0u8; 64];
+        assert_eq!(
+            io.read_exact_at(60, &mut buf),
+            Err(RimIOError::UnexpectedEof {
+                offset: 60,
+                requested: 64,
+                got: 40,
+            })
+        );
+        assert_eq!(
+            io.read_at(200, &mut buf),
+            Err(RimIOError::UnexpectedEof {
+                offset: 200,
+                requested: 64,
+                got: 0,
+            })
+        );
+        io.read_exact_at(36, &mut buf).unwrap();
+        io.write_all_at(100, &[0x22; 8]).unwrap();
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_zero_fill_hole_punch() {
+        use std::os::unix::fs::MetadataExt;
+        const GIB: usize = 1 << 30;
+
+        let mut file = tempfile().unwrap();
+        let mut io = StdRimIO::new(&mut file).with_hole_punch(true);
+        io.zero_fill(0, GIB).unwrap();
+        let meta = file.metadata().unwrap();
+        assert_eq!(meta.len(), GIB as u64);
+        assert!(meta.blocks() * 512 < 1 << 20);
+
+        // Punching over existing data must still read back zeroes.
+        let mut io = StdRimIO::new_with_offset(&mut file, 4096).with_hole_punch(true);
+        io.write_at(0, &[0xFF; 256 * 1024]).unwrap();
+        io.zero_fill(1000, 128 * 1024).unwrap();
+        let mut buf = vec![0xAA; 256 * 1024];
+        io.read_at(0, &mut buf).unwrap();
+        assert!(buf[..1000].iter().all(|&b| b == 0xFF));
+        assert!(buf[1000..1000 + 128 * 1024].iter().all(|&b| b == 0));
+        assert!(buf[1000 + 128 * 1024..].iter().all(|&b| b == 0xFF));
+    }
+
     #[test]
     fn test_zero_fill() {
         let mut file = tempfile().unwrap();
@@ -178,4 +641,75 @@ mod test {
 
         assert_eq!(buf, [0u8; 8]);
     }
+
+    #[test]
+    fn test_len() {
+        let mut file = tempfile().unwrap();
+        let mut io = StdRimIO::new_with_offset(&mut file, 512);
+        assert_eq!(io.len().unwrap(), 0);
+
+        io.write_at(1000, &[1; 24]).unwrap();
+        assert_eq!(io.len().unwrap(), 1536);
+
+        // Position-independent: reads after len() still land where expected.
+        let mut b = [0u8; 1];
+        io.read_at(1000, &mut b).unwrap();
+        assert_eq!(b, [1]);
+    }
+
+    #[test]
+    fn test_device_paths() {
+        assert_eq!(physical_drive_number(r"\\.\PhysicalDrive1"), Some(1));
+        assert_eq!(physical_drive_number(r"\\.\physicaldrive12"), Some(12));
+        assert_eq!(physical_drive_number(r"\\.\PhysicalDrive"), None);
+        assert_eq!(physical_drive_number(r"\\.\PhysicalDrive1a"), None);
+        assert_eq!(physical_drive_number(r"\\.\E:"), None);
+        assert_eq!(physical_drive_number("/dev/sdb"), None);
+
+        assert!(is_windows_device_path(r"\\.\PhysicalDrive0"));
+        assert!(is_windows_device_path(r"\\.\E:"));
+        assert!(!is_windows_device_path(r"C:\images\disk.img"));
+        assert!(!is_windows_device_path("/dev/sdb"));
+    }
+
+    #[test]
+    fn test_open_device_on_image() {
+        let mut tmp = tempfile::NamedTempFile::new().unwrap();
+        tmp.as_file_mut().set_len(4096).unwrap();
+
+        let mut file = StdRimIO::open_device(tmp.path()).unwrap();
+        let mut io = StdRimIO::new(&mut file).with_device_length(true);
+        io.write_at(512, b"rim").unwrap();
+        assert_eq!(io.len().unwrap(), 4096);
+
+        let mut out = [0u8; 3];
+        io.read_at(512, &mut out).unwrap();
+        assert_eq!(&out, b"rim");
+    }
+
+    #[test]
+    fn test_removable_parsing() {
+        let sd = "/sys/devices/pci0000:00/0000:00:14.0/mmc_host/mmc0/mmc0:aaaa/block/mmcblk0";
+        let stick = "/sys/devices/pci0000:00/0000:00:14.0/usb2/2-1/2-1:1.0/host6/target6:0:0/6:0:0:0/block/sdb";
+        let nvme = "/sys/devices/pci0000:00/0000:00:1d.0/0000:3d:00.0/nvme/nvme0/nvme0n1";
+        assert_eq!(parse_sysfs_removable("1\n", sd), Some(true));
+        assert_eq!(parse_sysfs_removable("0\n", stick), Some(true));
+        assert_eq!(parse_sysfs_removable("0\n", nvme), Some(false));
+        assert_eq!(parse_sysfs_removable("", nvme), None);
+
+        // STORAGE_DEVICE_DESCRIPTOR: RemovableMedia at 10, BusType at 28.
+        let mut desc = [0u8; 40];
+        desc[28] = 0x11; // NVMe
+        assert_eq!(parse_storage_descriptor(&desc), Some(false));
+        desc[28] = 0x07; // USB
+        assert_eq!(parse_storage_descriptor(&desc), Some(true));
+        desc[28] = 0x0B; // SATA
+        desc[10] = 1;
+        assert_eq!(parse_storage_descriptor(&desc), Some(true));
+        assert_eq!(parse_storage_descriptor(&desc[..20]), None);
+
+        // Image files are not devices.
+        let mut file = tempfile().unwrap();
+        assert_eq!(StdRimIO::new(&mut file).is_removable(), None);
+    }
 }
@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: MIT
+
+use crate::{RimIO, RimIOResult};
+
+/// Wrapper mirroring every write to a second backend.
+///
+/// Writes go to `primary` then `secondary`, reads are served by `primary`
+/// only. Useful to build an image while feeding a hasher, a counter or a
+/// second file in the same pass.
+pub struct TeeRimIO<'a, A: RimIO + ?Sized, B: RimIO + ?Sized> {
+    primary: &'a mut A,
+    secondary: &'a mut B,
+}
+
+impl<'a, A: RimIO + ?Sized, B: RimIO + ?Sized> TeeRimIO<'a, A, B> {
+    #[inline]
+    pub fn new(primary: &'a mut A, secondary: &'a mut B) -> Self {
+        Self { primary, secondary }
+    }
+
+    #[inline]
+    pub fn into_parts(self) -> (&'a mut A, &'a mut B) {
+        (self.primary, self.secondary)
+    }
+}
+
+impl<'a, A: RimIO + ?Sized, B: RimIO + ?Sized> RimIO for TeeRimIO<'a, A, B> {
+    #[inline]
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
+        self.primary.write_at(offset, data)?;
+        self.secondary.write_at(offset, data)
+    }
+
+    #[inline]
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
+        self.primary.read_at(offset, buf)
+    }
+
+    /// Flushes both backends, even if the primary fails.
+    #[inline]
+    fn flush(&mut self) -> RimIOResult {
+        let primary = self.primary.flush();
+        let secondary = self.secondary.flush();
+        primary.and(secondary)
+    }
+
+    #[inline]
+    fn set_offset(&mut self, partition_offset: u64) -> u64 {
+        self.secondary.set_offset(partition_offset);
+        self.primary.set_offset(partition_offset)
+    }
+
+    #[inline]
+    fn partition_offset(&self) -> u64 {
+        self.primary.partition_offset()
+    }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        self.primary.flush_persists() && self.secondary.flush_persists()
+    }
+
+    /// Length of the primary device.
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        self.primary.len()
+    }
+}
+
+#[cfg(all(test, feature = "mem"))]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_tee_mirrors_writes() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        let mut ma = MemRimIO::new(&mut a);
+        let mut mb = MemRimIO::new(&mut b);
+        let mut io = TeeRimIO::new(&mut ma, &mut mb);
+
+        io.write_at(4, b"mirror").unwrap();
+        io.set_offset(8);
+        io.write_at(8, b"!").unwrap();
+        io.flush().unwrap();
+
+        let (ma, _) = io.into_parts();
+        assert_eq!(ma.partition_offset(), 8);
+        assert_eq!(a, b);
+        assert_eq!(&a[4..10], b"mirror");
+        assert_eq!(a[16], b'!');
+    }
+}
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: MIT
+
+use std::time::Duration;
+
+use crate::{RimIO, RimIOResult};
+
+/// Wrapper that slows transfers down to a fixed bandwidth.
+///
+/// Each `read_at`/`write_at` sleeps for as long as the transferred bytes would
+/// take at `bytes_per_sec`, then forwards to the inner IO. Meant to exercise
+/// progress reporting and time budgets in tests and tools.
+pub struct ThrottledRimIO<'a, IO: RimIO + ?Sized> {
+    inner: &'a mut IO,
+    bytes_per_sec: u64,
+}
+
+impl<'a, IO: RimIO + ?Sized> ThrottledRimIO<'a, IO> {
+    /// Throttles to `bytes_per_sec` (0 disables throttling).
+    #[inline]
+    pub fn new(inner: &'a mut IO, bytes_per_sec: u64) -> Self {
+        Self {
+            inner,
+            bytes_per_sec,
+        }
+    }
+
+    /// Throttles to `mb_per_sec` MiB/s.
+    #[inline]
+    pub fn with_mb_per_sec(inner: &'a mut IO, mb_per_sec: f64) -> Self {
+        Self::new(inner, (mb_per_sec * (1024.0 * 1024.0)) as u64)
+    }
+
+    #[inline]
+    pub fn inner(&mut self) -> &mut IO {
+        self.inner
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> &'a mut IO {
+        self.inner
+    }
+
+    /// Time a transfer of `len` bytes takes at the configured rate.
+    #[inline]
+    pub fn delay_for(&self, len: usize) -> Duration {
+        if self.bytes_per_sec == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(len as f64 / self.bytes_per_sec as f64)
+    }
+}
+
+impl<'a, IO: RimIO + ?Sized> RimIO for ThrottledRimIO<'a, IO> {
+    #[inline]
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> RimIOResult {
+        std::thread::sleep(self.delay_for(data.len()));
+        self.inner.write_at(offset, data)
+    }
+
+    #[inline]
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> RimIOResult {
+        std::thread::sleep(self.delay_for(buf.len()));
+        self.inner.read_at(offset, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> RimIOResult {
+        self.inner.flush()
+    }
+
+    #[inline]
+    fn set_offset(&mut self, partition_offset: u64) -> u64 {
+        self.inner.set_offset(partition_offset)
+    }
+
+    #[inline]
+    fn partition_offset(&self) -> u64 {
+        self.inner.partition_offset()
+    }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        self.inner.flush_persists()
+    }
+
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        self.inner.len()
+    }
+}
+
+#[cfg(all(test, feature = "mem"))]
+mod test {
+    use std::time::Instant;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn test_throttled_transfer_time() {
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut mem = MemRimIO::new(&mut buf);
+        // 64 KiB written + 64 KiB read at 1 MiB/s => at least 125 ms.
+        let mut io = ThrottledRimIO::with_mb_per_sec(&mut mem, 1.0);
+
+        let t0 = Instant::now();
+        io.write_at(0, &[0xAB; 32 * 1024]).unwrap();
+        io.write_at(32 * 1024, &[0xCD; 32 * 1024]).unwrap();
+        let mut out = vec![0u8; 64 * 1024];
+        io.read_at(0, &mut out).unwrap();
+        assert!(t0.elapsed() >= std::time::Duration::from_millis(125));
+
+        assert_eq!(out[0], 0xAB);
+        assert_eq!(out[64 * 1024 - 1], 0xCD);
+        assert!(
+            ThrottledRimIO::new(&mut mem, 0)
+                .delay_for(1 << 30)
+                .is_zero()
+        );
+    }
+}
@@ -63,13 +63,15 @@ impl UefiRimIO {
 
     #[inline]
     fn check_bounds(&self, abs_off: u64, len: usize) -> RimIOResult {
-        let end = abs_off
-            .checked_add(len as u64)
-            .ok_or(RimIOError::OutOfBounds)?;
-        if end > self.media_len() {
-            return Err(RimIOError::OutOfBounds);
+        let size = self.media_len();
+        match abs_off.checked_add(len as u64) {
+            Some(end) if end <= size => Ok(()),
+            _ => Err(RimIOError::OutOfBounds {
+                offset: abs_off,
+                len,
+                size,
+            }),
         }
-        Ok(())
     }
 
     #[inline]
@@ -276,4 +278,14 @@ impl RimIO for UefiRimIO {
     fn partition_offset(&self) -> u64 {
         self.partition_offset
     }
+
+    #[inline]
+    fn flush_persists(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn len(&mut self) -> RimIOResult<u64> {
+        Ok(self.media_len())
+    }
 }
@@ -105,6 +105,77 @@ impl Default for DiffLogOptions {
     }
 }
 
+/// Rounds `current` up to the next multiple of `granularity` (no-op for 0).
+///
+/// Saturates at the largest multiple representable in a `u64`.
+#[inline]
+pub fn round_up_len(current: u64, granularity: u64) -> u64 {
+    if granularity == 0 {
+        return current;
+    }
+    match current.checked_next_multiple_of(granularity) {
+        Some(v) => v,
+        None => u64::MAX - u64::MAX % granularity,
+    }
+}
+
+//
+// crc32_region_parallel
+//
+
+/// Multi-threaded [`RimIOExt::crc32_region`] over `[offset, offset + len)`.
+///
+/// The region is split into up to `threads` block-aligned chunks (0 = available
+/// parallelism). Each chunk is hashed on its own thread through a fresh handle
+/// from `open`, and the partial CRCs are merged with a CRC32 combine, so the
+/// result is identical to the serial one.
+#[cfg(all(feature = "std", feature = "crc"))]
+pub fn crc32_region_parallel<T, F>(
+    open: F,
+    offset: u64,
+    len: u64,
+    threads: usize,
+) -> RimIOResult<u32>
+where
+    T: std::io::Read + std::io::Write + std::io::Seek,
+    F: Fn() -> std::io::Result<T> + Sync,
+{
+    let threads = match threads {
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    } as u64;
+    let chunk = round_up_len(len.div_ceil(threads), crate::BLOCK_BUF_SIZE as u64).max(1);
+
+    let parts: Vec<RimIOResult<crc32fast::Hasher>> = std::thread::scope(|s| {
+        let open = &open;
+        let workers: Vec<_> = (0..len.div_ceil(chunk))
+            .map(|i| {
+                let start = i * chunk;
+                let n = chunk.min(len - start);
+                s.spawn(move || {
+                    let mut handle = open()?;
+                    let mut io = StdRimIO::new(&mut handle);
+                    let crc = io.crc32_region(offset + start, n)?;
+                    Ok(crc32fast::Hasher::new_with_initial_len(crc, n))
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|w| {
+                w.join()
+                    .unwrap_or(Err(RimIOError::Other("crc32 worker panicked")))
+            })
+            .collect()
+    });
+
+    let mut hasher = crc32fast::Hasher::new();
+    for part in parts {
+        hasher.combine(&part?);
+    }
+    Ok(hasher.finalize())
+}
+
 //
 // compare_streamed_bytes
 //
@@ -538,6 +609,69 @@ mod tests {
         (MemRimIO::new(a), MemRimIO::new(b))
     }
 
+    #[test]
+    #[cfg(feature = "crc")]
+    fn crc32_parallel_matches_serial() {
+        use std::io::Write;
+
+        let data: Vec<u8> = (0..300_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+        let path = file.path().to_path_buf();
+        let open = || {
+            std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(&path)
+        };
+
+        let mut serial_file = open().unwrap();
+        let mut io = StdRimIO::new(&mut serial_file);
+        for (offset, len) in [(0, data.len() as u64), (123, 250_001), (7, 5), (0, 0)] {
+            let serial = io.crc32_region(offset, len).unwrap();
+            assert_eq!(
+                serial,
+                crc32fast::hash(&data[offset as usize..(offset + len) as usize])
+            );
+            for threads in [0, 1, 3, 8] {
+                let parallel = crc32_region_parallel(open, offset, len, threads).unwrap();
+                assert_eq!(
+                    parallel, serial,
+                    "offset={offset} len={len} threads={threads}"
+                );
+            }
+        }
+
+        assert!(crc32_region_parallel(open, 299_000, 4096, 4).is_err());
+    }
+
+    #[test]
+    fn round_up() {
+        assert_eq!(round_up_len(0, 512), 0);
+        assert_eq!(round_up_len(1, 512), 512);
+        assert_eq!(round_up_len(512, 512), 512);
+        assert_eq!(round_up_len(513, 512), 1024);
+        assert_eq!(round_up_len(7, 0), 7);
+    }
+
+    #[test]
+    fn set_len_aligned_rounds_to_mib() {
+        const MIB: u64 = 1 << 20;
+        let mut io = MemRimIO::new_growable();
+        io.write_at(0, &[0xAA; 1000]).unwrap();
+        // Stale data past the requested length must not survive
+        io.write_at(5000, &[0xBB; 16]).unwrap();
+
+        assert_eq!(io.set_len_aligned(1000, MIB).unwrap(), MIB);
+        assert_eq!(io.as_slice().len(), MIB as usize);
+        assert!(io.as_slice()[..1000].iter().all(|&b| b == 0xAA));
+        assert!(io.as_slice()[1000..].iter().all(|&b| b == 0));
+
+        assert_eq!(io.set_len_aligned(MIB, MIB).unwrap(), MIB);
+    }
+
     #[test]
     fn compare_equal() {
         let (mut io1, mut io2) = make_ios(1024);
@@ -77,6 +77,28 @@ pub enum GptError {
     },
     PrimaryGptCorrupted,
     BackupGptCorrupted,
+    ZeroLength {
+        start: u64,
+    },
+    /// Entries `a` and `b` share the same unique partition GUID.
+    DuplicateUniqueGuid {
+        a: usize,
+        b: usize,
+    },
+    /// Entry `index` reuses the disk GUID as its unique GUID.
+    DiskGuidCollision {
+        index: usize,
+    },
+    /// No aligned free gap of `sectors` sectors is left in the usable range.
+    NoFreeGap {
+        sectors: u64,
+    },
+    /// Every entry slot of the table is in use.
+    TableFull {
+        slots: u32,
+    },
+    /// No used entry matches the requested slot or name.
+    EntryNotFound,
 }
 
 impl GptError {
@@ -100,6 +122,12 @@ impl GptError {
             Overlap { .. } => "GPT: partition overlap detected",
             PrimaryGptCorrupted => "GPT: primary GPT is corrupted",
             BackupGptCorrupted => "GPT: backup GPT is corrupted",
+            ZeroLength { .. } => "GPT: zero-length partition",
+            DuplicateUniqueGuid { .. } => "GPT: duplicate unique partition GUID",
+            DiskGuidCollision { .. } => "GPT: partition GUID equals the disk GUID",
+            NoFreeGap { .. } => "GPT: no free gap large enough",
+            TableFull { .. } => "GPT: no free entry slot",
+            EntryNotFound => "GPT: no such partition entry",
         }
     }
 }
@@ -130,6 +158,9 @@ pub enum MbrError {
         b_start: u64,
         b_end: u64,
     },
+    NoSuchEntry {
+        index: usize,
+    },
 }
 
 impl MbrError {
@@ -144,6 +175,7 @@ impl MbrError {
             ProtectiveSizeMismatch { .. } => "MBR: protective size mismatch",
             UnsupportedType { .. } => "MBR: unsupported legacy type",
             Overlap { .. } => "MBR: partition overlap detected",
+            NoSuchEntry { .. } => "MBR: no such partition entry",
         }
     }
 }
@@ -281,7 +313,18 @@ impl core::fmt::Display for GptError {
                 b_start,
                 b_end
             ),
-            LbaOverflow | DiskTooSmallForAlignment | PrimaryGptCorrupted | BackupGptCorrupted => {
+            ZeroLength { start } => write!(f, "{} (start {})", self.msg(), start),
+            DuplicateUniqueGuid { a, b } => {
+                write!(f, "{} (entries {} and {})", self.msg(), a, b)
+            }
+            DiskGuidCollision { index } => write!(f, "{} (entry {})", self.msg(), index),
+            NoFreeGap { sectors } => write!(f, "{} ({} sectors)", self.msg(), sectors),
+            TableFull { slots } => write!(f, "{} ({} slots)", self.msg(), slots),
+            LbaOverflow
+            | DiskTooSmallForAlignment
+            | PrimaryGptCorrupted
+            | BackupGptCorrupted
+            | EntryNotFound => {
                 write!(f, "{}", self.msg())
             }
         }
@@ -336,6 +379,7 @@ impl core::fmt::Display for MbrError {
                 b_start,
                 b_end
             ),
+            NoSuchEntry { index } => write!(f, "{} (index {})", self.msg(), index),
         }
     }
 }
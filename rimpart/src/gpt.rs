@@ -10,7 +10,7 @@ use crate::DEFAULT_SECTOR_SIZE;
 use crate::guids::GptPartitionKind;
 use crate::{errors::*, io_ext::RimIOLbaExt};
 use rimio::prelude::*;
-use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+use zerocopy::{FromBytes, FromZeros, Immutable, IntoBytes, KnownLayout};
 
 pub const GPT_DEFAULT_ENTRY_SIZE: u32 = 128;
 pub const GPT_DEFAULT_HEADER_SIZE: u32 = 92;
@@ -21,6 +21,19 @@ pub const GPT_PRIMARY_HEADER_LBA: u64 = 1;
 pub const GPT_SIGNATURE: &[u8; 8] = b"EFI PART";
 pub const GPT_REVISION: u32 = 0x00010000;
 
+/// Platform required: firmware and tools must preserve the partition.
+pub const GPT_ATTR_REQUIRED: u64 = 1 << 0;
+/// EFI firmware must not produce a Block IO protocol for the partition.
+pub const GPT_ATTR_NO_BLOCK_IO: u64 = 1 << 1;
+/// Bootable for legacy BIOS (the GPT counterpart of the MBR active flag).
+pub const GPT_ATTR_LEGACY_BIOS_BOOTABLE: u64 = 1 << 2;
+/// Microsoft basic data: mounted read-only.
+pub const GPT_ATTR_READ_ONLY: u64 = 1 << 60;
+/// Microsoft basic data: hidden from the OS.
+pub const GPT_ATTR_HIDDEN: u64 = 1 << 62;
+/// Microsoft basic data: no drive letter assigned.
+pub const GPT_ATTR_NO_AUTOMOUNT: u64 = 1 << 63;
+
 #[inline]
 pub fn align_up(v: u64, a: u64) -> u64 {
     if v.is_multiple_of(a) {
@@ -39,6 +52,48 @@ pub fn align_lba_1m(sector_size: u64) -> u64 {
     ((1 << 20) / sector_size).max(1)
 }
 
+/// Aligned `(first_usable, last_usable)` range between the primary and backup
+/// entry tables, each `entries_sectors` long.
+///
+/// The first usable LBA never falls inside the primary table and the last one
+/// never reaches the backup table; a disk without room for one aligned block
+/// in between is rejected.
+fn usable_range(total_sectors: u64, entries_sectors: u64, align: u64) -> PartResult<(u64, u64)> {
+    let primary_end = GPT_PRIMARY_ENTRIES_LBA
+        .checked_add(entries_sectors)
+        .ok_or(GptError::LbaOverflow)?;
+    // backup header (1) + backup table
+    let backup_start = total_sectors
+        .checked_sub(1 + entries_sectors)
+        .ok_or(GptError::DiskTooSmallForAlignment)?;
+
+    let first_usable = primary_end
+        .checked_next_multiple_of(align)
+        .ok_or(GptError::LbaOverflow)?;
+    let last_usable = backup_start
+        .checked_sub(1)
+        .map(|raw_last| align_down(raw_last, align))
+        .ok_or(GptError::DiskTooSmallForAlignment)?;
+
+    if first_usable < primary_end || first_usable > last_usable || last_usable >= backup_start {
+        return Err(GptError::DiskTooSmallForAlignment.into());
+    }
+    Ok((first_usable, last_usable))
+}
+
+/// Generates a random (version 4) GUID in GPT on-disk byte order.
+///
+/// The version nibble lives in byte 7 and the RFC 4122 variant in byte 8,
+/// since the first three fields are stored little-endian. Fails when `rng`
+/// can't produce bytes.
+pub fn random_guid<R: crate::Rng + ?Sized>(rng: &mut R) -> PartResult<[u8; 16]> {
+    let mut guid = [0u8; 16];
+    rng.fill_bytes(&mut guid)?;
+    guid[7] = (guid[7] & 0x0F) | 0x40;
+    guid[8] = (guid[8] & 0x3F) | 0x80;
+    Ok(guid)
+}
+
 pub fn encode_gpt_name(name: &str) -> [u16; 36] {
     let mut buf = [0u16; 36];
     for (i, c) in name.encode_utf16().take(36).enumerate() {
@@ -47,6 +102,14 @@ pub fn encode_gpt_name(name: &str) -> [u16; 36] {
     buf
 }
 
+/// UTF-16LE characters of a GPT name up to the first NUL, unpaired
+/// surrogates replaced by U+FFFD.
+fn decode_name_chars(name: &[u16; 36]) -> impl Iterator<Item = char> + '_ {
+    let end = name.iter().position(|&c| c == 0).unwrap_or(36);
+    core::char::decode_utf16(name[..end].iter().copied())
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+}
+
 #[cfg(feature = "alloc")]
 pub fn decode_gpt_name(name: &[u16; 36]) -> String {
     let end = name.iter().position(|&c| c == 0).unwrap_or(36);
@@ -56,11 +119,7 @@ pub fn decode_gpt_name(name: &[u16; 36]) -> String {
 #[cfg(not(feature = "alloc"))]
 pub fn decode_gpt_name<'a>(name: &[u16; 36], buf: &'a mut [u8]) -> Result<&'a str, PartError> {
     let mut written = 0;
-    for &unit in name {
-        if unit == 0 {
-            break;
-        }
-        let ch = core::char::from_u32(unit as u32).unwrap_or(char::REPLACEMENT_CHARACTER);
+    for ch in decode_name_chars(name) {
         written += ch.encode_utf8(&mut buf[written..]).len();
     }
     core::str::from_utf8(&buf[..written]).map_err(|_| PartError::Other("UTF-8 error"))
@@ -118,6 +177,90 @@ impl GptEntry {
         !matches!(self.kind(), GptPartitionKind::Unknown(_))
     }
 
+    /// Partition name, decoded from UTF-16LE up to the first NUL.
+    #[cfg(feature = "alloc")]
+    pub fn name_string(&self) -> String {
+        decode_gpt_name(&self.name)
+    }
+
+    /// Whether the partition name is exactly `name` (case-sensitive), without allocating.
+    pub fn name_eq(&self, name: &str) -> bool {
+        decode_name_chars(&self.name).eq(name.chars())
+    }
+
+    /// Decodes the partition name into `out` without allocating.
+    ///
+    /// Returns the number of characters written; names longer than `out`
+    /// are truncated (36 characters always fit).
+    pub fn name_into(&self, out: &mut [char]) -> usize {
+        out.iter_mut()
+            .zip(decode_name_chars(&self.name))
+            .map(|(slot, c)| *slot = c)
+            .count()
+    }
+
+    #[inline]
+    pub fn has_attribute(&self, flag: u64) -> bool {
+        self.attributes & flag == flag
+    }
+
+    #[inline]
+    pub fn set_attribute(&mut self, flag: u64, on: bool) {
+        if on {
+            self.attributes |= flag;
+        } else {
+            self.attributes &= !flag;
+        }
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.has_attribute(GPT_ATTR_REQUIRED)
+    }
+
+    pub fn set_required(&mut self, on: bool) {
+        self.set_attribute(GPT_ATTR_REQUIRED, on)
+    }
+
+    pub fn is_no_block_io(&self) -> bool {
+        self.has_attribute(GPT_ATTR_NO_BLOCK_IO)
+    }
+
+    pub fn set_no_block_io(&mut self, on: bool) {
+        self.set_attribute(GPT_ATTR_NO_BLOCK_IO, on)
+    }
+
+    pub fn is_legacy_bios_bootable(&self) -> bool {
+        self.has_attribute(GPT_ATTR_LEGACY_BIOS_BOOTABLE)
+    }
+
+    pub fn set_legacy_bios_bootable(&mut self, on: bool) {
+        self.set_attribute(GPT_ATTR_LEGACY_BIOS_BOOTABLE, on)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.has_attribute(GPT_ATTR_READ_ONLY)
+    }
+
+    pub fn set_read_only(&mut self, on: bool) {
+        self.set_attribute(GPT_ATTR_READ_ONLY, on)
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.has_attribute(GPT_ATTR_HIDDEN)
+    }
+
+    pub fn set_hidden(&mut self, on: bool) {
+        self.set_attribute(GPT_ATTR_HIDDEN, on)
+    }
+
+    pub fn is_no_automount(&self) -> bool {
+        self.has_attribute(GPT_ATTR_NO_AUTOMOUNT)
+    }
+
+    pub fn set_no_automount(&mut self, on: bool) {
+        self.set_attribute(GPT_ATTR_NO_AUTOMOUNT, on)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.type_guid.iter().all(|&b| b == 0)
             && self.unique_guid.iter().all(|&b| b == 0)
@@ -131,6 +274,12 @@ impl GptEntry {
         if self.is_empty() {
             return Ok(());
         }
+        if self.start_lba == self.end_lba.wrapping_add(1) {
+            return Err(GptError::ZeroLength {
+                start: self.start_lba,
+            }
+            .into());
+        }
         if self.start_lba > self.end_lba {
             return Err(PartError::Other("GPT: entry start > end"));
         }
@@ -165,20 +314,8 @@ impl GptHeader {
         let align = align_lba_1m(sector_size);
 
         let entries_lba = GPT_PRIMARY_ENTRIES_LBA;
-        let raw_first = entries_lba + entries_sectors;
-        let tail = 1 + entries_sectors;
-
-        let raw_last = total_sectors
-            .checked_sub(1)
-            .and_then(|x| x.checked_sub(tail))
-            .ok_or(GptError::DiskTooSmallForAlignment)?;
-
-        let first_usable_lba = align_up(raw_first, align);
-        let last_usable_lba = align_down(raw_last, align);
-
-        if first_usable_lba > last_usable_lba {
-            return Err(GptError::DiskTooSmallForAlignment.into());
-        }
+        let (first_usable_lba, last_usable_lba) =
+            usable_range(total_sectors, entries_sectors, align)?;
 
         Ok(Self {
             signature: *GPT_SIGNATURE,
@@ -236,20 +373,8 @@ impl GptHeader {
         let align = align_lba_1m(sector_size);
 
         let entries_lba = crate::gpt::GPT_PRIMARY_ENTRIES_LBA;
-        let raw_first = entries_lba + entries_sectors;
-        let tail = 1 + entries_sectors; // backup header (1) + backup table
-
-        let raw_last = total_sectors
-            .checked_sub(1)
-            .and_then(|x| x.checked_sub(tail))
-            .ok_or(GptError::DiskTooSmallForAlignment)?;
-
-        let first_usable_lba = align_up(raw_first, align);
-        let last_usable_lba = align_down(raw_last, align);
-
-        if first_usable_lba > last_usable_lba {
-            return Err(GptError::DiskTooSmallForAlignment.into());
-        }
+        let (first_usable_lba, last_usable_lba) =
+            usable_range(total_sectors, entries_sectors, align)?;
 
         Ok(Self {
             signature: *GPT_SIGNATURE,
@@ -286,6 +411,16 @@ impl GptHeader {
         self
     }
 
+    /// Inverse of [`Self::to_backup`]: the primary header at LBA 1 pointing
+    /// back to this backup.
+    pub fn to_primary(mut self) -> Self {
+        self.backup_lba = self.current_lba;
+        self.current_lba = GPT_PRIMARY_HEADER_LBA;
+        self.entries_lba = GPT_PRIMARY_ENTRIES_LBA;
+        self.header_crc32 = compute_header_crc32(self);
+        self
+    }
+
     pub fn compute_crc32(&mut self, entries: &[GptEntry]) {
         self.entries_crc32 =
             compute_entries_crc32_from_iter(entries.iter().map(entry_head_bytes), self);
@@ -356,10 +491,13 @@ impl GptHeader {
         Ok(())
     }
 
-    pub fn validate_entry(&self, entry: &GptEntry, sector_size: u64) -> PartResult {
+    /// Checks an entry against the usable LBA range.
+    ///
+    /// Start alignment is not enforced here: disks from other tools are often
+    /// not 1 MiB aligned, see `utils::check_alignment` for a non-fatal report.
+    pub fn validate_entry(&self, entry: &GptEntry) -> PartResult {
         entry.validate()?;
 
-        let align = align_lba_1m(sector_size);
         let first_usable = self.first_usable_lba;
         let last_usable = self.last_usable_lba;
 
@@ -373,17 +511,10 @@ impl GptHeader {
             .into());
         }
 
-        if !entry.start_lba.is_multiple_of(align) {
-            return Err(GptError::EntryUnaligned {
-                lba: entry.start_lba,
-                align,
-            }
-            .into());
-        }
         Ok(())
     }
 
-    pub fn validate_entries(&self, entries: &[GptEntry], sector_size: u64) -> PartResult {
+    pub fn validate_entries(&self, entries: &[GptEntry]) -> PartResult {
         let calc = compute_entries_crc32_from_iter(entries.iter().map(entry_head_bytes), self);
         if calc != self.entries_crc32 {
             return Err(GptError::CrcEntriesMismatch {
@@ -393,14 +524,40 @@ impl GptHeader {
             .into());
         }
 
+        self.validate_entries_layout(entries)
+    }
+
+    /// Bounds, overlap and GUID checks of [`Self::validate_entries`], without
+    /// the CRC: `entries` may be the compacted view of a table with holes.
+    pub fn validate_entries_layout(&self, entries: &[GptEntry]) -> PartResult {
         for entry in entries {
-            self.validate_entry(entry, sector_size)?
+            self.validate_entry(entry)?
         }
 
         check_overlaps(entries)?;
+        self.check_unique_guids(entries)?;
 
         Ok(())
     }
+
+    /// Each used entry needs its own unique GUID, distinct from the disk GUID.
+    pub fn check_unique_guids(&self, entries: &[GptEntry]) -> PartResult {
+        for (i, a) in entries.iter().enumerate() {
+            if a.is_empty() {
+                continue;
+            }
+            if a.unique_guid == self.disk_guid {
+                return Err(GptError::DiskGuidCollision { index: i }.into());
+            }
+            if let Some(j) = entries[i + 1..]
+                .iter()
+                .position(|b| !b.is_empty() && b.unique_guid == a.unique_guid)
+            {
+                return Err(GptError::DuplicateUniqueGuid { a: i, b: i + 1 + j }.into());
+            }
+        }
+        Ok(())
+    }
 }
 
 #[inline]
@@ -420,7 +577,7 @@ fn check_overlaps(entries: &[GptEntry]) -> PartResult {
         return Ok(());
     }
 
-    segs.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    segs.sort_unstable_by_key(|s| s.0);
 
     let mut prev = segs[0];
     for &curr in &segs[1..] {
@@ -519,33 +676,25 @@ fn write_entries<IO: RimIO + ?Sized>(
 ) -> PartResult {
     let base = core::mem::size_of::<GptEntry>();
     let entry_size = header.entry_size as usize;
-    let per_sector = (sector_size as usize) / entry_size;
-    if per_sector == 0 {
+    if (sector_size as usize) < entry_size {
         return Err(GptError::EntrySizeExceedsSector {
             entry_size: header.entry_size,
             sector_size,
         }
         .into());
     }
-    let mut sector = vec![0u8; sector_size as usize];
 
-    let mut idx = 0usize;
-    let mut entries_lba = header.entries_lba;
+    // The table is a contiguous array: an entry size that does not divide the
+    // sector (e.g. 192 on 512) makes some entries straddle two sectors.
     let num_entries = header.num_entries as usize;
-    while idx < num_entries {
-        sector.fill(0);
-        let take = core::cmp::min(per_sector, num_entries - idx);
-        for s in 0..take {
-            let dst = &mut sector[s * entry_size..(s + 1) * entry_size];
-            if let Some(e) = entries.get(idx + s) {
-                let b = e.as_bytes();
-                dst[..base].copy_from_slice(b);
-            }
-        }
-        io.write_at_lba(entries_lba, sector_size, &sector)?;
-        entries_lba += 1;
-        idx += take;
+    let table_bytes =
+        (num_entries * entry_size).div_ceil(sector_size as usize) * sector_size as usize;
+    let mut table = vec![0u8; table_bytes];
+    for (slot, e) in table.chunks_exact_mut(entry_size).zip(entries) {
+        slot[..base].copy_from_slice(e.as_bytes());
     }
+
+    io.write_at_lba(header.entries_lba, sector_size, &table)?;
     Ok(())
 }
 
@@ -709,9 +858,9 @@ fn read_gpt_at_lba<IO: RimIO + ?Sized>(
     hdr.validate_header()?;
 
     let entries = read_gpt_entries(io, &hdr, sector_size)?;
-    // No need to re-check CRC here (already done in read_gpt_entries),
-    // but we keep logical validations:
-    hdr.validate_entries(&entries, sector_size)?;
+    // No need to re-check CRC here (already done in read_gpt_entries, over the
+    // raw slots), but we keep logical validations:
+    hdr.validate_entries_layout(&entries)?;
 
     Ok((hdr, entries))
 }
@@ -736,6 +885,357 @@ pub fn read_gpt<IO: RimIO + ?Sized>(io: &mut IO) -> PartResult<(GptHeader, Vec<G
     read_gpt_with_sector(io, crate::DEFAULT_SECTOR_SIZE)
 }
 
+/// First 1 MiB-aligned `[start, end]` run of `size_sectors` sectors inside the
+/// usable range of `header` that doesn't overlap any of `entries`.
+pub fn find_free_gap(
+    header: &GptHeader,
+    entries: &[GptEntry],
+    sector_size: u64,
+    size_sectors: u64,
+) -> PartResult<(u64, u64)> {
+    if size_sectors == 0 {
+        return Err(GptError::ZeroLength {
+            start: header.first_usable_lba,
+        }
+        .into());
+    }
+    let align = align_lba_1m(sector_size);
+    let mut cur = align_up(header.first_usable_lba, align);
+
+    // Entries are checked in start order; the candidate jumps past each blocker.
+    loop {
+        let end = cur
+            .checked_add(size_sectors - 1)
+            .ok_or(GptError::LbaOverflow)?;
+        if end > header.last_usable_lba {
+            return Err(GptError::NoFreeGap {
+                sectors: size_sectors,
+            }
+            .into());
+        }
+        let blocker = entries
+            .iter()
+            .filter(|e| !e.is_empty() && e.start_lba <= end && e.end_lba >= cur)
+            .map(|e| e.end_lba)
+            .max();
+        match blocker {
+            None => return Ok((cur, end)),
+            Some(last) => cur = align_up(last.saturating_add(1), align),
+        }
+    }
+}
+
+/// Copies the entry table of `from` to where `to` expects it, then writes `to`.
+#[cfg(feature = "alloc")]
+fn copy_gpt_table<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    from: &GptHeader,
+    to: &GptHeader,
+    sector_size: u64,
+) -> PartResult {
+    let table_bytes =
+        (from.num_entries as u64 * from.entry_size as u64).div_ceil(sector_size) * sector_size;
+    io.copy_region(
+        from.entries_lba * sector_size,
+        to.entries_lba * sector_size,
+        table_bytes,
+    )?;
+    io.write_struct_lba(to.current_lba, sector_size, to)?;
+    io.flush()?;
+    Ok(())
+}
+
+/// Last LBA of the device, where the backup header belongs, or `fallback`
+/// (a header's `backup_lba`) when the device length is unknown.
+#[cfg(feature = "alloc")]
+fn device_last_lba<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+    fallback: impl FnOnce(&mut IO) -> PartResult<u64>,
+) -> PartResult<u64> {
+    match io.len() {
+        Ok(len) => Ok((len / sector_size)
+            .checked_sub(1)
+            .ok_or(GptError::LbaOverflow)?),
+        Err(_) => fallback(io),
+    }
+}
+
+/// Rebuilds the backup header and entry table from the primary GPT.
+///
+/// The primary must validate (header, entries CRC, layout). The backup is
+/// written in the last sector of the device, whatever the primary's
+/// `backup_lba` says; a stale `backup_lba` (e.g. after resizing the image) is
+/// corrected in the primary header too.
+#[cfg(feature = "alloc")]
+pub fn repair_backup_from_primary<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+) -> PartResult<GptHeader> {
+    let (mut primary, _) = read_gpt_at_lba(io, GPT_PRIMARY_HEADER_LBA, sector_size)?;
+    let last_lba = device_last_lba(io, sector_size, |_| Ok(primary.backup_lba))?;
+    if primary.backup_lba != last_lba {
+        primary.backup_lba = last_lba;
+        primary.header_crc32 = compute_header_crc32(primary);
+        io.write_struct_lba(GPT_PRIMARY_HEADER_LBA, sector_size, &primary)?;
+    }
+
+    let backup = primary.to_backup(sector_size);
+    // The backup table must not land on the usable area (device shrunk).
+    if backup.entries_lba <= primary.last_usable_lba {
+        return Err(GptError::EntryOutOfBounds {
+            first_usable: primary.first_usable_lba,
+            last_usable: backup.entries_lba.saturating_sub(1),
+            start: primary.first_usable_lba,
+            end: primary.last_usable_lba,
+        }
+        .into());
+    }
+    copy_gpt_table(io, &primary, &backup, sector_size)?;
+    Ok(backup)
+}
+
+/// Rebuilds the primary header and entry table from the backup GPT.
+///
+/// The backup is looked up in the last sector of the device, or through the
+/// primary's `backup_lba` when the device length is unknown.
+#[cfg(feature = "alloc")]
+pub fn repair_primary_from_backup<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+) -> PartResult<GptHeader> {
+    let backup_lba = device_last_lba(io, sector_size, |io| {
+        let raw_primary: GptHeader = io.read_struct_lba(GPT_PRIMARY_HEADER_LBA, sector_size)?;
+        Ok(raw_primary.backup_lba)
+    })?;
+    let (backup, _) = read_gpt_at_lba(io, backup_lba, sector_size)?;
+    let primary = backup.to_primary();
+    copy_gpt_table(io, &backup, &primary, sector_size)?;
+    Ok(primary)
+}
+
+/// Unallocated `[start, end]` LBA ranges of the usable area, in disk order.
+///
+/// Each start is 1 MiB aligned (the placement [`find_free_gap`] would use);
+/// gaps left empty by the alignment are skipped.
+#[cfg(feature = "alloc")]
+pub fn free_gaps(header: &GptHeader, entries: &[GptEntry], sector_size: u64) -> Vec<(u64, u64)> {
+    let align = align_lba_1m(sector_size);
+    let mut used: Vec<&GptEntry> = entries.iter().filter(|e| !e.is_empty()).collect();
+    used.sort_by_key(|e| e.start_lba);
+
+    let mut gaps = Vec::new();
+    let mut cur = header.first_usable_lba;
+    let mut push = |from: u64, to: u64| {
+        let start = align_up(from, align);
+        if start <= to {
+            gaps.push((start, to));
+        }
+    };
+    for e in used {
+        if e.start_lba > cur {
+            push(cur, e.start_lba - 1);
+        }
+        cur = cur.max(e.end_lba.saturating_add(1));
+    }
+    if cur <= header.last_usable_lba {
+        push(cur, header.last_usable_lba);
+    }
+    gaps
+}
+
+/// Reads the GPT and returns its free regions, see [`free_gaps`].
+#[cfg(feature = "alloc")]
+pub fn free_regions_with_sector<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+) -> PartResult<Vec<(u64, u64)>> {
+    let (header, entries) = read_gpt_with_sector(io, sector_size)?;
+    Ok(free_gaps(&header, &entries, sector_size))
+}
+
+/// [`free_regions_with_sector`] for 512-byte sectors.
+#[cfg(feature = "alloc")]
+pub fn free_regions<IO: RimIO + ?Sized>(io: &mut IO) -> PartResult<Vec<(u64, u64)>> {
+    free_regions_with_sector(io, DEFAULT_SECTOR_SIZE)
+}
+
+/// Adds a partition in the first 1 MiB-aligned gap that fits `size_sectors`,
+/// then rewrites the primary and backup GPT (CRCs included).
+///
+/// The entry takes the first empty slot of the primary table, so existing
+/// partitions keep their slot (and partition number). Returns the chosen
+/// `(start_lba, end_lba)`.
+#[cfg(feature = "alloc")]
+pub fn add_partition_with_sector<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+    type_guid: [u8; 16],
+    unique_guid: [u8; 16],
+    size_sectors: u64,
+    name: &str,
+) -> PartResult<(u64, u64)> {
+    let (header, mut slots) = read_gpt_slots(io, sector_size)?;
+    let index = slots
+        .iter()
+        .position(GptEntry::is_empty)
+        .ok_or(GptError::TableFull {
+            slots: header.num_entries,
+        })?;
+
+    let mut used: Vec<GptEntry> = slots.iter().filter(|e| !e.is_empty()).copied().collect();
+    let (start, end) = find_free_gap(&header, &used, sector_size, size_sectors)?;
+    let entry = GptEntry::new(type_guid, unique_guid, start, end, 0, name);
+    used.push(entry);
+    header.validate_entries_layout(&used)?;
+
+    slots[index] = entry;
+    write_gpt_slots(io, sector_size, header, slots)?;
+    Ok((start, end))
+}
+
+/// [`add_partition_with_sector`] for 512-byte sectors.
+#[cfg(feature = "alloc")]
+pub fn add_partition<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    type_guid: [u8; 16],
+    unique_guid: [u8; 16],
+    size_sectors: u64,
+    name: &str,
+) -> PartResult<(u64, u64)> {
+    add_partition_with_sector(
+        io,
+        DEFAULT_SECTOR_SIZE,
+        type_guid,
+        unique_guid,
+        size_sectors,
+        name,
+    )
+}
+
+/// Identifies a GPT entry either by table slot or by partition name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GptSelector<'a> {
+    /// Slot index in the entry table (empty slots count).
+    Index(usize),
+    /// Partition name, compared against the decoded UTF-16 `name` field.
+    Name(&'a str),
+}
+
+impl From<usize> for GptSelector<'_> {
+    fn from(index: usize) -> Self {
+        GptSelector::Index(index)
+    }
+}
+
+impl<'a> From<&'a str> for GptSelector<'a> {
+    fn from(name: &'a str) -> Self {
+        GptSelector::Name(name)
+    }
+}
+
+/// Reads every slot of the primary table (empty ones included, so indices
+/// are table slots), after checking the entries CRC.
+#[cfg(feature = "alloc")]
+fn read_gpt_slots<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+) -> PartResult<(GptHeader, Vec<GptEntry>)> {
+    let mut reader = crate::gpt_stream::GptStreamReader::<_, 4096>::new(io, sector_size)?;
+    reader.validate_crc()?;
+    let slots = (0..reader.slots())
+        .map(|i| reader.read_at(i))
+        .collect::<PartResult<Vec<_>>>()?;
+    Ok((*reader.header(), slots))
+}
+
+/// Rewrites all `slots` in place, then both headers and the backup table.
+#[cfg(feature = "alloc")]
+fn write_gpt_slots<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+    header: GptHeader,
+    slots: Vec<GptEntry>,
+) -> PartResult {
+    let mut writer =
+        crate::gpt_stream::GptStreamWriter::<_, 4096>::from_header(io, sector_size, header)?;
+    writer.write_entries(slots.len(), slots.into_iter())?;
+    writer.finalize()
+}
+
+#[cfg(feature = "alloc")]
+fn select_slot(slots: &[GptEntry], selector: GptSelector<'_>) -> PartResult<usize> {
+    match selector {
+        GptSelector::Index(i) => Some(i).filter(|&i| slots.get(i).is_some_and(|e| !e.is_empty())),
+        GptSelector::Name(name) => slots
+            .iter()
+            .position(|e| !e.is_empty() && e.name_string() == name),
+    }
+    .ok_or(GptError::EntryNotFound.into())
+}
+
+/// Clears the entry matched by `selector`, leaving its slot empty, and
+/// rewrites both primary and backup tables with fresh CRCs.
+/// Returns the removed entry.
+#[cfg(feature = "alloc")]
+pub fn delete_partition_with_sector<'a, IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+    selector: impl Into<GptSelector<'a>>,
+) -> PartResult<GptEntry> {
+    let (header, mut slots) = read_gpt_slots(io, sector_size)?;
+    let index = select_slot(&slots, selector.into())?;
+
+    let removed = core::mem::replace(&mut slots[index], GptEntry::new_zeroed());
+    debug_assert!(slots[index].is_empty());
+
+    write_gpt_slots(io, sector_size, header, slots)?;
+    Ok(removed)
+}
+
+/// [`delete_partition_with_sector`] for 512-byte sectors.
+#[cfg(feature = "alloc")]
+pub fn delete_partition<'a, IO: RimIO + ?Sized>(
+    io: &mut IO,
+    selector: impl Into<GptSelector<'a>>,
+) -> PartResult<GptEntry> {
+    delete_partition_with_sector(io, DEFAULT_SECTOR_SIZE, selector)
+}
+
+/// Moves the end of the entry matched by `selector` to `new_end_lba`
+/// (inclusive), growing or shrinking it in place.
+///
+/// The new range must stay within the usable LBAs, keep at least one sector
+/// and not overlap any other entry. Both tables are rewritten.
+#[cfg(feature = "alloc")]
+pub fn resize_partition_with_sector<'a, IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+    selector: impl Into<GptSelector<'a>>,
+    new_end_lba: u64,
+) -> PartResult<GptEntry> {
+    let (header, mut slots) = read_gpt_slots(io, sector_size)?;
+    let index = select_slot(&slots, selector.into())?;
+
+    slots[index].end_lba = new_end_lba;
+    let used: Vec<GptEntry> = slots.iter().filter(|e| !e.is_empty()).copied().collect();
+    header.validate_entries_layout(&used)?;
+
+    let resized = slots[index];
+    write_gpt_slots(io, sector_size, header, slots)?;
+    Ok(resized)
+}
+
+/// [`resize_partition_with_sector`] for 512-byte sectors.
+#[cfg(feature = "alloc")]
+pub fn resize_partition<'a, IO: RimIO + ?Sized>(
+    io: &mut IO,
+    selector: impl Into<GptSelector<'a>>,
+    new_end_lba: u64,
+) -> PartResult<GptEntry> {
+    resize_partition_with_sector(io, DEFAULT_SECTOR_SIZE, selector, new_end_lba)
+}
+
 /// Places entries sequentially, aligned to 1 MiB, within the header bounds.
 /// Returns a `Vec<GptEntry>` or an error if it doesn't fit.
 /// Designed for tests and simple cases (no imposed intervals).
@@ -745,6 +1245,78 @@ pub fn make_aligned_entries<'a, I>(
     sector_size: u64,
     reqs: I,
 ) -> PartResult<Vec<GptEntry>>
+where
+    I: IntoIterator<
+        Item = (
+            &'a [u8; 16],
+            &'a [u8; 16],
+            u64, /*len_sectors*/
+            u64, /*attrs*/
+            &'a str,
+        ),
+    >,
+{
+    make_aligned_entries_with_gap(header, sector_size, 0, reqs)
+}
+
+/// Like [`make_aligned_entries`], but leaves at least `gap_sectors` unallocated
+/// sectors between consecutive entries (the next start is still 1 MiB aligned).
+#[cfg(feature = "alloc")]
+pub fn make_aligned_entries_with_gap<'a, I>(
+    header: &GptHeader,
+    sector_size: u64,
+    gap_sectors: u64,
+    reqs: I,
+) -> PartResult<Vec<GptEntry>>
+where
+    I: IntoIterator<
+        Item = (
+            &'a [u8; 16],
+            &'a [u8; 16],
+            u64, /*len_sectors*/
+            u64, /*attrs*/
+            &'a str,
+        ),
+    >,
+{
+    let opts = AlignOptions {
+        gap_sectors,
+        ..Default::default()
+    };
+    make_aligned_entries_with(header, sector_size, &opts, reqs)
+}
+
+/// How entry ends are treated by [`make_aligned_entries_with`].
+///
+/// Starts are always 1 MiB aligned; with `Down`/`Up` the length is also a
+/// multiple of the alignment, so `end_lba + 1` lands on a boundary too.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EndAlign {
+    /// Keep the requested length.
+    #[default]
+    Off,
+    /// Shrink the length to the previous boundary (fails if it becomes 0).
+    Down,
+    /// Pad the length up to the next boundary.
+    Up,
+}
+
+/// Placement options for [`make_aligned_entries_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AlignOptions {
+    /// Minimum unallocated sectors between consecutive entries.
+    pub gap_sectors: u64,
+    pub end: EndAlign,
+}
+
+/// Places entries sequentially, 1 MiB aligned, according to `opts`.
+#[cfg(feature = "alloc")]
+pub fn make_aligned_entries_with<'a, I>(
+    header: &GptHeader,
+    sector_size: u64,
+    opts: &AlignOptions,
+    reqs: I,
+) -> PartResult<Vec<GptEntry>>
 where
     I: IntoIterator<
         Item = (
@@ -766,9 +1338,14 @@ where
         if out.len() >= max_slots {
             return Err(PartError::Other("GPT: not enough entry slots"));
         }
+        let len_sectors = match opts.end {
+            EndAlign::Off => len_sectors,
+            EndAlign::Down => align_down(len_sectors, align),
+            EndAlign::Up => align_up(len_sectors, align),
+        };
         // zero-sized allocation
         if len_sectors == 0 {
-            return Err(PartError::Other("GPT: zero-sized allocation"));
+            return Err(GptError::ZeroLength { start: cur }.into());
         }
         // align the beginning
         if !cur.is_multiple_of(align) {
@@ -802,7 +1379,7 @@ where
         }
 
         out.push(GptEntry::new(*typ, *uid, cur, end, attrs, name));
-        cur = end.saturating_add(1);
+        cur = end.saturating_add(1).saturating_add(opts.gap_sectors);
     }
 
     Ok(out)
@@ -908,6 +1485,126 @@ mod tests {
         assert!(read_gpt(&mut io).is_err());
     }
 
+    #[test]
+    fn zero_length_entries_rejected() {
+        let hdr = GptHeader::new(512, 20_000, [0u8; 16]).unwrap();
+        let e = make_aligned_entries(&hdr, 512, [(&[1; 16], &[2; 16], 0, 0, "empty")]).unwrap_err();
+        assert!(matches!(e, PartError::Gpt(GptError::ZeroLength { .. })));
+
+        let entry = GptEntry::new([1; 16], [2; 16], 2048, 2047, 0, "empty");
+        assert_eq!(
+            entry.validate(),
+            Err(PartError::Gpt(GptError::ZeroLength { start: 2048 }))
+        );
+    }
+
+    #[test]
+    fn gap_between_aligned_entries() {
+        let hdr = GptHeader::new(512, 200_000, [0u8; 16]).unwrap();
+        let gap = 16 * 2048; // 16 MiB
+        let entries = make_aligned_entries_with_gap(
+            &hdr,
+            512,
+            gap,
+            [
+                (&[1; 16], &[2; 16], 4096, 0, "a"),
+                (&[1; 16], &[3; 16], 4096, 0, "b"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(entries[1].start_lba, entries[0].end_lba + 1 + gap);
+        assert!(entries[1].start_lba.is_multiple_of(align_lba_1m(512)));
+    }
+
+    #[test]
+    fn end_aligned_entries() {
+        let hdr = GptHeader::new(512, 200_000, [0u8; 16]).unwrap();
+        let align = align_lba_1m(512);
+        let reqs = [
+            (&[1; 16], &[2; 16], 3000, 0, "a"),
+            (&[1; 16], &[3; 16], 5000, 0, "b"),
+        ];
+        for end in [EndAlign::Down, EndAlign::Up] {
+            let opts = AlignOptions {
+                end,
+                ..Default::default()
+            };
+            let entries = make_aligned_entries_with(&hdr, 512, &opts, reqs).unwrap();
+            for e in &entries {
+                assert!(e.start_lba.is_multiple_of(align));
+                assert!((e.end_lba + 1).is_multiple_of(align));
+            }
+        }
+
+        let down = AlignOptions {
+            end: EndAlign::Down,
+            ..Default::default()
+        };
+        let e = make_aligned_entries_with(&hdr, 512, &down, [(&[1; 16], &[2; 16], 100, 0, "x")])
+            .unwrap_err();
+        assert!(matches!(e, PartError::Gpt(GptError::ZeroLength { .. })));
+
+        let up = AlignOptions {
+            end: EndAlign::Up,
+            ..Default::default()
+        };
+        // fits as requested, but not once padded up to the next boundary
+        let big = hdr.last_usable_lba - align + 1;
+        assert!(make_aligned_entries(&hdr, 512, [(&[1; 16], &[2; 16], big, 0, "x")]).is_ok());
+        assert!(
+            make_aligned_entries_with(&hdr, 512, &up, [(&[1; 16], &[2; 16], big, 0, "x")]).is_err()
+        );
+    }
+
+    #[test]
+    fn usable_range_clears_entry_tables() {
+        for (sector, num_entries) in [(512u64, 128u32), (4096, 128), (4096, 16_384)] {
+            let align = align_lba_1m(sector);
+            let total = 64 * 1024 * 1024 / sector;
+            let h = GptHeader::new_with_table(sector, total, [0; 16], num_entries, 128).unwrap();
+
+            let entries_sectors = (num_entries as u64 * 128).div_ceil(sector);
+            assert!(h.first_usable_lba >= h.entries_lba + entries_sectors);
+            assert!(h.first_usable_lba.is_multiple_of(align));
+            assert!(h.last_usable_lba < h.backup_lba - entries_sectors);
+            assert!(h.first_usable_lba <= h.last_usable_lba);
+        }
+
+        // 4Kn, 128 entries: table is LBAs 2..=5, first usable is the first 1 MiB boundary.
+        let h = GptHeader::new(4096, 16_384, [0; 16]).unwrap();
+        assert_eq!(h.first_usable_lba, 256);
+
+        // Room for both tables but not for an aligned LBA in between.
+        assert_eq!(
+            GptHeader::new(4096, 200, [0; 16]).unwrap_err(),
+            PartError::Gpt(GptError::DiskTooSmallForAlignment)
+        );
+        assert_eq!(
+            GptHeader::new(4096, 4, [0; 16]).unwrap_err(),
+            PartError::Gpt(GptError::DiskTooSmallForAlignment)
+        );
+    }
+
+    #[test]
+    fn random_guid_sets_version_and_variant() {
+        let mut rng = crate::rng::SplitMix64::new(42);
+        let a = random_guid(&mut rng).unwrap();
+        let b = random_guid(&mut rng).unwrap();
+        assert_ne!(a, b);
+        assert_eq!(random_guid(&mut crate::rng::SplitMix64::new(42)), Ok(a));
+
+        #[cfg(feature = "getrandom")]
+        let guids = [a, b, random_guid(&mut crate::rng::OsRng).unwrap()];
+        #[cfg(not(feature = "getrandom"))]
+        let guids = [a, b];
+        for guid in guids {
+            let s = crate::guids::format_guid(&guid);
+            assert_eq!(&s[14..15], "4", "{s}");
+            assert!(matches!(&s[19..20], "8" | "9" | "A" | "B"), "{s}");
+            assert_eq!(crate::guids::parse_guid_str(&s), Some(guid));
+        }
+    }
+
     #[test]
     fn entry_size_exceeds_sector() {
         let mut buf = vec![0u8; 512 * 20_000];
@@ -922,6 +1619,34 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn entries_straddle_sectors_with_192_byte_entries() {
+        let total = 20_000u64;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let hdr = GptHeader::new_with_table(512, total, [0x19; 16], 128, 192).unwrap();
+        // entry #2 spans bytes 384..576 of the table: across LBA 2 and 3
+        let parts = [
+            GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, "A"),
+            GptEntry::new([3; 16], [4; 16], 4096, 8191, 0, "B"),
+            GptEntry::new([5; 16], [6; 16], 8192, 12287, 0, "straddle"),
+        ];
+        write_gpt_with_header(&mut io, hdr, &parts, 512).unwrap();
+
+        let mut raw = [0u8; 1024];
+        io.read_at_lba(hdr.entries_lba, 512, &mut raw).unwrap();
+        assert_eq!(&raw[384..400], &[5; 16]);
+        assert_eq!(&raw[400..416], &[6; 16]);
+        assert_eq!(&raw[384 + 128..576], &[0u8; 64][..]);
+
+        let (rhdr, entries) = read_gpt_with_sector(&mut io, 512).unwrap();
+        assert_eq!(rhdr.entry_size, 192);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2].start_lba, 8192);
+        assert_eq!(decode_gpt_name(&entries[2].name), "straddle");
+    }
+
     #[test]
     fn crc_iter_over_entries_equals_iter_over_region() {
         let mut buf = vec![0u8; 512 * 20_000];
@@ -952,4 +1677,283 @@ mod tests {
         );
         assert_eq!(crc_entries, crc_region);
     }
+
+    #[test]
+    fn add_partition_fills_first_gap() {
+        let total = 64 * 2048; // 64 MiB
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        // Leaves a 4 MiB hole at 4 MiB.
+        let a = GptEntry::new([1; 16], [2; 16], 2048, 8191, 0, "a");
+        let b = GptEntry::new([1; 16], [3; 16], 16384, 20479, 0, "b");
+        write_gpt_from_entries(&mut io, &[a, b], total, [0xAB; 16]).unwrap();
+
+        let p1 = add_partition(&mut io, [4; 16], [10; 16], 4096, "p1").unwrap();
+        let p2 = add_partition(&mut io, [4; 16], [11; 16], 3000, "p2").unwrap();
+        let p3 = add_partition(&mut io, [4; 16], [12; 16], 10_000, "p3").unwrap();
+        assert_eq!(p1, (8192, 12287));
+        assert_eq!(p2, (12288, 15287));
+        assert_eq!(p3, (20480, 30479));
+
+        let (hdr, mut parts) = read_gpt(&mut io).unwrap();
+        assert_eq!(parts.len(), 5);
+        parts.sort_by_key(|p| p.start_lba);
+        for w in parts.windows(2) {
+            assert!(w[0].end_lba < w[1].start_lba);
+        }
+        assert!(parts.iter().all(|p| p.start_lba % 2048 == 0));
+
+        // Backup table agrees with the primary.
+        let backup: GptHeader = io.read_struct_lba(hdr.backup_lba, 512).unwrap();
+        assert_eq!(backup.entries_crc32, hdr.entries_crc32);
+
+        assert_eq!(
+            add_partition(&mut io, [4; 16], [13; 16], total, "big"),
+            Err(GptError::NoFreeGap { sectors: total }.into())
+        );
+    }
+
+    #[test]
+    fn delete_partition_by_index_and_name() {
+        let total = 64 * 2048;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let a = GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, "a");
+        let b = GptEntry::new([1; 16], [3; 16], 4096, 6143, 0, "b");
+        let c = GptEntry::new([1; 16], [4; 16], 6144, 8191, 0, "c");
+        write_gpt_from_entries(&mut io, &[a, b, c], total, [0xAB; 16]).unwrap();
+
+        let removed = delete_partition(&mut io, 1).unwrap();
+        assert_eq!(removed.unique_guid, [3; 16]);
+
+        let (hdr, parts) = read_gpt(&mut io).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].unique_guid, [2; 16]);
+        assert_eq!(parts[1].unique_guid, [4; 16]);
+
+        // The slot stays in place, zeroed; the backup carries the same CRC.
+        let slot: GptEntry = io.read_struct_lba(hdr.entries_lba + 1, 512).unwrap();
+        assert!(slot.is_empty());
+        let backup: GptHeader = io.read_struct_lba(hdr.backup_lba, 512).unwrap();
+        assert_eq!(backup.entries_crc32, hdr.entries_crc32);
+        backup.validate_header().unwrap();
+
+        assert!(matches!(
+            delete_partition(&mut io, 1),
+            Err(PartError::Gpt(GptError::EntryNotFound))
+        ));
+        assert_eq!(delete_partition(&mut io, "c").unwrap().start_lba, 6144);
+        assert_eq!(read_gpt(&mut io).unwrap().1.len(), 1);
+    }
+
+    #[test]
+    fn add_after_delete_reuses_the_hole() {
+        let total = 64 * 2048;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let a = GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, "a");
+        let b = GptEntry::new([1; 16], [3; 16], 4096, 6143, 0, "b");
+        let c = GptEntry::new([1; 16], [4; 16], 6144, 8191, 0, "c");
+        write_gpt_from_entries(&mut io, &[a, b, c], total, [0xAB; 16]).unwrap();
+
+        delete_partition(&mut io, "a").unwrap();
+        add_partition(&mut io, [5; 16], [9; 16], 2048, "new").unwrap();
+
+        // Untouched partitions keep their slot; the new one fills slot 0.
+        let (hdr, slots) = read_gpt_slots(&mut io, 512).unwrap();
+        assert_eq!(slots[0].unique_guid, [9; 16]);
+        assert_eq!(slots[1].unique_guid, [3; 16]);
+        assert_eq!(slots[2].unique_guid, [4; 16]);
+        assert!(slots[3..].iter().all(GptEntry::is_empty));
+
+        let backup: GptHeader = io.read_struct_lba(hdr.backup_lba, 512).unwrap();
+        assert_eq!(backup.entries_crc32, hdr.entries_crc32);
+    }
+
+    #[test]
+    fn resize_partition_grows_and_checks_neighbors() {
+        let total = 64 * 2048;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let a = GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, "a");
+        let b = GptEntry::new([1; 16], [3; 16], 8192, 10239, 0, "b");
+        write_gpt_from_entries(&mut io, &[a, b], total, [0xAB; 16]).unwrap();
+
+        // Grow the last partition up to the end of the usable range.
+        let (hdr, _) = read_gpt(&mut io).unwrap();
+        let grown = resize_partition(&mut io, "b", hdr.last_usable_lba).unwrap();
+        assert_eq!(grown.end_lba, hdr.last_usable_lba);
+
+        // Grow the first one into the free space before its neighbor.
+        resize_partition(&mut io, 0, 8191).unwrap();
+        let (hdr, parts) = read_gpt(&mut io).unwrap();
+        assert_eq!(parts[0].end_lba, 8191);
+        assert_eq!(parts[1].end_lba, hdr.last_usable_lba);
+        let backup: GptHeader = io.read_struct_lba(hdr.backup_lba, 512).unwrap();
+        assert_eq!(backup.entries_crc32, hdr.entries_crc32);
+
+        assert!(matches!(
+            resize_partition(&mut io, 0, 8192),
+            Err(PartError::Gpt(GptError::Overlap { .. }))
+        ));
+        assert!(matches!(
+            resize_partition(&mut io, 1, hdr.last_usable_lba + 1),
+            Err(PartError::Gpt(GptError::EntryOutOfBounds { .. }))
+        ));
+        assert!(resize_partition(&mut io, 0, 2000).is_err());
+
+        // Failed resizes leave the table untouched.
+        assert_eq!(read_gpt(&mut io).unwrap().1[0].end_lba, 8191);
+    }
+
+    #[test]
+    fn free_regions_reports_aligned_holes() {
+        let total = 64 * 2048;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        // Holes: [8192, 16383] and [20480, 24575] (its start aligned up from 20000).
+        let a = GptEntry::new([1; 16], [2; 16], 2048, 8191, 0, "a");
+        let b = GptEntry::new([1; 16], [3; 16], 16384, 19999, 0, "b");
+        let c = GptEntry::new([1; 16], [4; 16], 24576, 28671, 0, "c");
+        // Listed out of order: the scan must sort by start.
+        write_gpt_from_entries(&mut io, &[c, a, b], total, [0xAB; 16]).unwrap();
+
+        let (hdr, _) = read_gpt(&mut io).unwrap();
+        assert_eq!(
+            free_regions(&mut io).unwrap(),
+            vec![(8192, 16383), (20480, 24575), (28672, hdr.last_usable_lba)]
+        );
+
+        // The first gap is where add_partition would go.
+        let (start, _) = add_partition(&mut io, [5; 16], [6; 16], 2048, "d").unwrap();
+        assert_eq!(start, 8192);
+        assert_eq!(free_regions(&mut io).unwrap()[0], (10240, 16383));
+    }
+
+    #[test]
+    fn repair_backup_and_primary() {
+        use crate::gpt_stream::GptStreamReader;
+
+        let total = 16 * 2048;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        let a = GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, "a");
+        let b = GptEntry::new([1; 16], [3; 16], 4096, 8191, 0, "b");
+        write_gpt_from_entries(&mut io, &[a, b], total, [0xAB; 16]).unwrap();
+        let (primary, _) = read_gpt(&mut io).unwrap();
+
+        // Trash the backup table and header.
+        let backup_lba = primary.backup_lba;
+        io.write_at((backup_lba - 32) * 512, &[0xEE; 33 * 512])
+            .unwrap();
+        assert!(read_gpt_at_lba(&mut io, backup_lba, 512).is_err());
+
+        let backup = repair_backup_from_primary(&mut io, 512).unwrap();
+        assert_eq!(backup.current_lba, total - 1);
+        assert_eq!(backup.backup_lba, GPT_PRIMARY_HEADER_LBA);
+        let (hdr, entries) = read_gpt_at_lba(&mut io, backup_lba, 512).unwrap();
+        assert_eq!(hdr.entries_crc32, primary.entries_crc32);
+        assert_eq!(entries.len(), 2);
+
+        // Now the other way around.
+        io.write_at(512, &[0xEE; 33 * 512]).unwrap();
+        assert!(GptStreamReader::<_, 512>::new(&mut io, 512).is_err());
+
+        let repaired = repair_primary_from_backup(&mut io, 512).unwrap();
+        assert_eq!(repaired.header_crc32, primary.header_crc32);
+        let mut reader = GptStreamReader::<_, 512>::new(&mut io, 512).unwrap();
+        reader.validate_crc().unwrap();
+        assert_eq!(read_gpt(&mut io).unwrap().1.len(), 2);
+    }
+
+    #[test]
+    fn repair_backup_ignores_stale_backup_lba() {
+        let total = 16 * 2048;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        let a = GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, "a");
+        write_gpt_from_entries(&mut io, &[a], total, [0xAB; 16]).unwrap();
+
+        // Primary still valid, but its backup_lba points inside the disk.
+        let mut primary: GptHeader = io.read_struct_lba(GPT_PRIMARY_HEADER_LBA, 512).unwrap();
+        primary.backup_lba = total - 100;
+        primary.header_crc32 = compute_header_crc32(primary);
+        io.write_struct_lba(GPT_PRIMARY_HEADER_LBA, 512, &primary)
+            .unwrap();
+        io.write_at((total - 33) * 512, &[0xEE; 33 * 512]).unwrap();
+
+        let backup = repair_backup_from_primary(&mut io, 512).unwrap();
+        assert_eq!(backup.current_lba, total - 1);
+        let (hdr, entries) = read_gpt_at_lba(&mut io, total - 1, 512).unwrap();
+        assert_eq!(hdr.entries_crc32, primary.entries_crc32);
+        assert_eq!(entries.len(), 1);
+
+        let (fixed, _) = read_gpt_at_lba(&mut io, GPT_PRIMARY_HEADER_LBA, 512).unwrap();
+        assert_eq!(fixed.backup_lba, total - 1);
+        // Nothing was written where the stale pointer aimed.
+        let mut stale = [0u8; 512];
+        io.read_at((total - 100) * 512, &mut stale).unwrap();
+        assert_eq!(stale, [0u8; 512]);
+    }
+
+    #[test]
+    fn entry_name_roundtrip() {
+        let e = GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, "Données 𝄞");
+        assert_eq!(e.name_string(), "Données 𝄞");
+
+        let mut chars = ['\0'; 36];
+        let n = e.name_into(&mut chars);
+        assert_eq!(chars[..n].iter().collect::<String>(), "Données 𝄞");
+
+        let mut short = ['\0'; 3];
+        assert_eq!(e.name_into(&mut short), 3);
+        assert_eq!(short, ['D', 'o', 'n']);
+
+        let full = GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, &"x".repeat(40));
+        assert_eq!(full.name_string().len(), 36);
+    }
+
+    #[test]
+    fn attribute_flags_roundtrip() {
+        type Flag = (fn(&GptEntry) -> bool, fn(&mut GptEntry, bool), u64);
+        let flags: [Flag; 6] = [
+            (GptEntry::is_required, GptEntry::set_required, 1 << 0),
+            (GptEntry::is_no_block_io, GptEntry::set_no_block_io, 1 << 1),
+            (
+                GptEntry::is_legacy_bios_bootable,
+                GptEntry::set_legacy_bios_bootable,
+                1 << 2,
+            ),
+            (GptEntry::is_read_only, GptEntry::set_read_only, 1 << 60),
+            (GptEntry::is_hidden, GptEntry::set_hidden, 1 << 62),
+            (
+                GptEntry::is_no_automount,
+                GptEntry::set_no_automount,
+                1 << 63,
+            ),
+        ];
+
+        let mut e = GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, "data");
+        for (get, set, bit) in flags {
+            assert!(!get(&e));
+            set(&mut e, true);
+            assert!(get(&e));
+            assert_eq!(e.attributes, bit);
+            set(&mut e, false);
+            assert!(!get(&e));
+            assert_eq!(e.attributes, 0);
+        }
+
+        // Flags are independent of each other.
+        e.set_read_only(true);
+        e.set_hidden(true);
+        e.set_hidden(false);
+        assert!(e.is_read_only() && !e.is_hidden());
+        assert_eq!(e.attributes, GPT_ATTR_READ_ONLY);
+    }
 }
@@ -61,7 +61,7 @@ impl<'io, IO: RimIO + ?Sized, const N: usize> GptStreamReader<'io, IO, N> {
     }
 
     /// Reads an arbitrary entry (copies to internal buffer).
-    fn read_at(&mut self, index: usize) -> PartResult<GptEntry> {
+    pub(crate) fn read_at(&mut self, index: usize) -> PartResult<GptEntry> {
         let off = index as u64 * self.entry_size as u64;
         let base_lba = self.header.entries_lba + (off / self.sector_size);
         let in_sector = (off % self.sector_size) as usize;
@@ -72,34 +72,23 @@ impl<'io, IO: RimIO + ?Sized, const N: usize> GptStreamReader<'io, IO, N> {
             return Err(PartError::Other("GPT: entry/sector exceeds stack buffer"));
         }
 
-        // Case 1: entry fits in a single sector
-        if in_sector + entry_size <= ss {
-            // cache
-            if self.cached_lba != Some(base_lba) {
+        // The entry may straddle sector boundaries when entry_size does not
+        // divide the sector size: copy it piecewise, reusing the cached sector.
+        let mut lba = base_lba;
+        let mut in_sector = in_sector;
+        let mut copied = 0usize;
+        while copied < entry_size {
+            if self.cached_lba != Some(lba) {
                 self.io
-                    .read_at_lba(base_lba, self.sector_size, &mut self.sector_buf[..ss])?;
-                self.cached_lba = Some(base_lba);
+                    .read_at_lba(lba, self.sector_size, &mut self.sector_buf[..ss])?;
+                self.cached_lba = Some(lba);
             }
-            self.entry_buf[..entry_size]
-                .copy_from_slice(&self.sector_buf[in_sector..in_sector + entry_size]);
-        } else {
-            // Case 2: overlap on 2 sectors
-            // Read first sector (with cache)
-            if self.cached_lba != Some(base_lba) {
-                self.io
-                    .read_at_lba(base_lba, self.sector_size, &mut self.sector_buf[..ss])?;
-                self.cached_lba = Some(base_lba);
-            }
-            let first = ss - in_sector;
-            self.entry_buf[..first].copy_from_slice(&self.sector_buf[in_sector..ss]);
-
-            // Read second sector (not the same LBA, cache is replaced)
-            let next_lba = base_lba + 1;
-            self.io
-                .read_at_lba(next_lba, self.sector_size, &mut self.sector_buf[..ss])?;
-            self.cached_lba = Some(next_lba);
-            self.entry_buf[first..entry_size]
-                .copy_from_slice(&self.sector_buf[..(entry_size - first)]);
+            let n = (ss - in_sector).min(entry_size - copied);
+            self.entry_buf[copied..copied + n]
+                .copy_from_slice(&self.sector_buf[in_sector..in_sector + n]);
+            copied += n;
+            in_sector = 0;
+            lba += 1;
         }
 
         let base = core::mem::size_of::<GptEntry>();
@@ -135,6 +124,35 @@ impl<'io, IO: RimIO + ?Sized, const N: usize> GptStreamReader<'io, IO, N> {
         Ok(None)
     }
 
+    /// Collects every entry matching a predicate into `out`, without allocating.
+    ///
+    /// Returns the number of matches; fails if `out` cannot hold them all.
+    pub fn find_all<F>(&mut self, mut pred: F, out: &mut [(usize, GptEntry)]) -> PartResult<usize>
+    where
+        F: FnMut(&GptEntry) -> bool,
+    {
+        let mut found = 0usize;
+        for i in 0..self.slots() {
+            let e = self.read_at(i)?;
+            if e.is_empty() || !pred(&e) {
+                continue;
+            }
+            if found == out.len() {
+                return Err(PartError::Other("GPT: output slice too small"));
+            }
+            out[found] = (i, e);
+            found += 1;
+        }
+        Ok(found)
+    }
+
+    /// Checks that sector 0 holds a protective MBR (0xAA55 signature, a single
+    /// 0xEE entry covering the disk, clamped to 0xFFFFFFFF sectors).
+    pub fn validate_protective_mbr(&mut self) -> PartResult<()> {
+        let mbr = crate::mbr::read_mbr(&mut *self.io)?;
+        mbr.validate_protective(self.header.backup_lba + 1)
+    }
+
     /// Streaming bounds/alignment validation.
     pub fn validate_bounds(&mut self) -> PartResult<()> {
         for i in 0..self.slots() {
@@ -142,7 +160,7 @@ impl<'io, IO: RimIO + ?Sized, const N: usize> GptStreamReader<'io, IO, N> {
             if entry.is_empty() {
                 continue;
             }
-            self.header.validate_entry(&entry, self.sector_size)?;
+            self.header.validate_entry(&entry)?;
         }
         Ok(())
     }
@@ -174,6 +192,27 @@ impl<'io, IO: RimIO + ?Sized, const N: usize> GptStreamReader<'io, IO, N> {
         Ok(())
     }
 
+    /// O(n²) check that used entries have distinct unique GUIDs, none equal to the disk GUID.
+    pub fn validate_unique_guids(&mut self) -> PartResult<()> {
+        let n = self.slots();
+        for i in 0..n {
+            let ei = self.read_at(i)?;
+            if ei.is_empty() {
+                continue;
+            }
+            if ei.unique_guid == self.header.disk_guid {
+                return Err(GptError::DiskGuidCollision { index: i }.into());
+            }
+            for j in (i + 1)..n {
+                let ej = self.read_at(j)?;
+                if !ej.is_empty() && ej.unique_guid == ei.unique_guid {
+                    return Err(GptError::DuplicateUniqueGuid { a: i, b: j }.into());
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn validate_crc(&mut self) -> PartResult<()> {
         let ss = self.sector_size as usize;
         if ss > N {
@@ -265,11 +304,10 @@ pub struct GptStreamWriter<'io, IO: RimIO + ?Sized, const N: usize> {
     io: &'io mut IO,
     sector_size: u64,
     header: GptHeader,
-    sector: [u8; N],   // reused
-    slot: [u8; N],     // for padded entry (<= entry_size)
-    es: usize,         // entry_size
-    per_sector: usize, // entries par secteur
-    idx: usize,        // current index (0..num_entries)
+    sector: [u8; N], // reused
+    slot: [u8; N],   // for padded entry (<= entry_size)
+    es: usize,       // entry_size
+    idx: usize,      // current index (0..num_entries)
     crc: crc32fast::Hasher,
 }
 
@@ -299,8 +337,7 @@ impl<'io, IO: RimIO + ?Sized, const N: usize> GptStreamWriter<'io, IO, N> {
         if es > N {
             return Err(PartError::Other("GPT: entry_size exceeds stack buffer"));
         }
-        let per_sector = (sector_size as usize) / es;
-        if per_sector == 0 {
+        if (sector_size as usize) < es {
             return Err(GptError::EntrySizeExceedsSector {
                 entry_size: header.entry_size,
                 sector_size,
@@ -314,7 +351,6 @@ impl<'io, IO: RimIO + ?Sized, const N: usize> GptStreamWriter<'io, IO, N> {
             sector: [0; N],
             slot: [0; N],
             es,
-            per_sector,
             idx: 0,
             crc: crc32fast::Hasher::new(),
         })
@@ -328,41 +364,45 @@ impl<'io, IO: RimIO + ?Sized, const N: usize> GptStreamWriter<'io, IO, N> {
         let mut lba = self.header.entries_lba;
         let ss = self.sector_size as usize;
 
-        // fills the entire entry table (non-empty + zeroed slots)
-        let mut left = total;
-        while left > 0 {
-            let take = left.min(self.per_sector);
-            // zero out the sector
-            for b in &mut self.sector[..ss] {
-                *b = 0;
+        // fills the entire entry table (non-empty + zeroed slots) as one
+        // contiguous byte stream: slots may straddle two sectors.
+        let base = core::mem::size_of::<GptEntry>();
+        let mut fill = 0usize;
+        self.sector[..ss].fill(0);
+
+        for _ in 0..total {
+            // build the slot (head = GptEntry, tail = 0)
+            self.slot[..self.es].fill(0);
+            if self.idx < provided {
+                let e = it
+                    .next()
+                    .ok_or(PartError::Other("iterator shorter than `provided`"))?;
+                self.slot[..base].copy_from_slice(e.as_bytes());
             }
-
-            for s in 0..take {
-                // build the slot (head = GptEntry, tail = 0)
-                for b in &mut self.slot[..self.es] {
-                    *b = 0;
-                }
-                if self.idx < provided {
-                    let e = it
-                        .next()
-                        .ok_or(PartError::Other("iterator shorter than `provided`"))?;
-                    let head = e.as_bytes();
-                    let base = core::mem::size_of::<GptEntry>();
-                    self.slot[..base].copy_from_slice(head);
+            self.crc.update(&self.slot[..self.es]); // CRC entries
+
+            // copy the slot into the sector, flushing each sector once full
+            let mut src = 0usize;
+            while src < self.es {
+                let n = (self.es - src).min(ss - fill);
+                self.sector[fill..fill + n].copy_from_slice(&self.slot[src..src + n]);
+                fill += n;
+                src += n;
+                if fill == ss {
+                    self.io
+                        .write_at_lba(lba, self.sector_size, &self.sector[..ss])?;
+                    lba += 1;
+                    fill = 0;
+                    self.sector[..ss].fill(0);
                 }
-                self.crc.update(&self.slot[..self.es]); // CRC entries
-
-                // place the slot in the sector at the correct location
-                let dst = &mut self.sector[s * self.es..(s + 1) * self.es];
-                dst.copy_from_slice(&self.slot[..self.es]);
-
-                self.idx += 1;
             }
 
+            self.idx += 1;
+        }
+
+        if fill > 0 {
             self.io
                 .write_at_lba(lba, self.sector_size, &self.sector[..ss])?;
-            lba += 1;
-            left -= take;
         }
 
         Ok(())
@@ -395,26 +435,14 @@ impl<'io, IO: RimIO + ?Sized, const N: usize> GptStreamWriter<'io, IO, N> {
         };
         backup.header_crc32 = bcrc;
 
-        // copy the entries zone to the backup zone (streaming, sector by sector, no heap)
-        let ss = self.sector_size as usize;
-        let mut remaining = (self.header.num_entries as usize) * self.es;
-        let mut src_lba = self.header.entries_lba;
-        let mut dst_lba = backup.entries_lba;
-
-        while remaining > 0 {
-            // read a source sector
-            for b in &mut self.sector[..ss] {
-                *b = 0;
-            }
-            self.io
-                .read_at_lba(src_lba, self.sector_size, &mut self.sector[..ss])?;
-            // write destination sector
-            self.io
-                .write_at_lba(dst_lba, self.sector_size, &self.sector[..ss])?;
-            src_lba += 1;
-            dst_lba += 1;
-            remaining = remaining.saturating_sub(ss);
-        }
+        // copy the entries zone to the backup zone (streaming, no heap)
+        let ss = self.sector_size;
+        let table_bytes = (self.header.num_entries as u64 * self.es as u64).div_ceil(ss) * ss;
+        self.io.copy_region(
+            self.header.entries_lba * ss,
+            backup.entries_lba * ss,
+            table_bytes,
+        )?;
 
         // Write backup header
         self.io
@@ -431,6 +459,7 @@ mod tests {
 
     use super::*;
     use rimio::prelude::MemRimIO;
+    use zerocopy::FromZeros;
 
     #[test]
     fn gpt_cursor_iter_and_find_first_esp() {
@@ -476,6 +505,58 @@ mod tests {
         reader.validate_overlaps().unwrap();
     }
 
+    #[test]
+    fn gpt_cursor_find_all_data_partitions() {
+        let sector = 512u64;
+        let total_sectors = 20_000u64;
+        let mut buf = vec![0u8; (sector * total_sectors) as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        mbr::write_mbr_protective(&mut io, total_sectors).unwrap();
+        let data = guids::GPT_PARTITION_TYPE_DATA;
+        let parts = [
+            gpt::GptEntry::new(data, [1; 16], 2048, 4095, 0, "data1"),
+            gpt::GptEntry::new(guids::GPT_PARTITION_TYPE_ESP, [2; 16], 4096, 6143, 0, "ESP"),
+            gpt::GptEntry::new(data, [3; 16], 6144, 8191, 0, "data2"),
+        ];
+        gpt::write_gpt_from_entries(&mut io, &parts, total_sectors, [0xAB; 16]).unwrap();
+
+        let mut reader = super::GptStreamReader::<_, 512>::new(&mut io, sector).unwrap();
+        let is_data = |e: &gpt::GptEntry| e.kind() == guids::GptPartitionKind::Data;
+
+        let mut out = [(0, gpt::GptEntry::new_zeroed()); 4];
+        let n = reader.find_all(is_data, &mut out).unwrap();
+        assert_eq!(n, 2);
+        assert_eq!((out[0].0, out[0].1.start_lba), (0, 2048));
+        assert_eq!((out[1].0, out[1].1.start_lba), (2, 6144));
+
+        let mut small = [(0, gpt::GptEntry::new_zeroed()); 1];
+        assert!(reader.find_all(is_data, &mut small).is_err());
+    }
+
+    #[test]
+    fn gpt_cursor_validates_protective_mbr() {
+        let sector = 512u64;
+        let total_sectors = 20_000u64;
+        let mut buf = vec![0u8; (sector * total_sectors) as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        mbr::write_mbr_protective(&mut io, total_sectors).unwrap();
+        let p1 = gpt::GptEntry::new(guids::GPT_PARTITION_TYPE_ESP, [1; 16], 2048, 4095, 0, "ESP");
+        gpt::write_gpt_from_entries(&mut io, &[p1], total_sectors, [0xAB; 16]).unwrap();
+
+        {
+            let mut reader = super::GptStreamReader::<_, 512>::new(&mut io, sector).unwrap();
+            reader.validate_protective_mbr().unwrap();
+        }
+
+        // Someone wiped sector 0: the GPT alone still parses, the MBR check must not.
+        io.write_at(0, &[0u8; 512]).unwrap();
+        let mut reader = super::GptStreamReader::<_, 512>::new(&mut io, sector).unwrap();
+        reader.validate_crc().unwrap();
+        assert!(reader.validate_protective_mbr().is_err());
+    }
+
     #[test]
     fn gpt_cursor_detects_overlap() {
         let sector = 512u64;
@@ -632,7 +713,7 @@ mod tests {
         let mut hdr = gpt::GptHeader::new(sector, total, [0xEE; 16]).unwrap();
         hdr.entry_size = 1024; // > sector
 
-        // from_header must refuse (per_sector == 0)
+        // from_header must refuse (entry larger than a sector)
         let err = GptStreamWriter::<_, 2048>::from_header(&mut io, sector, hdr).unwrap_err();
         match err {
             PartError::Gpt(GptError::EntrySizeExceedsSector {
@@ -681,4 +762,54 @@ mod tests {
         reader.validate_overlaps().unwrap();
         reader.validate_crc().unwrap();
     }
+
+    #[test]
+    fn gpt_stream_192_byte_entries_straddle_sectors() {
+        let sector = 512u64;
+        let total = 20_000u64;
+        let mut buf = vec![0u8; (sector * total) as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        mbr::write_mbr_protective(&mut io, total).unwrap();
+
+        let parts: Vec<_> = (0..5u8)
+            .map(|i| {
+                let start = 2048 + i as u64 * 2048;
+                gpt::GptEntry::new(
+                    guids::GPT_PARTITION_TYPE_DATA,
+                    [i + 1; 16],
+                    start,
+                    start + 2047,
+                    0,
+                    "p",
+                )
+            })
+            .collect();
+
+        {
+            let hdr = gpt::GptHeader::new_with_table(sector, total, [0x19; 16], 128, 192).unwrap();
+            let mut w = GptStreamWriter::<_, 512>::from_header(&mut io, sector, hdr).unwrap();
+            w.write_entries(parts.len(), parts.iter().copied()).unwrap();
+            w.finalize().unwrap();
+        }
+
+        // streaming reader: entries #2 (384..576) and #5 (960..1152) straddle
+        let mut reader = super::GptStreamReader::<_, 512>::new(&mut io, sector).unwrap();
+        let got: Vec<_> = reader.iter().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(got.len(), 5);
+        for (a, b) in got.iter().zip(&parts) {
+            assert_eq!(a.start_lba, b.start_lba);
+            assert_eq!(a.unique_guid, b.unique_guid);
+        }
+        reader.validate_crc().unwrap();
+        reader.validate_overlaps().unwrap();
+
+        // the allocating reader sees the same contiguous table (primary and backup)
+        let (hdr, entries) = gpt::read_gpt_with_sector(&mut io, sector).unwrap();
+        assert_eq!(hdr.entry_size, 192);
+        assert_eq!(entries.len(), 5);
+        let backup: gpt::GptHeader = io.read_struct_lba(hdr.backup_lba, sector).unwrap();
+        let backup_entries = gpt::read_gpt_entries(&mut io, &backup, sector).unwrap();
+        assert_eq!(backup_entries.len(), 5);
+    }
 }
@@ -1,4 +1,8 @@
 // SPDX-License-Identifier: MIT
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 
 crate::define_partition_types! {
     Esp => "EFI System Partition", [
@@ -16,7 +20,7 @@ crate::define_partition_types! {
         0x68, 0xB6, 0xB7, 0x26, 0x99, 0xC7
     ],
     Linux => "Linux Filesystem Partition", [
-        0x0F, 0xC6, 0x3D, 0xAF,
+        0xAF, 0x3D, 0xC6, 0x0F,
         0x83, 0x84,
         0x72, 0x47,
         0x8E, 0x79,
@@ -26,7 +30,7 @@ crate::define_partition_types! {
         0x48, 0x61, 0x68, 0x21,
         0x49, 0x64,
         0x6F, 0x6E,
-        0x74, 0x74,
+        0x74, 0x4E,
         0x65, 0x65, 0x64, 0x45, 0x46, 0x49
     ],
     Swap => "Linux Swap Partition", [
@@ -50,4 +54,204 @@ crate::define_partition_types! {
         0xA1, 0x6A,
         0xBF, 0xD5, 0x01, 0x79, 0xD6, 0xAC
     ],
+    Msr => "Microsoft Reserved Partition", [
+        0x16, 0xE3, 0xC9, 0xE3,
+        0x5C, 0x0B,
+        0xB8, 0x4D,
+        0x81, 0x7D,
+        0xF9, 0x2D, 0xF0, 0x02, 0x15, 0xAE
+    ],
+    Lvm => "Linux LVM Partition", [
+        0x79, 0xD3, 0xD6, 0xE6,
+        0x07, 0xF5,
+        0xC2, 0x44,
+        0xA2, 0x3C,
+        0x23, 0x8F, 0x2A, 0x3D, 0xF9, 0x28
+    ],
+    Chromeos => "ChromeOS Kernel Partition", [
+        0x5D, 0x2A, 0x3A, 0xFE,
+        0x32, 0x4F,
+        0xA7, 0x41,
+        0xB7, 0x25,
+        0xAC, 0xCC, 0x32, 0x85, 0xA3, 0x09
+    ],
+    Hfsplus => "Apple HFS+ Partition", [
+        0x00, 0x53, 0x46, 0x48,
+        0x00, 0x00,
+        0xAA, 0x11,
+        0xAA, 0x11,
+        0x00, 0x30, 0x65, 0x43, 0xEC, 0xAC
+    ],
+}
+
+// GPT stores the first three GUID fields little-endian and the last two as
+// plain bytes: "C12A7328-F81F-11D2-BA4B-00A0C93EC93B" starts with 0x28, 0x73.
+const GUID_FIELD_BYTES: [&[usize]; 5] = [
+    &[3, 2, 1, 0],
+    &[5, 4],
+    &[7, 6],
+    &[8, 9],
+    &[10, 11, 12, 13, 14, 15],
+];
+
+/// Parses a `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX` GUID string (any hex case)
+/// into its GPT on-disk byte order.
+pub fn parse_guid_str(s: &str) -> Option<[u8; 16]> {
+    let mut out = [0u8; 16];
+    let mut fields = s.split('-');
+    for idx in GUID_FIELD_BYTES {
+        let field = fields.next()?.as_bytes();
+        if field.len() != idx.len() * 2 || !field.iter().all(u8::is_ascii_hexdigit) {
+            return None;
+        }
+        for (pair, &i) in field.chunks_exact(2).zip(idx) {
+            let hex = core::str::from_utf8(pair).ok()?;
+            out[i] = u8::from_str_radix(hex, 16).ok()?;
+        }
+    }
+    fields.next().is_none().then_some(out)
+}
+
+/// Same as [`parse_guid_str`], e.g. for a type GUID written in a layout file.
+pub fn parse_guid(s: &str) -> Option<[u8; 16]> {
+    parse_guid_str(s)
+}
+
+/// Writes `guid` (GPT on-disk byte order) in the canonical uppercase form.
+pub fn write_guid<W: core::fmt::Write>(w: &mut W, guid: &[u8; 16]) -> core::fmt::Result {
+    for (n, idx) in GUID_FIELD_BYTES.iter().enumerate() {
+        if n > 0 {
+            w.write_char('-')?;
+        }
+        for &i in *idx {
+            write!(w, "{:02X}", guid[i])?;
+        }
+    }
+    Ok(())
+}
+
+/// Formats `guid` (GPT on-disk byte order) as a canonical uppercase string.
+#[cfg(feature = "alloc")]
+pub fn format_guid(guid: &[u8; 16]) -> String {
+    let mut s = String::with_capacity(36);
+    let _ = write_guid(&mut s, guid);
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ESP: &str = "C12A7328-F81F-11D2-BA4B-00A0C93EC93B";
+
+    #[test]
+    fn known_type_guids_match_their_strings() {
+        for (s, bytes) in [
+            (ESP, GPT_PARTITION_TYPE_ESP),
+            (
+                "EBD0A0A2-B9E5-4433-87C0-68B6B72699C7",
+                GPT_PARTITION_TYPE_DATA,
+            ),
+            (
+                "0FC63DAF-8483-4772-8E79-3D69D8477DE4",
+                GPT_PARTITION_TYPE_LINUX,
+            ),
+            (
+                "21686148-6449-6E6F-744E-656564454649",
+                GPT_PARTITION_TYPE_BIOSBOOT,
+            ),
+            (
+                "0657FD6D-A4AB-43C4-84E5-0933C84B4F4F",
+                GPT_PARTITION_TYPE_SWAP,
+            ),
+            (
+                "BC13C2FF-59E6-4262-A352-B275FD6F7172",
+                GPT_PARTITION_TYPE_BOOT,
+            ),
+            (
+                "DE94BBA4-06D1-4D40-A16A-BFD50179D6AC",
+                GPT_PARTITION_TYPE_RECOVERY,
+            ),
+            (
+                "E3C9E316-0B5C-4DB8-817D-F92DF00215AE",
+                GPT_PARTITION_TYPE_MSR,
+            ),
+            (
+                "E6D6D379-F507-44C2-A23C-238F2A3DF928",
+                GPT_PARTITION_TYPE_LVM,
+            ),
+            (
+                "FE3A2A5D-4F32-41A7-B725-ACCC3285A309",
+                GPT_PARTITION_TYPE_CHROMEOS,
+            ),
+            (
+                "48465300-0000-11AA-AA11-00306543ECAC",
+                GPT_PARTITION_TYPE_HFSPLUS,
+            ),
+        ] {
+            assert_eq!(parse_guid_str(s), Some(bytes), "{s}");
+            assert_eq!(format_guid(&bytes), s);
+        }
+    }
+
+    #[test]
+    fn new_kinds_roundtrip_through_from_guid() {
+        for (kind, guid) in [
+            (GptPartitionKind::Msr, GPT_PARTITION_TYPE_MSR),
+            (GptPartitionKind::Lvm, GPT_PARTITION_TYPE_LVM),
+            (GptPartitionKind::Chromeos, GPT_PARTITION_TYPE_CHROMEOS),
+            (GptPartitionKind::Hfsplus, GPT_PARTITION_TYPE_HFSPLUS),
+            (GptPartitionKind::Swap, GPT_PARTITION_TYPE_SWAP),
+        ] {
+            assert_eq!(GptPartitionKind::from_guid(&guid), kind);
+            assert_eq!(kind.as_guid(), Some(&guid));
+        }
+
+        let other = [0x42; 16];
+        assert_eq!(
+            GptPartitionKind::from_guid(&other),
+            GptPartitionKind::Unknown(other)
+        );
+        assert_eq!(GptPartitionKind::Unknown(other).as_guid(), None);
+    }
+
+    #[test]
+    fn guid_string_roundtrip() {
+        assert_eq!(parse_guid_str(ESP), Some(GPT_PARTITION_TYPE_ESP));
+        assert_eq!(
+            parse_guid_str(&ESP.to_lowercase()),
+            Some(GPT_PARTITION_TYPE_ESP)
+        );
+        assert_eq!(format_guid(&GPT_PARTITION_TYPE_ESP), ESP);
+    }
+
+    #[test]
+    fn parsed_guid_strings_map_to_partition_kinds() {
+        for (s, kind) in [
+            (ESP, GptPartitionKind::Esp),
+            (
+                "0FC63DAF-8483-4772-8E79-3D69D8477DE4",
+                GptPartitionKind::Linux,
+            ),
+        ] {
+            let guid = parse_guid(s).unwrap();
+            assert_eq!(GptPartitionKind::from_guid(&guid), kind);
+            assert_eq!(format_guid(&guid), s);
+            assert_eq!(parse_guid(&format_guid(&guid)), Some(guid));
+        }
+    }
+
+    #[test]
+    fn malformed_guid_strings_are_rejected() {
+        for bad in [
+            "",
+            "C12A7328F81F11D2BA4B00A0C93EC93B",
+            "C12A7328-F81F-11D2-BA4B-00A0C93EC93",
+            "C12A7328-F81F-11D2-BA4B-00A0C93EC93B-00",
+            "C12A7328-F81F-11D2-BA4B-00A0C93EC9GB",
+            "+12A7328-F81F-11D2-BA4B-00A0C93EC93B",
+        ] {
+            assert_eq!(parse_guid_str(bad), None, "{bad}");
+        }
+    }
 }
@@ -16,20 +16,35 @@ pub mod gpt_stream;
 pub mod guids;
 /// Master Boot Record (MBR) and Protective MBR implementation.
 pub mod mbr;
+/// Random sources for GUID generation.
+pub mod rng;
+pub use rng::Rng;
 
 #[cfg(feature = "alloc")]
 pub mod scanner;
 #[cfg(feature = "alloc")]
-pub use scanner::{scan_disk, scan_disk_with_sector};
+pub use scanner::{
+    DetectedFs, detect_fs, scan_disk, scan_disk_nested, scan_disk_nested_with_sector,
+    scan_disk_with_sector,
+};
+
+/// Serializable scan reports (`serde` feature).
+#[cfg(feature = "serde")]
+pub mod report;
 
 pub mod utils;
 
 #[cfg(feature = "alloc")]
 pub use utils::{
-    detect_partition_offset_by_type_guid, truncate_image, truncate_image_custom_sector,
-    validate_full_disk,
+    AlignmentFinding, ConvertedPartition, check_alignment, chs_to_lba, convert_mbr_to_gpt,
+    convert_mbr_to_gpt_with_sector, detect_partition_offset_by_name,
+    detect_partition_offset_by_type_guid, lba_to_chs, mbr_type_to_guid, truncate_image,
+    truncate_image_custom_sector, validate_alignment, validate_full_disk,
 };
 #[cfg(not(feature = "alloc"))]
-pub use utils::{truncate_image, truncate_image_custom_sector};
+pub use utils::{
+    chs_to_lba, detect_partition_offset_by_name, lba_to_chs, mbr_type_to_guid, truncate_image,
+    truncate_image_custom_sector,
+};
 
 pub const DEFAULT_SECTOR_SIZE: u64 = 512;
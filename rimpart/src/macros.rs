@@ -90,7 +90,11 @@ macro_rules! define_partition_types {
                 fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                     match self {
                         $(Self::$name => write!(f, $desc),)+
-                        Self::Unknown(guid) => write!(f, "Unknown ({:02X?})", guid),
+                        Self::Unknown(guid) => {
+                            f.write_str("Unknown (")?;
+                            $crate::guids::write_guid(f, guid)?;
+                            f.write_str(")")
+                        }
                     }
                 }
             }
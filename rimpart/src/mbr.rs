@@ -9,6 +9,8 @@ use crate::errors::*;
 use rimio::prelude::*;
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
+pub use crate::utils::{CHS_MAX, chs_to_lba, lba_to_chs};
+
 pub const MBR_SIGNATURE: [u8; 2] = [0x55, 0xAA];
 pub const PROTECTIVE_GPT: u8 = 0xEE;
 
@@ -25,7 +27,6 @@ fn lba_end_inclusive(start_lba: u32, sectors: u32) -> PartResult<u64> {
     Ok(end)
 }
 
-#[allow(dead_code)]
 #[inline]
 fn is_extended_type(t: u8) -> bool {
     // Classic extended types (CHS/LBA); includes 0x85 (Linux extended)
@@ -64,7 +65,7 @@ fn check_overlaps_legacy(entries: &[MbrEntry]) -> PartResult<()> {
     if segs.len() <= 1 {
         return Ok(());
     }
-    segs.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    segs.sort_unstable_by_key(|s| s.0);
     let mut prev = segs[0];
     for &curr in &segs[1..] {
         if curr.0 <= prev.1 {
@@ -230,6 +231,7 @@ impl MbrEntryPacked {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MbrKind {
     Empty,
     Protective,
@@ -379,6 +381,89 @@ impl Mbr {
     }
 }
 
+/// Decoded cylinder/head/sector address of an MBR entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chs {
+    pub cylinder: u16,
+    pub head: u8,
+    /// 1-based, 0 only in unused entries
+    pub sector: u8,
+}
+
+impl Chs {
+    /// Decodes the on-disk `[head, sector | cyl_hi << 6, cyl_lo]` triplet.
+    #[inline]
+    pub fn decode(raw: [u8; 3]) -> Self {
+        Self {
+            cylinder: ((raw[1] as u16 & 0xC0) << 2) | raw[2] as u16,
+            head: raw[0],
+            sector: raw[1] & 0x3F,
+        }
+    }
+
+    /// Encodes back to the on-disk triplet (cylinder truncated to 10 bits).
+    #[inline]
+    pub fn encode(self) -> [u8; 3] {
+        [
+            self.head,
+            (self.sector & 0x3F) | ((self.cylinder >> 2) as u8 & 0xC0),
+            self.cylinder as u8,
+        ]
+    }
+}
+
+/// Decoded view of a primary MBR partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MbrPartition {
+    pub bootable: bool,
+    pub type_byte: u8,
+    pub start_chs: Chs,
+    pub end_chs: Chs,
+    pub start_lba: u32,
+    pub sectors: u32,
+}
+
+impl MbrPartition {
+    /// `None` for an empty slot.
+    pub fn from_entry(e: &MbrEntry) -> Option<Self> {
+        if e.is_empty() {
+            return None;
+        }
+        Some(Self {
+            bootable: e.boot_flag == 0x80,
+            type_byte: e.part_type,
+            start_chs: Chs::decode(e.starting_chs),
+            end_chs: Chs::decode(e.end_chs),
+            start_lba: e.start_lba,
+            sectors: e.sectors,
+        })
+    }
+
+    #[inline]
+    pub fn end_lba(&self) -> u64 {
+        (self.start_lba as u64 + self.sectors as u64).saturating_sub(1)
+    }
+
+    #[inline]
+    pub fn is_extended(&self) -> bool {
+        is_extended_type(self.type_byte)
+    }
+}
+
+impl Mbr {
+    /// The four primary slots, decoded; empty slots are `None`.
+    pub fn partitions(&self) -> [Option<MbrPartition>; 4] {
+        self.aligned_entries().map(|e| MbrPartition::from_entry(&e))
+    }
+}
+
+/// Reads the MBR at LBA 0 and decodes its four primary entries.
+pub fn list_partitions<IO: RimIO + ?Sized>(io: &mut IO) -> PartResult<[Option<MbrPartition>; 4]> {
+    let mbr = read_mbr(io)?;
+    mbr.validate_entries_basic()?;
+    Ok(mbr.partitions())
+}
+
 pub fn write_mbr<IO: RimIO + ?Sized>(io: &mut IO, mbr: &Mbr) -> PartResult<()> {
     io.write_struct(0, mbr)?;
     io.flush()?;
@@ -404,6 +489,32 @@ pub fn read_mbr<IO: RimIO + ?Sized>(io: &mut IO) -> PartResult<Mbr> {
     Ok(mbr)
 }
 
+/// Sets or clears the active (0x80) flag of primary entry `index`.
+///
+/// Activating an entry clears the flag on the other three, as legacy BIOS
+/// boot code expects a single active partition. Empty slots cannot be
+/// activated.
+pub fn set_bootable<IO: RimIO + ?Sized>(io: &mut IO, index: usize, active: bool) -> PartResult<()> {
+    let mut mbr = read_mbr(io)?;
+    let mut entries = mbr.aligned_entries();
+    let Some(target) = entries.get(index) else {
+        return Err(MbrError::NoSuchEntry { index }.into());
+    };
+    if active && target.is_empty() {
+        return Err(MbrError::NoSuchEntry { index }.into());
+    }
+
+    for (i, e) in entries.iter_mut().enumerate() {
+        if i == index {
+            e.boot_flag = if active { 0x80 } else { 0x00 };
+        } else if active {
+            e.boot_flag = 0x00;
+        }
+    }
+    mbr.entries = entries.map(|e| MbrEntryPacked::from_aligned(&e));
+    write_mbr(io, &mbr)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -463,4 +574,91 @@ mod tests {
         assert_eq!(e0.part_type, 0x83);
         assert_eq!(e0.boot_flag, 0x80);
     }
+
+    #[test]
+    fn list_partitions_decodes_chs() {
+        let mut buf = [0u8; 512];
+        // Bootable FAT32 LBA at 2048 (CHS 0/32/33), ends at 1050623 (65/101/36)
+        buf[446..462].copy_from_slice(&[
+            0x80, 0x20, 0x21, 0x00, 0x0C, 0x65, 0x24, 0x41, //
+            0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00,
+        ]);
+        // Linux past the CHS limit: both addresses clamped to 1023/254/63
+        buf[462..478].copy_from_slice(&[
+            0x00, 0xFE, 0xFF, 0xFF, 0x83, 0xFE, 0xFF, 0xFF, //
+            0x00, 0x08, 0x10, 0x00, 0x00, 0x00, 0x40, 0x00,
+        ]);
+        buf[510..].copy_from_slice(&MBR_SIGNATURE);
+        let mut io = MemRimIO::new(&mut buf);
+
+        let parts = list_partitions(&mut io).unwrap();
+        let esp = parts[0].unwrap();
+        assert!(esp.bootable);
+        assert_eq!(esp.type_byte, 0x0C);
+        assert_eq!((esp.start_lba, esp.sectors), (2048, 0x10_0000));
+        assert_eq!(esp.end_lba(), 2048 + 0x10_0000 - 1);
+        let start = Chs {
+            cylinder: 0,
+            head: 32,
+            sector: 33,
+        };
+        assert_eq!(esp.start_chs, start);
+        assert_eq!(
+            esp.end_chs,
+            Chs {
+                cylinder: 65,
+                head: 101,
+                sector: 36
+            }
+        );
+        assert_eq!(start.encode(), [0x20, 0x21, 0x00]);
+
+        let linux = parts[1].unwrap();
+        assert!(!linux.bootable && !linux.is_extended());
+        assert_eq!(linux.start_lba, 0x10_0800);
+        let max = Chs {
+            cylinder: 1023,
+            head: 254,
+            sector: 63,
+        };
+        assert_eq!((linux.start_chs, linux.end_chs), (max, max));
+        assert_eq!(max.encode(), [0xFE, 0xFF, 0xFF]);
+
+        assert!(parts[2].is_none() && parts[3].is_none());
+    }
+
+    #[test]
+    fn set_bootable_flags_single_entry() {
+        let linux = |start| MbrEntry::new(0x00, [0; 3], 0x83, [0; 3], start, 2048);
+        let mut entries = [MbrEntry::new_empty(); 4];
+        entries[0] = MbrEntry::new(0x80, [0; 3], 0x0C, [0; 3], 2048, 2048);
+        entries[1] = linux(4096);
+        entries[2] = linux(6144);
+
+        let mut buf = [0u8; 512];
+        let mut io = MemRimIO::new(&mut buf);
+        write_mbr_from_entries(&mut io, entries).unwrap();
+        io.write_at(0, &[0xCC; 446]).unwrap();
+
+        set_bootable(&mut io, 1, true).unwrap();
+        let mbr = read_mbr(&mut io).unwrap();
+        let flags = mbr.aligned_entries().map(|e| e.boot_flag);
+        assert_eq!(flags, [0x00, 0x80, 0x00, 0x00]);
+        assert!(mbr.has_valid_signature());
+        assert_eq!(mbr.aligned_entries()[1].start_lba, 4096);
+        assert!({ mbr.boot_code }.iter().all(|&b| b == 0xCC));
+
+        set_bootable(&mut io, 1, false).unwrap();
+        let flags = read_mbr(&mut io)
+            .unwrap()
+            .aligned_entries()
+            .map(|e| e.boot_flag);
+        assert_eq!(flags, [0; 4]);
+
+        assert!(matches!(
+            set_bootable(&mut io, 4, true),
+            Err(PartError::Mbr(MbrError::NoSuchEntry { index: 4 }))
+        ));
+        assert!(set_bootable(&mut io, 3, true).is_err());
+    }
 }
@@ -0,0 +1,187 @@
+// SPDX-License-Identifier: MIT
+
+//! Serializable views of scan results.
+//!
+//! The on-disk structures ([`GptHeader`], [`GptEntry`]) stay plain zerocopy
+//! types; these DTOs carry decoded names and GUID strings instead of raw bytes.
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gpt::{GptEntry, GptHeader},
+    guids::format_guid,
+    mbr::MbrKind,
+    scanner::{DetectedFs, DiskInfo, PartitionInfo},
+};
+
+/// Serializable form of [`DiskInfo`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskReport {
+    pub mbr_kind: MbrKind,
+    pub sector_size: u64,
+    pub gpt: Option<GptHeaderReport>,
+    pub partitions: Vec<PartitionReport>,
+}
+
+/// Serializable form of [`GptHeader`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GptHeaderReport {
+    pub disk_guid: String,
+    pub revision: u32,
+    pub current_lba: u64,
+    pub backup_lba: u64,
+    pub first_usable_lba: u64,
+    pub last_usable_lba: u64,
+    pub entries_lba: u64,
+    pub num_entries: u32,
+    pub entry_size: u32,
+    pub header_crc32: u32,
+    pub entries_crc32: u32,
+}
+
+/// Serializable form of [`GptEntry`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GptEntryReport {
+    pub type_guid: String,
+    pub unique_guid: String,
+    pub start_lba: u64,
+    pub end_lba: u64,
+    pub attributes: u64,
+    pub name: String,
+}
+
+/// Serializable form of [`PartitionInfo`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartitionReport {
+    pub index: usize,
+    /// Human-readable partition type, e.g. `EFI System Partition`
+    pub kind: String,
+    pub type_guid: String,
+    pub unique_guid: String,
+    pub start_lba: u64,
+    pub end_lba: u64,
+    pub start_bytes: u64,
+    pub size_bytes: u64,
+    pub attrs: u64,
+    pub name: String,
+    pub fs: DetectedFs,
+    pub depth: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nested: Vec<PartitionReport>,
+}
+
+impl From<&DiskInfo> for DiskReport {
+    fn from(info: &DiskInfo) -> Self {
+        Self {
+            mbr_kind: info.mbr_kind,
+            sector_size: info.sector_size,
+            gpt: info.gpt_header.as_ref().map(GptHeaderReport::from),
+            partitions: info.partitions.iter().map(PartitionReport::from).collect(),
+        }
+    }
+}
+
+impl From<&GptHeader> for GptHeaderReport {
+    fn from(h: &GptHeader) -> Self {
+        Self {
+            disk_guid: format_guid(&h.disk_guid),
+            revision: h.revision,
+            current_lba: h.current_lba,
+            backup_lba: h.backup_lba,
+            first_usable_lba: h.first_usable_lba,
+            last_usable_lba: h.last_usable_lba,
+            entries_lba: h.entries_lba,
+            num_entries: h.num_entries,
+            entry_size: h.entry_size,
+            header_crc32: h.header_crc32,
+            entries_crc32: h.entries_crc32,
+        }
+    }
+}
+
+impl From<&GptEntry> for GptEntryReport {
+    fn from(e: &GptEntry) -> Self {
+        Self {
+            type_guid: format_guid(&e.type_guid),
+            unique_guid: format_guid(&e.unique_guid),
+            start_lba: e.start_lba,
+            end_lba: e.end_lba,
+            attributes: e.attributes,
+            name: e.name_string(),
+        }
+    }
+}
+
+impl From<&PartitionInfo> for PartitionReport {
+    fn from(p: &PartitionInfo) -> Self {
+        Self {
+            index: p.index,
+            kind: p.kind.to_string(),
+            type_guid: format_guid(&p.type_guid),
+            unique_guid: format_guid(&p.unique_guid),
+            start_lba: p.start_lba,
+            end_lba: p.end_lba,
+            start_bytes: p.start_bytes,
+            size_bytes: p.size_bytes,
+            attrs: p.attrs,
+            name: p.name.clone(),
+            fs: p.fs,
+            depth: p.depth,
+            nested: p.nested.iter().map(PartitionReport::from).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gpt, guids, mbr, scanner::scan_disk};
+
+    #[test]
+    fn scan_serializes_to_json() {
+        let mut buf = vec![0u8; 512 * 20_000];
+        let mut io = rimio::prelude::MemRimIO::new(&mut buf);
+
+        mbr::write_mbr_protective(&mut io, 20_000).unwrap();
+        let p1 = GptEntry::new(
+            guids::GPT_PARTITION_TYPE_ESP,
+            [1; 16],
+            2048,
+            4095,
+            0,
+            "EFI-SYSTEM",
+        );
+        gpt::write_gpt_from_entries(&mut io, &[p1], 20_000, [0xAB; 16]).unwrap();
+
+        let info = scan_disk(&mut io).unwrap();
+        let report = DiskReport::from(&info);
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["mbr_kind"], "Protective");
+        assert_eq!(
+            json["gpt"]["disk_guid"],
+            "ABABABAB-ABAB-ABAB-ABAB-ABABABABABAB"
+        );
+        let part = &json["partitions"][0];
+        assert_eq!(part["name"], "EFI-SYSTEM");
+        assert_eq!(part["kind"], "EFI System Partition");
+        assert_eq!(part["type_guid"], "C12A7328-F81F-11D2-BA4B-00A0C93EC93B");
+        assert_eq!(part["unique_guid"], "01010101-0101-0101-0101-010101010101");
+        assert_eq!(part["start_lba"], 2048);
+        assert_eq!(part["fs"], "Unknown");
+        assert!(part.get("nested").is_none());
+
+        let entry = GptEntryReport::from(&p1);
+        assert_eq!(entry.name, "EFI-SYSTEM");
+        assert_eq!(entry.type_guid, part["type_guid"]);
+
+        let back: DiskReport = serde_json::from_value(json).unwrap();
+        assert_eq!(back, report);
+    }
+}
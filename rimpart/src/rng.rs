@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MIT
+
+//! Random sources for GUID generation.
+//!
+//! The crate stays `no_std`: callers bring their own [`Rng`]. With the
+//! `getrandom` feature [`OsRng`] draws from the operating system;
+//! [`SplitMix64`] is a seeded generator for tests and reproducible images.
+
+use crate::errors::PartResult;
+
+/// Source of random bytes.
+pub trait Rng {
+    /// Fills `dest` entirely with random bytes, or fails when the source is
+    /// unavailable.
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> PartResult;
+}
+
+/// Operating system randomness (via `getrandom`).
+#[cfg(feature = "getrandom")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsRng;
+
+#[cfg(feature = "getrandom")]
+impl Rng for OsRng {
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> PartResult {
+        getrandom::fill(dest)
+            .map_err(|_| crate::errors::PartError::Other("OS random source unavailable"))
+    }
+}
+
+/// Deterministic SplitMix64 generator: same seed, same bytes.
+///
+/// Not suitable where unpredictability matters.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> PartResult {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        Ok(())
+    }
+}
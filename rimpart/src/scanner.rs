@@ -12,16 +12,76 @@ use crate::{
     DEFAULT_SECTOR_SIZE,
     errors::*,
     gpt::{self, GptHeader},
-    guids::GptPartitionKind,
+    guids::{self, GptPartitionKind},
     mbr::{self, Mbr, MbrKind, PROTECTIVE_GPT},
 };
 
+/// Hard limit on nested partition table recursion.
+pub const MAX_SCAN_DEPTH: usize = 4;
+
+// Filesystem signatures, mirrored from rimfs (rimpart doesn't depend on it).
+const FAT32_FS_TYPE_OFFSET: u64 = 82;
+const FAT32_FS_TYPE: &[u8; 8] = b"FAT32   ";
+const EXFAT_FS_NAME_OFFSET: u64 = 3;
+const EXFAT_FS_NAME: &[u8; 8] = b"EXFAT   ";
+const EXT4_SUPERBLOCK_OFFSET: u64 = 1024;
+const EXT4_MAGIC_OFFSET: u64 = 56;
+const EXT4_SUPERBLOCK_MAGIC: u16 = 0xEF53;
+
+/// Filesystem found at the start of a partition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DetectedFs {
+    Fat32,
+    ExFat,
+    Ext4,
+    Unknown,
+}
+
+impl core::fmt::Display for DetectedFs {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            DetectedFs::Fat32 => "fat32",
+            DetectedFs::ExFat => "exfat",
+            DetectedFs::Ext4 => "ext4",
+            DetectedFs::Unknown => "unknown",
+        })
+    }
+}
+
+/// Classifies the filesystem starting at `offset` from its boot sector or superblock.
+pub fn detect_fs<IO: RimIO + ?Sized>(io: &mut IO, offset: u64) -> RimIOResult<DetectedFs> {
+    let mut sector = [0u8; 512];
+    io.read_at(offset, &mut sector)?;
+
+    let at = |off: u64, len: usize| &sector[off as usize..off as usize + len];
+    if at(EXFAT_FS_NAME_OFFSET, 8) == EXFAT_FS_NAME {
+        return Ok(DetectedFs::ExFat);
+    }
+    if at(FAT32_FS_TYPE_OFFSET, 8) == FAT32_FS_TYPE {
+        return Ok(DetectedFs::Fat32);
+    }
+
+    let mut magic = [0u8; 2];
+    io.read_at(
+        offset + EXT4_SUPERBLOCK_OFFSET + EXT4_MAGIC_OFFSET,
+        &mut magic,
+    )?;
+    if u16::from_le_bytes(magic) == EXT4_SUPERBLOCK_MAGIC {
+        return Ok(DetectedFs::Ext4);
+    }
+
+    Ok(DetectedFs::Unknown)
+}
+
 /// Partition information
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone)]
 pub struct PartitionInfo {
     pub index: usize,
     pub kind: GptPartitionKind,
+    /// Raw partition type GUID, as stored in the entry
+    pub type_guid: [u8; 16],
     pub unique_guid: [u8; 16],
     pub start_lba: u64,
     pub end_lba: u64,
@@ -29,6 +89,14 @@ pub struct PartitionInfo {
     pub size_bytes: u64,
     pub attrs: u64,
     pub name: String,
+    /// Filesystem found in the partition's first sectors
+    pub fs: DetectedFs,
+    /// Nesting level: 0 for the disk's own table, 1 for a table found inside a partition, ...
+    pub depth: usize,
+    /// Partitions of a table embedded in this partition (see `scan_disk_nested`).
+    ///
+    /// Their LBAs are relative to this partition, `start_bytes` stays absolute.
+    pub nested: Vec<PartitionInfo>,
 }
 
 /// Global scan result
@@ -55,14 +123,28 @@ impl core::fmt::Display for DiskInfo {
                 "absent"
             }
         )?;
-        for p in &self.partitions {
-            writeln!(
-                f,
-                "  + part[{}] name={} type={} lba={}..{} size={}",
-                p.index, p.name, p.kind, p.start_lba, p.end_lba, p.size_bytes
-            )?;
+        fn rows(f: &mut core::fmt::Formatter<'_>, parts: &[PartitionInfo]) -> core::fmt::Result {
+            for p in parts {
+                for _ in 0..p.depth {
+                    write!(f, "  ")?;
+                }
+                writeln!(
+                    f,
+                    "  + part[{}] name={} type={} fs={} guid={} lba={}..{} size={}",
+                    p.index,
+                    p.name,
+                    p.kind,
+                    p.fs,
+                    guids::format_guid(&p.unique_guid),
+                    p.start_lba,
+                    p.end_lba,
+                    p.size_bytes
+                )?;
+                rows(f, &p.nested)?;
+            }
+            Ok(())
         }
-        Ok(())
+        rows(f, &self.partitions)
     }
 
     #[cfg(feature = "std")]
@@ -80,36 +162,51 @@ impl core::fmt::Display for DiskInfo {
             self.mbr_kind,
             gpt_state
         )?;
+        if let Some(hdr) = &self.gpt_header {
+            writeln!(f, "  Disk GUID: {}", guids::format_guid(&hdr.disk_guid))?;
+        }
 
         writeln!(
             f,
-            "  ┌────┬──────────────────────────────┬──────────────────────────────┬────────────┬────────────┬───────────────┐"
+            "  ┌────┬──────────────────────────────┬──────────────────────────────┬─────────┬──────────────────────────────────────┬────────────┬────────────┬───────────────┐"
         )?;
         writeln!(
             f,
-            "  | Id | Name                         | Type                         | Start LBA  | End LBA    | Size          |"
+            "  | Id | Name                         | Type                         | FS      | Unique GUID                          | Start LBA  | End LBA    | Size          |"
         )?;
         writeln!(
             f,
-            "  ├────┼──────────────────────────────┼──────────────────────────────┼────────────┼────────────┼───────────────┤"
+            "  ├────┼──────────────────────────────┼──────────────────────────────┼─────────┼──────────────────────────────────────┼────────────┼────────────┼───────────────┤"
         )?;
 
-        for p in &self.partitions {
-            writeln!(
-                f,
-                "  | {:<2} | {:<28} | {:<28} | {:>10} | {:>10} | {:>13} |",
-                p.index,
-                truncate(&p.name, 28),
-                truncate(&p.kind.to_string(), 28),
-                sep_u64(p.start_lba),
-                sep_u64(p.end_lba),
-                pretty_bytes(p.size_bytes),
-            )?;
+        fn rows(f: &mut core::fmt::Formatter<'_>, parts: &[PartitionInfo]) -> core::fmt::Result {
+            for p in parts {
+                let name = if p.depth == 0 {
+                    p.name.clone()
+                } else {
+                    format!("{}↳ {}", "  ".repeat(p.depth - 1), p.name)
+                };
+                writeln!(
+                    f,
+                    "  | {:<2} | {:<28} | {:<28} | {:<7} | {:<36} | {:>10} | {:>10} | {:>13} |",
+                    p.index,
+                    truncate(&name, 28),
+                    truncate(&p.kind.to_string(), 28),
+                    p.fs.to_string(),
+                    guids::format_guid(&p.unique_guid),
+                    sep_u64(p.start_lba),
+                    sep_u64(p.end_lba),
+                    pretty_bytes(p.size_bytes),
+                )?;
+                rows(f, &p.nested)?;
+            }
+            Ok(())
         }
+        rows(f, &self.partitions)?;
 
         writeln!(
             f,
-            "  └────┴──────────────────────────────┴──────────────────────────────┴────────────┴────────────┴───────────────┘"
+            "  └────┴──────────────────────────────┴──────────────────────────────┴─────────┴──────────────────────────────────────┴────────────┴────────────┴───────────────┘"
         )
     }
 }
@@ -119,6 +216,40 @@ impl core::fmt::Display for DiskInfo {
 pub fn scan_disk_with_sector<IO: RimIO + ?Sized>(
     io: &mut IO,
     sector_size: u64,
+) -> PartResult<DiskInfo> {
+    scan_at(io, sector_size, 0, 0, 0)
+}
+
+#[cfg(feature = "alloc")]
+pub fn scan_disk<IO: RimIO + ?Sized>(io: &mut IO) -> PartResult<DiskInfo> {
+    scan_disk_with_sector(io, DEFAULT_SECTOR_SIZE)
+}
+
+/// Like `scan_disk_with_sector`, but also probes each partition for an embedded
+/// MBR/GPT and recurses up to `max_depth` levels (clamped to `MAX_SCAN_DEPTH`).
+///
+/// Partitions that do not hold a readable GPT are left with an empty `nested` list.
+#[cfg(feature = "alloc")]
+pub fn scan_disk_nested_with_sector<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+    max_depth: usize,
+) -> PartResult<DiskInfo> {
+    scan_at(io, sector_size, 0, 0, max_depth.min(MAX_SCAN_DEPTH))
+}
+
+#[cfg(feature = "alloc")]
+pub fn scan_disk_nested<IO: RimIO + ?Sized>(io: &mut IO, max_depth: usize) -> PartResult<DiskInfo> {
+    scan_disk_nested_with_sector(io, DEFAULT_SECTOR_SIZE, max_depth)
+}
+
+#[cfg(feature = "alloc")]
+fn scan_at<IO: RimIO + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+    base_bytes: u64,
+    depth: usize,
+    max_depth: usize,
 ) -> PartResult<DiskInfo> {
     // Read raw MBR (don't fail if non-protective)
     let mbr: Mbr = io.read_struct(0)?;
@@ -157,18 +288,40 @@ pub fn scan_disk_with_sector<IO: RimIO + ?Sized>(
                 .checked_mul(sector_size)
                 .ok_or(PartError::Other("size_bytes overflow"))?;
 
-            let name = e.name;
+            let nested = if depth < max_depth {
+                // Go through `dyn RimIO` so nested views don't grow the IO type at each level.
+                let mut view = PartitionRimIO::new(io, start_bytes, size_bytes);
+                match scan_at::<dyn RimIO>(
+                    &mut view,
+                    sector_size,
+                    base_bytes + start_bytes,
+                    depth + 1,
+                    max_depth,
+                ) {
+                    Ok(inner) if inner.gpt_header.is_some() => inner.partitions,
+                    _ => Vec::new(),
+                }
+            } else {
+                Vec::new()
+            };
+
+            // An unreadable first sector just means nothing was recognised.
+            let fs = detect_fs(io, start_bytes).unwrap_or(DetectedFs::Unknown);
 
             parts.push(PartitionInfo {
                 index: idx,
                 kind: GptPartitionKind::from_guid(&e.type_guid),
+                type_guid: e.type_guid,
                 unique_guid: e.unique_guid,
                 start_lba,
                 end_lba,
-                start_bytes,
+                start_bytes: base_bytes + start_bytes,
                 size_bytes,
                 attrs: e.attributes,
-                name: gpt::decode_gpt_name(&name),
+                name: e.name_string(),
+                fs,
+                depth,
+                nested,
             });
         }
     }
@@ -181,16 +334,16 @@ pub fn scan_disk_with_sector<IO: RimIO + ?Sized>(
     })
 }
 
-#[cfg(feature = "alloc")]
-pub fn scan_disk<IO: RimIO + ?Sized>(io: &mut IO) -> PartResult<DiskInfo> {
-    scan_disk_with_sector(io, DEFAULT_SECTOR_SIZE)
-}
-
 fn truncate(s: &str, max: usize) -> &str {
     if s.len() <= max {
         return s;
     }
-    &s[..max]
+    // Don't split a multi-byte char (e.g. the nesting arrow).
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
 }
 
 #[cfg(feature = "alloc")]
@@ -274,6 +427,145 @@ mod tests {
         assert_eq!(info.partitions[1].name, "rootfs");
 
         // Display (smoke)
+        let report = info.to_string();
+        assert!(
+            report.contains("ABABABAB-ABAB-ABAB-ABAB-ABABABABABAB"),
+            "{report}"
+        );
+        assert!(
+            report.contains("01010101-0101-0101-0101-010101010101"),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn scan_nested_gpt_in_partition() {
+        const DISK: u64 = 40_000;
+        let mut buf = vec![0u8; (512 * DISK) as usize];
+        let mut io = rimio::prelude::MemRimIO::new(&mut buf);
+
+        mbr::write_mbr_protective(&mut io, DISK).unwrap();
+        let outer = GptEntry::new(
+            guids::GPT_PARTITION_TYPE_DATA,
+            [1; 16],
+            2048,
+            DISK - 2048,
+            0,
+            "whole-disk",
+        );
+        gpt::write_gpt_from_entries(&mut io, &[outer], DISK, [0xAB; 16]).unwrap();
+
+        // A complete disk image inside the outer partition.
+        let inner_sectors = DISK - 4096 + 1;
+        {
+            let mut view = PartitionRimIO::new(&mut io, 2048 * 512, inner_sectors * 512);
+            mbr::write_mbr_protective(&mut view, inner_sectors).unwrap();
+            let p1 = GptEntry::new(
+                guids::GPT_PARTITION_TYPE_ESP,
+                [2; 16],
+                2048,
+                4095,
+                0,
+                "inner-esp",
+            );
+            let p2 = GptEntry::new(
+                guids::GPT_PARTITION_TYPE_LINUX,
+                [3; 16],
+                4096,
+                8191,
+                0,
+                "inner-root",
+            );
+            gpt::write_gpt_from_entries(&mut view, &[p1, p2], inner_sectors, [0xCD; 16]).unwrap();
+        }
+
+        // Default scan doesn't recurse.
+        let flat = scan_disk(&mut io).unwrap();
+        assert!(flat.partitions[0].nested.is_empty());
+
+        let info = scan_disk_nested(&mut io, 1).unwrap();
+        assert_eq!(info.partitions.len(), 1);
+        let nested = &info.partitions[0].nested;
+        assert_eq!(nested.len(), 2);
+        assert!(nested.iter().all(|p| p.depth == 1));
+        assert_eq!(nested[0].name, "inner-esp");
+        assert_eq!(nested[0].start_lba, 2048);
+        assert_eq!(nested[0].start_bytes, (2048 + 2048) * 512);
+        assert_eq!(nested[1].name, "inner-root");
+
         println!("{info}");
     }
+
+    #[test]
+    fn scan_detects_filesystems() {
+        use rimfs::{exfat::*, ext4::*, fat32::*};
+
+        const DISK: u64 = 4 * SECTORS + 4096;
+        const SECTORS: u64 = 65_536; // 32 MiB, enough for FAT32
+        let mut buf = vec![0u8; (512 * DISK) as usize];
+        let mut io = rimio::prelude::MemRimIO::new(&mut buf);
+
+        mbr::write_mbr_protective(&mut io, DISK).unwrap();
+        let entries: Vec<_> = ["fat", "exfat", "ext4", "raw"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let start = 2048 + i as u64 * SECTORS;
+                GptEntry::new(
+                    guids::GPT_PARTITION_TYPE_DATA,
+                    [i as u8 + 1; 16],
+                    start,
+                    start + SECTORS - 1,
+                    0,
+                    name,
+                )
+            })
+            .collect();
+        gpt::write_gpt_from_entries(&mut io, &entries, DISK, [0xAB; 16]).unwrap();
+
+        let size = SECTORS * 512;
+
+        {
+            let mut part = PartitionRimIO::new(&mut io, 2048 * 512, size);
+            let meta = Fat32Meta::new(size, Some("FAT")).unwrap();
+            Fat32Formatter::new(&mut part, &meta).format(false).unwrap();
+        }
+        {
+            let mut part = PartitionRimIO::new(&mut io, (2048 + SECTORS) * 512, size);
+            let meta = ExFatMeta::new(size, Some("EXFAT")).unwrap();
+            ExFatFormatter::new(&mut part, &meta).format(false).unwrap();
+        }
+        {
+            let mut part = PartitionRimIO::new(&mut io, (2048 + 2 * SECTORS) * 512, size);
+            let meta = Ext4Meta::new(size, Some("root"));
+            Ext4Formatter::new(&mut part, &meta).format(false).unwrap();
+        }
+
+        let info = scan_disk(&mut io).unwrap();
+        let found: Vec<_> = info.partitions.iter().map(|p| p.fs).collect();
+        assert_eq!(
+            found,
+            [
+                DetectedFs::Fat32,
+                DetectedFs::ExFat,
+                DetectedFs::Ext4,
+                DetectedFs::Unknown
+            ]
+        );
+        assert!(info.to_string().contains("| exfat   |"));
+    }
+
+    #[test]
+    fn signatures_match_rimfs() {
+        assert_eq!(FAT32_FS_TYPE, rimfs::fs::fat32::constant::FAT_FS_TYPE);
+        assert_eq!(EXFAT_FS_NAME, rimfs::fs::exfat::constant::EXFAT_FS_NAME);
+        assert_eq!(
+            EXT4_SUPERBLOCK_MAGIC,
+            rimfs::fs::ext4::constant::EXT4_SUPERBLOCK_MAGIC
+        );
+        assert_eq!(
+            EXT4_SUPERBLOCK_OFFSET,
+            rimfs::fs::ext4::constant::EXT4_SUPERBLOCK_OFFSET
+        );
+    }
 }
@@ -1,8 +1,54 @@
 // SPDX-License-Identifier: MIT
 
-use crate::{DEFAULT_SECTOR_SIZE, errors::*, gpt, mbr};
+use crate::{DEFAULT_SECTOR_SIZE, errors::*, gpt, gpt_stream::GptStreamReader, mbr};
 use rimio::prelude::*;
 
+/// Standard BIOS translation geometry used for MBR CHS fields.
+pub const CHS_HEADS: u8 = 255;
+pub const CHS_SECTORS_PER_TRACK: u8 = 63;
+/// Highest encodable address with the standard geometry (1023/254/63),
+/// written for anything beyond ~8 GiB.
+pub const CHS_MAX: [u8; 3] = [0xFE, 0xFF, 0xFF];
+
+/// Encodes `lba` as an MBR CHS triplet for the given geometry.
+///
+/// LBAs past cylinder 1023 (or a degenerate geometry) clamp to the
+/// highest address, i.e. [`CHS_MAX`] for 255/63.
+pub fn lba_to_chs(lba: u64, heads: u8, sectors_per_track: u8) -> [u8; 3] {
+    let (heads, spt) = (heads as u64, (sectors_per_track & 0x3F) as u64);
+    if heads == 0 || spt == 0 {
+        return CHS_MAX;
+    }
+    let cylinder = lba / (heads * spt);
+    let chs = if cylinder > 1023 {
+        mbr::Chs {
+            cylinder: 1023,
+            head: (heads - 1) as u8,
+            sector: spt as u8,
+        }
+    } else {
+        mbr::Chs {
+            cylinder: cylinder as u16,
+            head: (lba / spt % heads) as u8,
+            sector: (lba % spt + 1) as u8,
+        }
+    };
+    chs.encode()
+}
+
+/// Decodes an MBR CHS triplet back to an LBA for the given geometry.
+///
+/// `None` when the address is invalid for it (sector 0, head or sector out
+/// of range).
+pub fn chs_to_lba(chs: [u8; 3], heads: u8, sectors_per_track: u8) -> Option<u64> {
+    let chs = mbr::Chs::decode(chs);
+    if chs.sector == 0 || chs.sector > sectors_per_track || chs.head >= heads {
+        return None;
+    }
+    let (heads, spt) = (heads as u64, sectors_per_track as u64);
+    Some((chs.cylinder as u64 * heads + chs.head as u64) * spt + chs.sector as u64 - 1)
+}
+
 /// Report struct for truncate_image (optional, allows nice reporting)
 #[derive(Debug, Clone, Copy)]
 pub struct TruncateReport {
@@ -77,20 +123,216 @@ pub fn detect_partition_offset_by_type_guid_with_sector(
     Ok(part.start_lba.saturating_mul(sector_size))
 }
 
+/// Detect the byte offset of the first partition named `name` (case-sensitive).
+/// - Verifies MBR is protective (0xEE)
+/// - Streams the GPT entries (no allocation) and stops at the first match
+pub fn detect_partition_offset_by_name(
+    io: &mut dyn RimIO,
+    name: &str,
+    sector_size: u64,
+) -> PartResult<Option<u64>> {
+    let m = mbr::read_mbr(io)?;
+    m.validate_protective(0)?;
+    let mut reader = GptStreamReader::<_, 4096>::new(io, sector_size)?;
+    let found = reader.find_first(|e| e.name_eq(name))?;
+    Ok(found.map(|(_, e)| e.start_lba.saturating_mul(sector_size)))
+}
+
+/// Start alignment of one partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlignmentFinding {
+    /// Position of the partition in the entry list
+    pub index: usize,
+    pub start_lba: u64,
+    /// Start falls on a 1 MiB boundary
+    pub aligned_1m: bool,
+    /// Start falls on a 4 KiB boundary
+    pub aligned_4k: bool,
+}
+
+impl AlignmentFinding {
+    /// Whether this start deserves a warning (not on a 1 MiB boundary).
+    #[inline]
+    pub fn is_warning(&self) -> bool {
+        !self.aligned_1m
+    }
+}
+
+impl core::fmt::Display for AlignmentFinding {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let level = if self.is_warning() { "warning" } else { "info" };
+        write!(
+            f,
+            "{level}: partition {} starts at LBA {} (1 MiB aligned: {}, 4K aligned: {})",
+            self.index,
+            self.start_lba,
+            if self.aligned_1m { "yes" } else { "no" },
+            if self.aligned_4k { "yes" } else { "no" }
+        )
+    }
+}
+
+/// Reports each partition's start alignment relative to 1 MiB and 4 KiB.
+#[cfg(feature = "alloc")]
+pub fn check_alignment(parts: &[gpt::GptEntry], sector_size: u64) -> Vec<AlignmentFinding> {
+    parts
+        .iter()
+        .enumerate()
+        .map(|(index, p)| {
+            let start_bytes = p.start_lba.saturating_mul(sector_size);
+            AlignmentFinding {
+                index,
+                start_lba: p.start_lba,
+                aligned_1m: start_bytes.is_multiple_of(1 << 20),
+                aligned_4k: start_bytes.is_multiple_of(4096),
+            }
+        })
+        .collect()
+}
+
+/// Reads the GPT and reports each partition's start alignment.
+///
+/// Unlike [`validate_full_disk`] this skips the protective MBR check; only an
+/// unreadable GPT is an error, misaligned partitions are just findings.
+#[cfg(feature = "alloc")]
+pub fn validate_alignment(
+    io: &mut dyn RimIO,
+    sector_size: u64,
+) -> PartResult<Vec<AlignmentFinding>> {
+    let (_, parts) = gpt::read_gpt_with_sector(io, sector_size)?;
+    Ok(check_alignment(&parts, sector_size))
+}
+
 /// Full-disk validation:
-/// - Parse & validate GPT (header + entries + overlaps/alignment already done)
+/// - Parse & validate GPT (header + entries + overlaps)
 /// - Parse MBR & validate protective entry coherent with disk size
+///
+/// Returns the partition alignment report; misaligned starts are informational
+/// and never fail validation.
 #[cfg(feature = "alloc")]
-pub fn validate_full_disk(io: &mut dyn RimIO) -> PartResult<()> {
-    // GPT (valide header + entries + overlaps/alignments via parse_gpt)
-    let (header, _parts) = gpt::read_gpt(io)?;
+pub fn validate_full_disk(io: &mut dyn RimIO) -> PartResult<Vec<AlignmentFinding>> {
+    // GPT (valide header + entries + overlaps via parse_gpt)
+    let (header, parts) = gpt::read_gpt(io)?;
 
     // Consistency of protective MBR with disk size
     let total_sectors = header.backup_lba + 1;
     let m = mbr::read_mbr(io)?;
     m.validate_protective(total_sectors)?;
 
-    Ok(())
+    Ok(check_alignment(&parts, DEFAULT_SECTOR_SIZE))
+}
+
+/// One MBR primary carried over by [`convert_mbr_to_gpt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConvertedPartition {
+    /// Slot (0..4) in the original MBR
+    pub mbr_index: usize,
+    pub type_byte: u8,
+    pub type_guid: [u8; 16],
+    /// `false` when the type byte had no GPT equivalent and fell back to
+    /// Microsoft basic data: worth a warning.
+    pub mapped: bool,
+}
+
+/// GPT type for a legacy MBR type byte, `None` when there is no equivalent.
+pub fn mbr_type_to_guid(type_byte: u8) -> Option<[u8; 16]> {
+    use crate::guids::*;
+    Some(match type_byte {
+        0xEF => GPT_PARTITION_TYPE_ESP,
+        0x01 | 0x04 | 0x06 | 0x07 | 0x0B | 0x0C | 0x0E => GPT_PARTITION_TYPE_DATA,
+        0x83 => GPT_PARTITION_TYPE_LINUX,
+        0x82 => GPT_PARTITION_TYPE_SWAP,
+        0x27 => GPT_PARTITION_TYPE_RECOVERY,
+        0xEA => GPT_PARTITION_TYPE_BOOT,
+        _ => return None,
+    })
+}
+
+/// Rewrites an MBR-partitioned disk as GPT: protective MBR (keeping the boot
+/// code), primary and backup tables.
+///
+/// Partitions keep their exact start/end LBAs. The usable range is 1 MiB
+/// aligned unless a partition lies outside it (e.g. a DOS layout starting at
+/// LBA 63), in which case it widens to everything between the entry tables.
+/// The conversion fails without writing anything when a partition overlaps
+/// the GPT structures themselves (e.g. ends in the last 33 sectors).
+/// Extended partitions are refused as their logical partitions would be
+/// lost. Active partitions get the legacy BIOS bootable attribute, and each
+/// partition gets a random unique GUID from `rng`.
+#[cfg(feature = "alloc")]
+pub fn convert_mbr_to_gpt_with_sector<IO: RimIO + ?Sized, R: crate::Rng + ?Sized>(
+    io: &mut IO,
+    sector_size: u64,
+    total_sectors: u64,
+    disk_guid: [u8; 16],
+    rng: &mut R,
+) -> PartResult<Vec<ConvertedPartition>> {
+    let old = mbr::read_mbr(io)?;
+    if old.aligned_entries().iter().any(|e| e.is_protective()) {
+        return Err(PartError::Other("MBR: disk is already GPT"));
+    }
+    // Overlaps are caught by the GPT checks below; any type byte is accepted.
+    old.validate_entries_basic()?;
+
+    let mut report = Vec::new();
+    let mut entries = Vec::new();
+    for (mbr_index, part) in old.partitions().into_iter().enumerate() {
+        let Some(part) = part else { continue };
+        if part.is_extended() {
+            return Err(MbrError::UnsupportedType { ty: part.type_byte }.into());
+        }
+        let mapped = mbr_type_to_guid(part.type_byte);
+        let type_guid = mapped.unwrap_or(crate::guids::GPT_PARTITION_TYPE_DATA);
+
+        let mut entry = gpt::GptEntry::new(
+            type_guid,
+            gpt::random_guid(rng)?,
+            part.start_lba as u64,
+            part.end_lba(),
+            0,
+            "",
+        );
+        entry.set_legacy_bios_bootable(part.bootable);
+        entries.push(entry);
+        report.push(ConvertedPartition {
+            mbr_index,
+            type_byte: part.type_byte,
+            type_guid,
+            mapped: mapped.is_some(),
+        });
+    }
+
+    let mut header = gpt::GptHeader::new(sector_size, total_sectors, disk_guid)?;
+    let entries_sectors =
+        (header.num_entries as u64 * header.entry_size as u64).div_ceil(sector_size);
+    if entries
+        .iter()
+        .any(|e| e.start_lba < header.first_usable_lba)
+    {
+        header.first_usable_lba = gpt::GPT_PRIMARY_ENTRIES_LBA + entries_sectors;
+    }
+    if entries.iter().any(|e| e.end_lba > header.last_usable_lba) {
+        header.last_usable_lba = header.backup_lba - entries_sectors - 1;
+    }
+    header.compute_crc32(&entries);
+    header.validate_entries(&entries)?;
+
+    let mut protective = mbr::Mbr::new_protective(total_sectors);
+    protective.boot_code = old.boot_code;
+    mbr::write_mbr(io, &protective)?;
+    gpt::write_gpt_with_header(io, header, &entries, sector_size)?;
+    Ok(report)
+}
+
+/// [`convert_mbr_to_gpt_with_sector`] for 512-byte sectors.
+#[cfg(feature = "alloc")]
+pub fn convert_mbr_to_gpt<IO: RimIO + ?Sized, R: crate::Rng + ?Sized>(
+    io: &mut IO,
+    total_sectors: u64,
+    disk_guid: [u8; 16],
+    rng: &mut R,
+) -> PartResult<Vec<ConvertedPartition>> {
+    convert_mbr_to_gpt_with_sector(io, DEFAULT_SECTOR_SIZE, total_sectors, disk_guid, rng)
 }
 
 #[cfg(test)]
@@ -112,4 +354,222 @@ mod tests {
         let mut io = MemRimIO::new(&mut buf);
         assert!(detect_partition_offset_by_type_guid(&mut io, &[0u8; 16]).is_err());
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn offset_by_name_finds_first_exact_match() {
+        use crate::guids::*;
+
+        let total = 20_000;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let esp = gpt::GptEntry::new(GPT_PARTITION_TYPE_ESP, [2; 16], 2048, 4095, 0, "ESP");
+        let root = gpt::GptEntry::new(GPT_PARTITION_TYPE_LINUX, [3; 16], 4096, 8191, 0, "rootfs");
+        let dup = gpt::GptEntry::new(GPT_PARTITION_TYPE_LINUX, [4; 16], 8192, 9999, 0, "rootfs");
+        mbr::write_mbr_protective(&mut io, total).unwrap();
+        gpt::write_gpt_from_entries(&mut io, &[esp, root, dup], total, [0xAB; 16]).unwrap();
+
+        assert_eq!(
+            detect_partition_offset_by_name(&mut io, "rootfs", 512).unwrap(),
+            Some(4096 * 512)
+        );
+        assert_eq!(
+            detect_partition_offset_by_name(&mut io, "ESP", 512).unwrap(),
+            Some(2048 * 512)
+        );
+        assert_eq!(
+            detect_partition_offset_by_name(&mut io, "esp", 512).unwrap(),
+            None
+        );
+        assert_eq!(
+            detect_partition_offset_by_name(&mut io, "root", 512).unwrap(),
+            None
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn alignment_warns_only_on_misaligned() {
+        let total = 20_000;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let aligned = gpt::GptEntry::new([1; 16], [2; 16], 2048, 4095, 0, "aligned");
+        let misaligned = gpt::GptEntry::new([1; 16], [3; 16], 4097, 8191, 0, "misaligned");
+        mbr::write_mbr_protective(&mut io, total).unwrap();
+        gpt::write_gpt_from_entries(&mut io, &[aligned, misaligned], total, [0xAB; 16]).unwrap();
+
+        let report = validate_full_disk(&mut io).unwrap();
+        assert_eq!(report.len(), 2);
+        assert!(report[0].aligned_1m && report[0].aligned_4k);
+        assert!(!report[0].is_warning());
+        assert!(!report[1].aligned_1m && !report[1].aligned_4k);
+        assert!(report[1].is_warning());
+        assert_eq!(report[1].start_lba, 4097);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn validate_alignment_on_4k_sectors() {
+        let (ss, total) = (4096u64, 4096u64);
+        let mut buf = vec![0u8; (ss * total) as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let one_mib = gpt::align_lba_1m(ss);
+        let aligned = gpt::GptEntry::new([1; 16], [2; 16], one_mib, 2 * one_mib - 1, 0, "a");
+        let misaligned =
+            gpt::GptEntry::new([1; 16], [3; 16], 2 * one_mib + 8, 4 * one_mib - 1, 0, "b");
+        gpt::write_gpt_from_entries_with_sector(
+            &mut io,
+            &[aligned, misaligned],
+            ss,
+            total,
+            [0xAB; 16],
+        )
+        .unwrap();
+
+        let report = validate_alignment(&mut io, ss).unwrap();
+        assert_eq!(report.len(), 2);
+        assert!(!report[0].is_warning());
+        assert_eq!(report[1].index, 1);
+        assert_eq!(report[1].start_lba, 2 * one_mib + 8);
+        assert!(report[1].is_warning() && report[1].aligned_4k);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn duplicate_unique_guids_are_flagged() {
+        let total = 20_000;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let a = gpt::GptEntry::new([1; 16], [7; 16], 2048, 4095, 0, "a");
+        let b = gpt::GptEntry::new([1; 16], [7; 16], 4096, 8191, 0, "b");
+        mbr::write_mbr_protective(&mut io, total).unwrap();
+        gpt::write_gpt_from_entries(&mut io, &[a, b], total, [0xAB; 16]).unwrap();
+
+        assert_eq!(
+            validate_full_disk(&mut io).unwrap_err(),
+            PartError::Gpt(GptError::DuplicateUniqueGuid { a: 0, b: 1 })
+        );
+        let mut reader =
+            crate::gpt_stream::GptStreamReader::<_, 512>::new(&mut io, DEFAULT_SECTOR_SIZE)
+                .unwrap();
+        assert!(reader.validate_unique_guids().is_err());
+
+        // Same check against the disk GUID.
+        let c = gpt::GptEntry::new([1; 16], [0xAB; 16], 4096, 8191, 0, "c");
+        gpt::write_gpt_from_entries(&mut io, &[a, c], total, [0xAB; 16]).unwrap();
+        assert_eq!(
+            validate_full_disk(&mut io).unwrap_err(),
+            PartError::Gpt(GptError::DiskGuidCollision { index: 1 })
+        );
+    }
+
+    #[test]
+    fn chs_clamp_and_roundtrip() {
+        let (h, s) = (CHS_HEADS, CHS_SECTORS_PER_TRACK);
+        assert_eq!(lba_to_chs(0, h, s), [0x00, 0x01, 0x00]);
+        assert_eq!(lba_to_chs(2048, h, s), [0x20, 0x21, 0x00]);
+        // First LBA past cylinder 1023 and anything beyond clamp.
+        assert_eq!(lba_to_chs(1024 * 255 * 63, h, s), CHS_MAX);
+        assert_eq!(lba_to_chs(u64::MAX, h, s), CHS_MAX);
+        assert_eq!(chs_to_lba(CHS_MAX, h, s), Some(1024 * 255 * 63 - 1));
+
+        for lba in (0..1024 * 255 * 63).step_by(12_345).chain([16_450_559]) {
+            assert_eq!(chs_to_lba(lba_to_chs(lba, h, s), h, s), Some(lba));
+        }
+        // Small geometry, as used by some tiny images.
+        for lba in 0..2048 {
+            assert_eq!(chs_to_lba(lba_to_chs(lba, 16, 32), 16, 32), Some(lba));
+        }
+
+        assert_eq!(chs_to_lba([0, 0, 0], h, s), None);
+        assert_eq!(chs_to_lba([0xFF, 0x01, 0x00], h, s), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn convert_legacy_mbr() {
+        let mut rng = crate::rng::SplitMix64::new(7);
+        let total = 20_000u64;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+
+        let mut entries = [mbr::MbrEntry::new_empty(); 4];
+        entries[0] = mbr::MbrEntry::new(0x80, [0; 3], 0x0C, [0; 3], 2048, 4096);
+        entries[1] = mbr::MbrEntry::new(0x00, [0; 3], 0xA5, [0; 3], 6144, 8192);
+        entries[2] = mbr::MbrEntry::new(0x00, [0; 3], 0xEF, [0; 3], 14336, 2048);
+        mbr::write_mbr_from_entries(&mut io, entries).unwrap();
+        io.write_at(0, &[0x90; 16]).unwrap();
+
+        let report = convert_mbr_to_gpt(&mut io, total, [0xAB; 16], &mut rng).unwrap();
+        assert_eq!(report.len(), 3);
+        assert!(report[0].mapped);
+        assert_eq!(report[0].type_guid, crate::guids::GPT_PARTITION_TYPE_DATA);
+        // FreeBSD has no table entry: basic data, flagged as unmapped.
+        assert_eq!((report[1].type_byte, report[1].mapped), (0xA5, false));
+        assert_eq!(report[2].type_guid, crate::guids::GPT_PARTITION_TYPE_ESP);
+
+        let (_, parts) = gpt::read_gpt(&mut io).unwrap();
+        assert_eq!(parts.len(), 3);
+        assert_eq!((parts[0].start_lba, parts[0].end_lba), (2048, 6143));
+        assert_eq!((parts[1].start_lba, parts[1].end_lba), (6144, 14335));
+        assert!(parts[0].is_legacy_bios_bootable());
+        assert!(!parts[1].is_legacy_bios_bootable());
+        assert_ne!(parts[0].unique_guid, parts[1].unique_guid);
+
+        let m = mbr::read_mbr(&mut io).unwrap();
+        m.validate_protective(total).unwrap();
+        assert_eq!(m.boot_code[..16], [0x90; 16]);
+        validate_full_disk(&mut io).unwrap();
+
+        // Converting again is refused.
+        assert!(convert_mbr_to_gpt(&mut io, total, [0xAB; 16], &mut rng).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn convert_refuses_partition_over_backup_gpt() {
+        let mut rng = crate::rng::SplitMix64::new(7);
+        let total = 20_000u64;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        let mut entries = [mbr::MbrEntry::new_empty(); 4];
+        entries[0] = mbr::MbrEntry::new(0x00, [0; 3], 0x83, [0; 3], 2048, total as u32 - 2048);
+        mbr::write_mbr_from_entries(&mut io, entries).unwrap();
+
+        assert!(matches!(
+            convert_mbr_to_gpt(&mut io, total, [0xAB; 16], &mut rng),
+            Err(PartError::Gpt(GptError::EntryOutOfBounds { .. }))
+        ));
+        // Nothing was written.
+        assert_eq!(mbr::read_mbr(&mut io).unwrap().kind(), mbr::MbrKind::Legacy);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn convert_keeps_dos_lba_63_start() {
+        let total = 20_000u64;
+        let mut buf = vec![0u8; 512 * total as usize];
+        let mut io = MemRimIO::new(&mut buf);
+        let mut rng = crate::rng::SplitMix64::new(7);
+        let mut entries = [mbr::MbrEntry::new_empty(); 4];
+        entries[0] = mbr::MbrEntry::new(0x80, [0; 3], 0x06, [0; 3], 63, 4032);
+        entries[1] = mbr::MbrEntry::new(0x00, [0; 3], 0x83, [0; 3], 4095, 8190);
+        mbr::write_mbr_from_entries(&mut io, entries).unwrap();
+
+        convert_mbr_to_gpt(&mut io, total, [0xAB; 16], &mut rng).unwrap();
+
+        let (header, parts) = gpt::read_gpt(&mut io).unwrap();
+        assert_eq!(header.first_usable_lba, 34);
+        assert_eq!((parts[0].start_lba, parts[0].end_lba), (63, 4094));
+        assert_eq!((parts[1].start_lba, parts[1].end_lba), (4095, 12284));
+        for p in &parts {
+            assert_ne!(p.unique_guid, header.disk_guid);
+        }
+        assert_ne!(parts[0].unique_guid, parts[1].unique_guid);
+        validate_full_disk(&mut io).unwrap();
+    }
 }